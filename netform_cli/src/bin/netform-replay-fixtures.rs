@@ -1,22 +1,21 @@
 use std::fs;
 use std::path::Path;
 
-use netform_dialect_eos::parse_eos;
-use netform_dialect_iosxe::parse_iosxe;
-use netform_dialect_junos::parse_junos;
-use netform_diff::{NormalizeOptions, OrderPolicyConfig, diff_documents};
-use netform_ir::{Document, parse_generic};
+use netform_cli::dialect_registry;
+use netform_diff::{DiffAlgorithm, NormalizeOptions, OrderPolicyConfig, diff_documents};
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
 struct Fixture {
     name: String,
-    #[serde(default)]
-    dialect: FixtureDialect,
+    #[serde(default = "default_dialect")]
+    dialect: String,
     intended: String,
     actual: String,
     normalization_steps: Vec<netform_diff::NormalizationStep>,
     order_policy: OrderPolicyConfig,
+    #[serde(default)]
+    algorithm: DiffAlgorithm,
     expected: Expected,
 }
 
@@ -27,14 +26,8 @@ struct Expected {
     finding_codes: Vec<String>,
 }
 
-#[derive(Debug, Clone, Copy, Default, Deserialize)]
-#[serde(rename_all = "kebab-case")]
-enum FixtureDialect {
-    #[default]
-    Generic,
-    Eos,
-    Iosxe,
-    Junos,
+fn default_dialect() -> String {
+    "generic".to_string()
 }
 
 fn edit_type_name(edit: &netform_diff::Edit) -> &'static str {
@@ -42,6 +35,7 @@ fn edit_type_name(edit: &netform_diff::Edit) -> &'static str {
         netform_diff::Edit::Insert { .. } => "Insert",
         netform_diff::Edit::Delete { .. } => "Delete",
         netform_diff::Edit::Replace { .. } => "Replace",
+        netform_diff::Edit::Move { .. } => "Move",
     }
 }
 
@@ -52,6 +46,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut entries = fs::read_dir(&fixtures_dir)?.collect::<Result<Vec<_>, _>>()?;
     entries.sort_by_key(|e| e.path());
 
+    let registry = dialect_registry();
     let mut checked = 0usize;
     for entry in entries {
         let path = entry.path();
@@ -65,11 +60,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         let raw = fs::read_to_string(&path)?;
         let fixture: Fixture = serde_json::from_str(&raw)?;
 
-        let intended = parse_config(&fixture.intended, fixture.dialect);
-        let actual = parse_config(&fixture.actual, fixture.dialect);
+        let dialect = registry
+            .resolve(&fixture.dialect)
+            .map_err(|err| format!("fixture {}: {err}", fixture.name))?;
+        let intended = dialect.parse_document(&fixture.intended);
+        let actual = dialect.parse_document(&fixture.actual);
 
         let options = NormalizeOptions::new(fixture.normalization_steps)
-            .with_order_policy(fixture.order_policy);
+            .with_order_policy(fixture.order_policy)
+            .with_algorithm(fixture.algorithm);
         let diff = diff_documents(&intended, &actual, options);
 
         if diff.has_changes != fixture.expected.has_changes {
@@ -113,12 +112,3 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("replayed {checked} fixture(s)");
     Ok(())
 }
-
-fn parse_config(input: &str, dialect: FixtureDialect) -> Document {
-    match dialect {
-        FixtureDialect::Generic => parse_generic(input),
-        FixtureDialect::Eos => parse_eos(input),
-        FixtureDialect::Iosxe => parse_iosxe(input),
-        FixtureDialect::Junos => parse_junos(input),
-    }
-}
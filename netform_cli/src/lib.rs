@@ -0,0 +1,19 @@
+//! Shared setup for the `config-diff` and `netform-replay-fixtures` binaries.
+
+use netform_ir::DialectRegistry;
+
+/// Registry holding `generic` plus every dialect crate bundled with the cli.
+pub fn dialect_registry() -> DialectRegistry {
+    let mut registry = DialectRegistry::new();
+    netform_dialect_asa::register(&mut registry);
+    netform_dialect_eos::register(&mut registry);
+    netform_dialect_fortios::register(&mut registry);
+    netform_dialect_iosxe::register(&mut registry);
+    netform_dialect_iosxr::register(&mut registry);
+    netform_dialect_junos::register(&mut registry);
+    netform_dialect_nxos::register(&mut registry);
+    netform_dialect_routeros::register(&mut registry);
+    netform_dialect_sros::register(&mut registry);
+    netform_dialect_vrp::register(&mut registry);
+    registry
+}
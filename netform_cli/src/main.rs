@@ -1,22 +1,41 @@
+use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
 
-use clap::{Parser, ValueEnum};
-use netform_dialect_eos::parse_eos;
-use netform_dialect_iosxe::parse_iosxe;
-use netform_dialect_junos::parse_junos;
+use clap::{Parser, Subcommand, ValueEnum};
+use netform_cli::dialect_registry;
+use netform_dialect_rules::RuleDialect;
 use netform_diff::{
-    NormalizationStep, NormalizeOptions, OrderPolicy, OrderPolicyConfig, build_plan,
-    diff_documents, format_markdown_report,
+    DiffAlgorithm, Finding, FindingLevel, FleetDevice, FleetReference, InvalidPatternError,
+    LinePattern, MergeConflict, NormalizationStep, NormalizeOptions, OrderPolicy,
+    OrderPolicyConfig, StepScope, build_plan, diff_documents, format_fleet_report,
+    format_markdown_report, merge3, summarize_fleet,
 };
-use netform_ir::{Document, parse_generic};
+use netform_ir::{
+    DialectDetection, DialectHint, DialectRegistry, Document, Node, Selector, UnknownDialectError,
+    detect_dialect, glob_match,
+};
+use serde::Serialize;
+
+/// `--dialect` value that detects each file's dialect from its content.
+const AUTO_DIALECT: &str = "auto";
+
+/// Registry name of the built-in generic dialect, the detection fallback.
+const GENERIC_DIALECT: &str = "generic";
 
 #[derive(Debug, Parser)]
 #[command(name = "config-diff")]
 #[command(about = "Compare two config files and print a drift report")]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
-    file_a: PathBuf,
-    file_b: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(required = true)]
+    file_a: Option<PathBuf>,
+    #[arg(required = true)]
+    file_b: Option<PathBuf>,
 
     #[arg(long)]
     json: bool,
@@ -24,6 +43,21 @@ struct Cli {
     #[arg(long)]
     plan_json: bool,
 
+    #[command(flatten)]
+    compare: CompareArgs,
+
+    /// Registered dialect name, or `auto` to detect it per file.
+    #[arg(long, default_value = "generic", value_parser = parse_dialect_name)]
+    dialect: String,
+
+    /// Parse with a declarative dialect rule file (`.toml`, `.yaml`, or `.yml`).
+    #[arg(long, value_name = "PATH", conflicts_with = "dialect")]
+    dialect_file: Option<PathBuf>,
+}
+
+/// Normalization and ordering flags shared by every diffing mode.
+#[derive(Debug, clap::Args)]
+struct CompareArgs {
     #[arg(long)]
     ignore_comments: bool,
 
@@ -33,11 +67,128 @@ struct Cli {
     #[arg(long)]
     normalize_whitespace: bool,
 
+    /// Drop lines matching a regex; repeatable.
+    #[arg(long, value_name = "REGEX", value_parser = parse_line_pattern)]
+    ignore_matching: Vec<LinePattern>,
+
+    /// Rewrite regex matches before comparing (`$1` expands groups); repeatable.
+    #[arg(long, num_args = 2, value_names = ["REGEX", "REPLACEMENT"])]
+    rewrite: Vec<String>,
+
+    /// Compare secret values only by presence and redact them in output.
+    #[arg(long, conflicts_with = "hash_secrets")]
+    mask_secrets: bool,
+
+    /// Compare secret values by digest and redact them in output.
+    #[arg(long)]
+    hash_secrets: bool,
+
+    /// Compare abbreviated interface names (`Gi0/1`) by their full spelling.
+    #[arg(long)]
+    canonicalize_interfaces: bool,
+
     #[arg(long, value_enum, default_value_t = CliOrderPolicy::Ordered)]
     order_policy: CliOrderPolicy,
 
-    #[arg(long, value_enum, default_value_t = CliDialect::Generic)]
-    dialect: CliDialect,
+    /// Alignment algorithm for ordered comparisons.
+    #[arg(long, value_enum, default_value_t = CliAlgorithm::Myers)]
+    algorithm: CliAlgorithm,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Select config lines/blocks with a selector expression.
+    Query(QueryArgs),
+    /// Three-way merge two configs derived from a common base.
+    Merge(MergeArgs),
+    /// Compare one reference config against many device configs.
+    Fleet(FleetArgs),
+}
+
+#[derive(Debug, clap::Args)]
+struct QueryArgs {
+    /// Selector, for example `interface * > shutdown` or `router bgp * >> neighbor`.
+    selector: String,
+    file: PathBuf,
+
+    #[arg(long)]
+    json: bool,
+
+    /// Registered dialect name, or `auto` to detect it per file.
+    #[arg(long, default_value = "generic", value_parser = parse_dialect_name)]
+    dialect: String,
+
+    /// Parse with a declarative dialect rule file (`.toml`, `.yaml`, or `.yml`).
+    #[arg(long, value_name = "PATH", conflicts_with = "dialect")]
+    dialect_file: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Args)]
+struct MergeArgs {
+    base: PathBuf,
+    ours: PathBuf,
+    theirs: PathBuf,
+
+    /// Print the merged text and conflicts as JSON instead of conflict markers.
+    #[arg(long)]
+    json: bool,
+
+    /// Alignment algorithm for sibling lines.
+    #[arg(long, value_enum, default_value_t = CliAlgorithm::Myers)]
+    algorithm: CliAlgorithm,
+
+    /// Registered dialect name, or `auto` to detect it per file.
+    #[arg(long, default_value = "generic", value_parser = parse_dialect_name)]
+    dialect: String,
+
+    /// Parse with a declarative dialect rule file (`.toml`, `.yaml`, or `.yml`).
+    #[arg(long, value_name = "PATH", conflicts_with = "dialect")]
+    dialect_file: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Args)]
+struct FleetArgs {
+    /// Reference (golden) config every device is compared against.
+    reference: PathBuf,
+
+    /// Device configs: files, directories (every file inside), or `*`/`?`
+    /// globs over file names.
+    #[arg(required = true)]
+    devices: Vec<String>,
+
+    /// Print the fleet summary as JSON instead of markdown.
+    #[arg(long)]
+    json: bool,
+
+    /// Write each device's `diff.json` to `<DIR>/<device>/diff.json`.
+    #[arg(long, value_name = "DIR")]
+    out_dir: Option<PathBuf>,
+
+    #[command(flatten)]
+    compare: CompareArgs,
+
+    /// Registered dialect name, or `auto` to detect it per file.
+    #[arg(long, default_value = "generic", value_parser = parse_dialect_name)]
+    dialect: String,
+
+    /// Parse with a declarative dialect rule file (`.toml`, `.yaml`, or `.yml`).
+    #[arg(long, value_name = "PATH", conflicts_with = "dialect")]
+    dialect_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+struct MergeOutput<'a> {
+    merged: String,
+    has_conflicts: bool,
+    conflicts: &'a [MergeConflict],
+}
+
+#[derive(Debug, Serialize)]
+struct QueryMatch {
+    path: netform_ir::Path,
+    line: usize,
+    text: String,
+    key_hint: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -48,43 +199,96 @@ enum CliOrderPolicy {
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
-enum CliDialect {
-    Generic,
-    Eos,
-    Iosxe,
-    Junos,
+enum CliAlgorithm {
+    Myers,
+    Patience,
+    Histogram,
+}
+
+impl From<CliAlgorithm> for DiffAlgorithm {
+    fn from(algorithm: CliAlgorithm) -> Self {
+        match algorithm {
+            CliAlgorithm::Myers => DiffAlgorithm::Myers,
+            CliAlgorithm::Patience => DiffAlgorithm::Patience,
+            CliAlgorithm::Histogram => DiffAlgorithm::Histogram,
+        }
+    }
+}
+
+impl CompareArgs {
+    fn normalize_options(&self) -> Result<NormalizeOptions, InvalidPatternError> {
+        let mut steps = Vec::new();
+        if self.ignore_comments {
+            steps.push(NormalizationStep::IgnoreComments);
+        }
+        if self.ignore_blank_lines {
+            steps.push(NormalizationStep::IgnoreBlankLines);
+        }
+        if self.normalize_whitespace {
+            steps.push(NormalizationStep::CollapseInternalWhitespace);
+        }
+        for pattern in &self.ignore_matching {
+            steps.push(NormalizationStep::IgnoreMatching {
+                pattern: pattern.clone(),
+                scope: StepScope::default(),
+            });
+        }
+        for pair in self.rewrite.chunks_exact(2) {
+            steps.push(NormalizationStep::Rewrite {
+                pattern: LinePattern::new(&pair[0])?,
+                replacement: pair[1].clone(),
+                scope: StepScope::default(),
+            });
+        }
+        if self.mask_secrets {
+            steps.push(NormalizationStep::MaskSecrets);
+        }
+        if self.hash_secrets {
+            steps.push(NormalizationStep::HashSecrets);
+        }
+        if self.canonicalize_interfaces {
+            steps.push(NormalizationStep::CanonicalizeInterfaces);
+        }
+        let policy = match self.order_policy {
+            CliOrderPolicy::Ordered => OrderPolicy::Ordered,
+            CliOrderPolicy::Unordered => OrderPolicy::Unordered,
+            CliOrderPolicy::KeyedStable => OrderPolicy::KeyedStable,
+        };
+        Ok(NormalizeOptions::new(steps)
+            .with_order_policy(OrderPolicyConfig {
+                default: policy,
+                overrides: Vec::new(),
+            })
+            .with_algorithm(self.algorithm.into()))
+    }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
-    let a_text = fs::read_to_string(&cli.file_a)?;
-    let b_text = fs::read_to_string(&cli.file_b)?;
+    match &cli.command {
+        Some(Command::Query(args)) => return run_query(args),
+        Some(Command::Merge(args)) => return run_merge(args),
+        Some(Command::Fleet(args)) => return run_fleet(args),
+        None => {}
+    }
 
-    let a_doc = parse_config(&a_text, cli.dialect);
-    let b_doc = parse_config(&b_text, cli.dialect);
+    let file_a = cli.file_a.clone().expect("required by clap");
+    let file_b = cli.file_b.clone().expect("required by clap");
+    let a_text = fs::read_to_string(&file_a)?;
+    let b_text = fs::read_to_string(&file_b)?;
 
-    let mut steps = Vec::new();
-    if cli.ignore_comments {
-        steps.push(NormalizationStep::IgnoreComments);
-    }
-    if cli.ignore_blank_lines {
-        steps.push(NormalizationStep::IgnoreBlankLines);
-    }
-    if cli.normalize_whitespace {
-        steps.push(NormalizationStep::CollapseInternalWhitespace);
-    }
-    let policy = match cli.order_policy {
-        CliOrderPolicy::Ordered => OrderPolicy::Ordered,
-        CliOrderPolicy::Unordered => OrderPolicy::Unordered,
-        CliOrderPolicy::KeyedStable => OrderPolicy::KeyedStable,
-    };
-    let options = NormalizeOptions::new(steps).with_order_policy(OrderPolicyConfig {
-        default: policy,
-        overrides: Vec::new(),
-    });
+    let mut registry = dialect_registry();
+    let dialect = select_dialect(&mut registry, &cli.dialect, cli.dialect_file.as_deref())?;
+    let (a_doc, a_detection) = parse_config(&registry, &a_text, &dialect)?;
+    let (b_doc, b_detection) = parse_config(&registry, &b_text, &dialect)?;
 
-    let diff = diff_documents(&a_doc, &b_doc, options);
+    let options = cli.compare.normalize_options()?;
+
+    let mut diff = diff_documents(&a_doc, &b_doc, options);
+    if let (Some(a), Some(b)) = (&a_detection, &b_detection) {
+        diff.findings.extend(detection_findings(a, b));
+    }
 
     if cli.plan_json {
         let plan = build_plan(&diff);
@@ -96,8 +300,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "{}",
             format_markdown_report(
                 &diff,
-                &cli.file_a.display().to_string(),
-                &cli.file_b.display().to_string(),
+                &file_a.display().to_string(),
+                &file_b.display().to_string(),
             )
         );
     }
@@ -105,11 +309,315 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn parse_config(input: &str, dialect: CliDialect) -> Document {
-    match dialect {
-        CliDialect::Generic => parse_generic(input),
-        CliDialect::Eos => parse_eos(input),
-        CliDialect::Iosxe => parse_iosxe(input),
-        CliDialect::Junos => parse_junos(input),
+fn run_query(args: &QueryArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let selector = Selector::parse(&args.selector)?;
+    let text = fs::read_to_string(&args.file)?;
+    let mut registry = dialect_registry();
+    let dialect = select_dialect(&mut registry, &args.dialect, args.dialect_file.as_deref())?;
+    let (doc, _) = parse_config(&registry, &text, &dialect)?;
+
+    let matches = doc
+        .select(&selector)
+        .into_iter()
+        .filter_map(|m| {
+            let line = match doc.node(m.node)? {
+                Node::Line(line) => line,
+                Node::Block(block) => &block.header,
+            };
+            Some(QueryMatch {
+                path: m.path,
+                line: line.span.line,
+                text: line.raw.clone(),
+                key_hint: line.key_hint.clone(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&matches)?);
+    } else {
+        for m in &matches {
+            let path = m
+                .path
+                .0
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(".");
+            println!("{}:{} [{}] {}", args.file.display(), m.line, path, m.text);
+        }
+    }
+
+    Ok(())
+}
+
+fn run_merge(args: &MergeArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let mut registry = dialect_registry();
+    let dialect = select_dialect(&mut registry, &args.dialect, args.dialect_file.as_deref())?;
+    let mut docs = Vec::new();
+    for file in [&args.base, &args.ours, &args.theirs] {
+        let (doc, _) = parse_config(&registry, &fs::read_to_string(file)?, &dialect)?;
+        docs.push(doc);
+    }
+
+    let options = NormalizeOptions::default().with_algorithm(args.algorithm.into());
+    let result = merge3(&docs[0], &docs[1], &docs[2], options);
+
+    if args.json {
+        let output = MergeOutput {
+            merged: result.merged.render(),
+            has_conflicts: result.has_conflicts(),
+            conflicts: &result.conflicts,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        print!("{}", result.render_with_markers());
+    }
+
+    if result.has_conflicts() {
+        // Like `git merge-file`, report conflicts through the exit status.
+        std::io::stdout().flush()?;
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_fleet(args: &FleetArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let mut registry = dialect_registry();
+    let dialect = select_dialect(&mut registry, &args.dialect, args.dialect_file.as_deref())?;
+    let (reference_doc, reference_detection) =
+        parse_config(&registry, &fs::read_to_string(&args.reference)?, &dialect)?;
+    let reference = FleetReference::new(&reference_doc, args.compare.normalize_options()?);
+
+    let paths = expand_device_paths(&args.devices)?;
+    let mut devices = Vec::new();
+    for (path, name) in paths.iter().zip(device_names(&paths)) {
+        let (doc, detection) = parse_config(&registry, &fs::read_to_string(path)?, &dialect)?;
+        let mut diff = reference.diff(&doc);
+        if let (Some(a), Some(b)) = (&reference_detection, &detection) {
+            diff.findings.extend(detection_findings(a, b));
+        }
+        devices.push(FleetDevice { name, diff });
+    }
+
+    if let Some(out_dir) = &args.out_dir {
+        for device in &devices {
+            let dir = out_dir.join(&device.name);
+            fs::create_dir_all(&dir)?;
+            fs::write(
+                dir.join("diff.json"),
+                serde_json::to_string_pretty(&device.diff)?,
+            )?;
+        }
+    }
+
+    let summary = summarize_fleet(&devices);
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+    } else {
+        println!(
+            "{}",
+            format_fleet_report(&summary, &args.reference.display().to_string())
+        );
+    }
+
+    Ok(())
+}
+
+/// Unique device names: file names, or relative paths below the devices'
+/// common directory when file names repeat.
+fn device_names(paths: &[PathBuf]) -> Vec<String> {
+    let file_names = paths
+        .iter()
+        .map(|path| {
+            path.file_name().map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            )
+        })
+        .collect::<Vec<_>>();
+    if file_names.iter().collect::<HashSet<_>>().len() == file_names.len() {
+        return file_names;
+    }
+
+    let mut common = paths
+        .first()
+        .map(|path| parent_components(path))
+        .unwrap_or_default();
+    for path in paths {
+        let dir = parent_components(path);
+        let shared = common.iter().zip(&dir).take_while(|(a, b)| a == b).count();
+        common.truncate(shared);
+    }
+
+    // Only normal components, so names stay inside `--out-dir`.
+    let mut seen = HashSet::new();
+    paths
+        .iter()
+        .map(|path| {
+            let base = path
+                .components()
+                .skip(common.len())
+                .filter_map(|part| match part {
+                    Component::Normal(part) => Some(part.to_string_lossy()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+            let mut name = base.clone();
+            let mut copy = 1usize;
+            while !seen.insert(name.clone()) {
+                copy += 1;
+                name = format!("{base}~{copy}");
+            }
+            name
+        })
+        .collect()
+}
+
+fn parent_components(path: &Path) -> Vec<Component<'_>> {
+    path.parent()
+        .map(|dir| dir.components().collect())
+        .unwrap_or_default()
+}
+
+/// Expand fleet device arguments into a sorted, de-duplicated file list.
+fn expand_device_paths(targets: &[String]) -> std::io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for target in targets {
+        let path = Path::new(target);
+        let file_name = path.file_name().map(|name| name.to_string_lossy());
+        if let Some(pattern) = file_name.filter(|name| name.contains(['*', '?'])) {
+            let dir = path
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .unwrap_or(Path::new("."));
+            for entry in fs::read_dir(dir)? {
+                let entry = entry?;
+                if entry.file_type()?.is_file()
+                    && glob_match(&pattern, &entry.file_name().to_string_lossy())
+                {
+                    paths.push(entry.path());
+                }
+            }
+        } else if path.is_dir() {
+            for entry in fs::read_dir(path)? {
+                let entry = entry?;
+                if entry.file_type()?.is_file() {
+                    paths.push(entry.path());
+                }
+            }
+        } else {
+            paths.push(path.to_path_buf());
+        }
+    }
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+fn parse_line_pattern(pattern: &str) -> Result<LinePattern, InvalidPatternError> {
+    LinePattern::new(pattern)
+}
+
+/// Accept `auto` or any name in [`dialect_registry`].
+fn parse_dialect_name(name: &str) -> Result<String, UnknownDialectError> {
+    if name == AUTO_DIALECT {
+        return Ok(name.to_string());
+    }
+    dialect_registry().resolve(name)?;
+    Ok(name.to_string())
+}
+
+/// Name of the dialect to parse with: the `--dialect-file` rules, registered
+/// under their own name, or the `--dialect` value.
+///
+/// Fails with the validation error if the rule file is unusable.
+fn select_dialect(
+    registry: &mut DialectRegistry,
+    dialect: &str,
+    file: Option<&Path>,
+) -> Result<String, String> {
+    let Some(path) = file else {
+        return Ok(dialect.to_string());
+    };
+    let rules = RuleDialect::load(path).map_err(|err| err.to_string())?;
+    let name = rules.rules().name.clone();
+    registry.register(name.clone(), Box::new(rules));
+    Ok(name)
+}
+
+/// Resolve `auto` to a registered dialect name, returning the detection it used.
+fn resolve_dialect(
+    registry: &DialectRegistry,
+    input: &str,
+    dialect: &str,
+) -> (String, Option<DialectDetection>) {
+    if dialect != AUTO_DIALECT {
+        return (dialect.to_string(), None);
+    }
+    let detection = detect_dialect(input);
+    let resolved = detection
+        .best()
+        .map(|guess| guess.name.as_str())
+        .filter(|name| registry.contains(name))
+        .unwrap_or(GENERIC_DIALECT)
+        .to_string();
+    (resolved, Some(detection))
+}
+
+/// Warnings for uncertain detections and for sides detected as different dialects.
+fn detection_findings(a: &DialectDetection, b: &DialectDetection) -> Vec<Finding> {
+    let mut out = Vec::new();
+    for (side, detection) in [("left", a), ("right", b)] {
+        if detection.is_confident() {
+            continue;
+        }
+        let Some(best) = detection.best() else {
+            continue;
+        };
+        out.push(dialect_finding(format!(
+            "{side} dialect detection is uncertain: guessed `{}` with confidence {:.2}",
+            best.name, best.confidence
+        )));
+    }
+
+    if let (Some(a_best), Some(b_best)) = (a.best(), b.best())
+        && a_best.name != b_best.name
+    {
+        out.push(dialect_finding(format!(
+            "sides were detected as different dialects: left `{}`, right `{}`",
+            a_best.name, b_best.name
+        )));
+    }
+    out
+}
+
+fn dialect_finding(message: String) -> Finding {
+    Finding {
+        code: "dialect_uncertain".to_string(),
+        level: FindingLevel::Warning,
+        message,
+        path: None,
+        span: None,
+    }
+}
+
+/// Parse `input` with a registered dialect, resolving `auto` first and
+/// recording the detected dialect.
+fn parse_config(
+    registry: &DialectRegistry,
+    input: &str,
+    dialect: &str,
+) -> Result<(Document, Option<DialectDetection>), UnknownDialectError> {
+    let (dialect, detection) = resolve_dialect(registry, input, dialect);
+    let mut doc = registry.parse(&dialect, input)?;
+    if detection.is_some() {
+        doc.metadata.dialect_hint = if dialect == GENERIC_DIALECT {
+            DialectHint::Generic
+        } else {
+            DialectHint::Named(dialect)
+        };
     }
+    Ok((doc, detection))
 }
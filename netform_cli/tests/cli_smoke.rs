@@ -91,6 +91,501 @@ fn config_diff_cli_accepts_dialect_flag() {
     assert_eq!(diff_json["has_changes"], true);
 }
 
+#[test]
+fn config_diff_cli_records_selected_algorithm() {
+    let left = temp_file_path("left-algorithm");
+    let right = temp_file_path("right-algorithm");
+    fs::write(&left, "hostname r1\n!\nntp server 10.0.0.1\n!\n").expect("write left");
+    fs::write(&right, "hostname r1\n!\nntp server 10.0.0.2\n!\n").expect("write right");
+
+    for algorithm in ["myers", "patience", "histogram"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+            .arg("--algorithm")
+            .arg(algorithm)
+            .arg("--json")
+            .arg(&left)
+            .arg(&right)
+            .output()
+            .expect("run config-diff --algorithm");
+
+        assert!(output.status.success());
+        let diff_json: serde_json::Value =
+            serde_json::from_slice(&output.stdout).expect("valid json");
+        assert_eq!(diff_json["algorithm"], algorithm);
+        assert_eq!(diff_json["edits"].as_array().map(Vec::len), Some(1));
+    }
+}
+
+#[test]
+fn config_diff_cli_applies_pattern_normalization_flags() {
+    let left = temp_file_path("left-patterns");
+    let right = temp_file_path("right-patterns");
+    fs::write(
+        &left,
+        "! Last configuration change at 10:00:00 UTC\nntp clock-period 17208\nhostname edge-1\n",
+    )
+    .expect("write left");
+    fs::write(
+        &right,
+        "! Last configuration change at 12:30:00 UTC\nntp clock-period 36029\nhostname edge-1\n",
+    )
+    .expect("write right");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("--json")
+        .arg("--ignore-matching")
+        .arg("^! Last configuration change")
+        .arg("--rewrite")
+        .arg(r"^(ntp clock-period) \d+$")
+        .arg("$1 <volatile>")
+        .arg(&left)
+        .arg(&right)
+        .output()
+        .expect("run config-diff");
+    assert!(output.status.success());
+    let diff: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    assert_eq!(diff["has_changes"], false);
+    assert_eq!(
+        diff["normalization_steps"][1]["rewrite"]["replacement"],
+        "$1 <volatile>"
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("--ignore-matching")
+        .arg("(unclosed")
+        .arg(&left)
+        .arg(&right)
+        .output()
+        .expect("run config-diff");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid pattern"));
+}
+
+#[test]
+fn config_diff_cli_redacts_secrets_when_masking() {
+    let left = temp_file_path("left-secrets");
+    let right = temp_file_path("right-secrets");
+    fs::write(
+        &left,
+        "username admin secret 9 $9$oldhash\nhostname edge-1\n",
+    )
+    .expect("write left");
+    fs::write(
+        &right,
+        "username admin secret 9 $9$newhash\nhostname edge-2\n",
+    )
+    .expect("write right");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("--dialect")
+        .arg("iosxe")
+        .arg("--mask-secrets")
+        .arg(&left)
+        .arg(&right)
+        .output()
+        .expect("run config-diff");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("edge-2"));
+    assert!(!stdout.contains("username admin"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("--dialect")
+        .arg("iosxe")
+        .arg("--hash-secrets")
+        .arg("--plan-json")
+        .arg(&left)
+        .arg(&right)
+        .output()
+        .expect("run config-diff --plan-json");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("username admin secret 9 <secret:changed>"));
+    assert!(!stdout.contains("$9$"));
+}
+
+#[test]
+fn config_diff_cli_canonicalizes_interface_abbreviations() {
+    let left = temp_file_path("left-interfaces");
+    let right = temp_file_path("right-interfaces");
+    fs::write(
+        &left,
+        "interface Po10\n shutdown\nip route 0.0.0.0 0.0.0.0 Po10\n",
+    )
+    .expect("write left");
+    fs::write(
+        &right,
+        "interface Port-channel10\n shutdown\nip route 0.0.0.0 0.0.0.0 Port-channel10\n",
+    )
+    .expect("write right");
+
+    let diff_json = |extra: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+            .args(["--json", "--dialect", "iosxe"])
+            .args(extra)
+            .arg(&left)
+            .arg(&right)
+            .output()
+            .expect("run config-diff");
+        assert!(output.status.success());
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).expect("valid json")
+    };
+    assert_eq!(diff_json(&[])["has_changes"], true);
+    let diff = diff_json(&["--canonicalize-interfaces"]);
+    assert_eq!(diff["has_changes"], false);
+    assert_eq!(diff["normalization_steps"][0], "canonicalize_interfaces");
+}
+
+#[test]
+fn config_diff_cli_rejects_unknown_dialect() {
+    let file = temp_file_path("unknown-dialect");
+    fs::write(&file, "hostname edge-1\n").expect("write config");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("--dialect")
+        .arg("ios")
+        .arg(&file)
+        .arg(&file)
+        .output()
+        .expect("run config-diff --dialect ios");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(
+            "unknown dialect `ios` (known: asa, eos, fortios, generic, iosxe, iosxr, junos, nxos, routeros, sros, vrp)"
+        ),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn config_diff_cli_auto_dialect_reports_uncertain_and_mismatched_detection() {
+    let left = temp_file_path("left-auto");
+    let right = temp_file_path("right-auto");
+    fs::write(
+        &left,
+        "## Last commit: 2026-10-01 10:00:00 UTC by admin\nsystem {\n    host-name r1;\n}\n",
+    )
+    .expect("write left");
+    fs::write(&right, "hostname r1\n!\n").expect("write right");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("--dialect")
+        .arg("auto")
+        .arg("--json")
+        .arg(&left)
+        .arg(&right)
+        .output()
+        .expect("run config-diff --dialect auto");
+
+    assert!(output.status.success());
+    let diff_json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    let messages = diff_json["findings"]
+        .as_array()
+        .expect("findings array")
+        .iter()
+        .filter(|finding| finding["code"] == "dialect_uncertain")
+        .map(|finding| finding["message"].as_str().expect("message").to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(messages.len(), 2, "{messages:?}");
+    assert!(messages[0].starts_with("right dialect detection is uncertain: guessed `iosxe`"));
+    assert_eq!(
+        messages[1],
+        "sides were detected as different dialects: left `junos`, right `iosxe`"
+    );
+}
+
+#[test]
+fn config_diff_cli_parses_with_dialect_file() {
+    let rules = temp_file_path("rules").with_extension("toml");
+    fs::write(
+        &rules,
+        "name = \"acme\"\ncomment_prefixes = [\"//\"]\nblock_terminators = [\"exit\"]\n\n[[key_hints]]\npattern = \"zone {0}\"\ntemplate = \"zone:{0}\"\n",
+    )
+    .expect("write rules");
+    let left = temp_file_path("left-rules");
+    let right = temp_file_path("right-rules");
+    fs::write(&left, "// old\nzone a\n mtu 1500\nexit\nzone b\nexit\n").expect("write left");
+    fs::write(&right, "// new\nzone a\n mtu 1500\nexit\nzone b\nexit\n").expect("write right");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("--dialect-file")
+        .arg(&rules)
+        .arg("--ignore-comments")
+        .arg("--json")
+        .arg(&left)
+        .arg(&right)
+        .output()
+        .expect("run config-diff --dialect-file");
+
+    assert!(output.status.success());
+    let diff_json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    assert_eq!(diff_json["has_changes"], false);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("query")
+        .arg("--dialect-file")
+        .arg(&rules)
+        .arg("--json")
+        .arg("zone a")
+        .arg(&left)
+        .output()
+        .expect("run config-diff query --dialect-file");
+
+    assert!(output.status.success());
+    let matches: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    assert_eq!(matches[0]["key_hint"], "zone:a");
+}
+
+#[test]
+fn config_diff_cli_reports_invalid_dialect_file() {
+    let rules = temp_file_path("rules-bad").with_extension("yaml");
+    fs::write(
+        &rules,
+        "name: acme\nkey_hints:\n  - pattern: \"vlan {0}\"\n    template: \"vlan:{1}\"\n",
+    )
+    .expect("write rules");
+    let config = temp_file_path("rules-config");
+    fs::write(&config, "vlan 10\n").expect("write config");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("--dialect-file")
+        .arg(&rules)
+        .arg(&config)
+        .arg(&config)
+        .output()
+        .expect("run config-diff --dialect-file");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("invalid `key_hints[0].template`: `{1}` is not captured"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn config_diff_query_prints_text_and_json_matches() {
+    let file = temp_file_path("query");
+    fs::write(
+        &file,
+        "interface Ethernet1\n  shutdown\ninterface Ethernet2\n  no shutdown\n",
+    )
+    .expect("write config");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("query")
+        .arg("interface * > shutdown")
+        .arg(&file)
+        .output()
+        .expect("run config-diff query");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout.contains(":2 [0.0]   shutdown"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("query")
+        .arg("--dialect")
+        .arg("iosxe")
+        .arg("--json")
+        .arg("interface *")
+        .arg(&file)
+        .output()
+        .expect("run config-diff query --json");
+    assert!(output.status.success());
+    let matches: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    let matches = matches.as_array().expect("array of matches");
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[1]["key_hint"], "interface:Ethernet2");
+    assert_eq!(matches[1]["line"], 3);
+}
+
+#[test]
+fn config_diff_query_rejects_malformed_selector() {
+    let file = temp_file_path("query-bad");
+    fs::write(&file, "hostname edge-1\n").expect("write config");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("query")
+        .arg("interface >")
+        .arg(&file)
+        .output()
+        .expect("run config-diff query");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn config_diff_merge_prints_conflict_markers_and_json() {
+    let base = temp_file_path("merge-base");
+    let ours = temp_file_path("merge-ours");
+    let theirs = temp_file_path("merge-theirs");
+    fs::write(
+        &base,
+        "hostname edge-1\nlogging host 10.0.0.9\nntp server 10.0.0.1\n",
+    )
+    .expect("write base");
+    fs::write(
+        &ours,
+        "hostname edge-2\nlogging host 10.0.0.9\nntp server 10.0.0.1\n",
+    )
+    .expect("write ours");
+    fs::write(
+        &theirs,
+        "hostname edge-3\nlogging host 10.0.0.9\nntp server 10.0.0.2\n",
+    )
+    .expect("write theirs");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("merge")
+        .arg(&base)
+        .arg(&ours)
+        .arg(&theirs)
+        .output()
+        .expect("run config-diff merge");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "<<<<<<< ours\nhostname edge-2\n||||||| base\nhostname edge-1\n=======\nhostname edge-3\n>>>>>>> theirs\nlogging host 10.0.0.9\nntp server 10.0.0.2\n"
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("merge")
+        .arg("--json")
+        .arg(&base)
+        .arg(&ours)
+        .arg(&theirs)
+        .output()
+        .expect("run config-diff merge --json");
+    assert_eq!(output.status.code(), Some(1));
+    let merge: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    assert_eq!(
+        merge["merged"],
+        "hostname edge-2\nlogging host 10.0.0.9\nntp server 10.0.0.2\n"
+    );
+    assert_eq!(merge["has_conflicts"], true);
+    assert_eq!(
+        merge["conflicts"][0]["theirs_anchor"]["path"],
+        serde_json::json!([0])
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("merge")
+        .arg(&base)
+        .arg(&base)
+        .arg(&theirs)
+        .output()
+        .expect("run config-diff merge");
+    assert!(output.status.success());
+}
+
+#[test]
+fn config_diff_fleet_summarizes_devices_and_writes_per_device_diffs() {
+    let root = temp_file_path("fleet");
+    let devices = root.join("devices");
+    let out = root.join("out");
+    fs::create_dir_all(&devices).expect("create devices dir");
+    let golden = root.join("golden.cfg");
+    fs::write(&golden, "hostname golden\nntp server 10.0.0.1\n").expect("write golden");
+    fs::write(
+        devices.join("edge-1.cfg"),
+        "hostname golden\nntp server 10.0.0.9\n",
+    )
+    .expect("write edge-1");
+    fs::write(
+        devices.join("edge-2.cfg"),
+        "hostname golden\nntp server 10.0.0.9\n",
+    )
+    .expect("write edge-2");
+    fs::write(
+        devices.join("core-1.cfg"),
+        "hostname golden\nntp server 10.0.0.1\n",
+    )
+    .expect("write core-1");
+    fs::write(devices.join("notes.txt"), "not a config\n").expect("write notes");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("fleet")
+        .arg("--out-dir")
+        .arg(&out)
+        .arg(&golden)
+        .arg(devices.join("*.cfg"))
+        .output()
+        .expect("run config-diff fleet");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("# Fleet Diff Report"));
+    assert!(stdout.contains("- Devices: 3 (2 changed)"));
+    assert!(stdout.contains("1. Replace on 2/3 device(s)"));
+
+    let diff: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(out.join("edge-1.cfg").join("diff.json")).expect("read diff.json"),
+    )
+    .expect("valid json");
+    assert_eq!(diff["has_changes"], true);
+    assert!(out.join("core-1.cfg").join("diff.json").exists());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("fleet")
+        .arg("--json")
+        .arg(&golden)
+        .arg(&devices)
+        .output()
+        .expect("run config-diff fleet --json");
+    assert!(output.status.success());
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    assert_eq!(summary["device_count"], 4);
+    assert_eq!(
+        summary["edits"][0]["devices"].as_array().map(Vec::len),
+        Some(2)
+    );
+}
+
+#[test]
+fn config_diff_fleet_names_devices_uniquely_when_file_names_collide() {
+    let root = temp_file_path("fleet-collide");
+    let out = root.join("out");
+    for site in ["a", "b"] {
+        fs::create_dir_all(root.join(site)).expect("create site dir");
+    }
+    let golden = root.join("golden.cfg");
+    fs::write(&golden, "hostname golden\nntp server 10.0.0.1\n").expect("write golden");
+    fs::write(
+        root.join("a").join("r1.cfg"),
+        "hostname golden\nntp server 10.0.0.8\n",
+    )
+    .expect("write a/r1");
+    fs::write(
+        root.join("b").join("r1.cfg"),
+        "hostname golden\nntp server 10.0.0.9\n",
+    )
+    .expect("write b/r1");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
+        .arg("fleet")
+        .arg("--json")
+        .arg("--out-dir")
+        .arg(&out)
+        .arg(&golden)
+        .arg(root.join("a").join("r1.cfg"))
+        .arg(root.join("b").join("r1.cfg"))
+        .output()
+        .expect("run config-diff fleet");
+    assert!(output.status.success());
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    assert_eq!(
+        summary["outliers"],
+        serde_json::json!([
+            { "device": "a/r1.cfg", "unique_edits": 1, "total_edits": 1 },
+            { "device": "b/r1.cfg", "unique_edits": 1, "total_edits": 1 }
+        ])
+    );
+    for site in ["a", "b"] {
+        assert!(out.join(site).join("r1.cfg").join("diff.json").exists());
+    }
+}
+
 #[test]
 fn config_diff_cli_fails_for_missing_file() {
     let output = Command::new(env!("CARGO_BIN_EXE_config-diff"))
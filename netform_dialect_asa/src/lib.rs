@@ -0,0 +1,347 @@
+//! Cisco ASA-oriented dialect profile for `netform_ir`.
+//!
+//! This crate provides a conservative ASA profile that customizes:
+//! - comment classification (`!`, and `:` lines such as `: Saved` and `:end`)
+//! - tokenization with quoted-string preservation
+//! - key hints for `object`/`object-group` blocks, interfaces, and vpn/policy stanzas
+//! - `access-list:<name>` and `crypto-map:<name>:<seq>` hints on flat lines
+//! - `access-list:<name>` list hints, so `netform_diff` matches each ACL by name
+//!   and keeps its entries in order
+//! - `secret`, `password`, `key-string`, and `pre-shared-key` values as secrets, plus
+//!   `snmp-server community` strings
+//! - abbreviated interface names (`Gi0/0`, `Po1`) expanded in key hints and
+//!   `LineNode::interfaces`
+//!
+//! # Example
+//!
+//! ```rust
+//! use netform_dialect_asa::parse_asa;
+//!
+//! let cfg = "object network WEB\n host 10.0.0.5\naccess-list OUTSIDE_IN extended permit tcp any object WEB eq https\n";
+//! let doc = parse_asa(cfg);
+//! assert_eq!(doc.render(), cfg);
+//! ```
+
+use netform_ir::{
+    Dialect, DialectHint, DialectRegistry, Document, InterfaceName, ParsedLineParts, SecretKeyword,
+    SecretSpan, TriviaKind, canonical_interface_name, interface_names_in, parse_with_dialect,
+    secret_values_after,
+};
+
+/// Keywords whose following value is a secret.
+const SECRET_KEYWORDS: &[SecretKeyword] = &[
+    SecretKeyword::typed("secret"),
+    SecretKeyword::typed("password"),
+    SecretKeyword::typed("key-string"),
+    SecretKeyword::typed("pre-shared-key"),
+];
+
+/// Secret keywords that apply on `snmp-server` lines only, where `community`
+/// is a community string rather than a route-map or BGP community.
+const SNMP_SECRET_KEYWORDS: &[SecretKeyword] = &[SecretKeyword::plain("community")];
+
+/// Interface types as `(full name, shortest abbreviation)`.
+const INTERFACE_TYPES: &[(&str, &str)] = &[
+    ("GigabitEthernet", "Gi"),
+    ("TenGigabitEthernet", "Te"),
+    ("Management", "Ma"),
+    ("Port-channel", "Po"),
+    ("Ethernet", "Et"),
+];
+
+/// Dialect implementation for ASA-like configuration text.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AsaDialect;
+
+/// Parse text using [`AsaDialect`].
+pub fn parse_asa(input: &str) -> Document {
+    parse_with_dialect(input, &AsaDialect)
+}
+
+/// Register [`AsaDialect`] as `asa` in `registry`.
+pub fn register(registry: &mut DialectRegistry) {
+    registry.register("asa", Box::new(AsaDialect));
+}
+
+impl Dialect for AsaDialect {
+    fn dialect_hint(&self) -> DialectHint {
+        DialectHint::Named("asa".to_string())
+    }
+
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        classify_asa_trivia(raw)
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        parse_asa_parts(raw)
+    }
+
+    fn key_hint(
+        &self,
+        _raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Option<String> {
+        if trivia != TriviaKind::Content {
+            return None;
+        }
+        asa_key_hint(parsed)
+    }
+
+    fn list_hint(
+        &self,
+        _raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Option<String> {
+        // ACL entries are first-match sequences, so their order always matters.
+        let parsed = parsed?;
+        if trivia != TriviaKind::Content || parsed.head != "access-list" {
+            return None;
+        }
+        parsed
+            .args
+            .first()
+            .map(|name| format!("access-list:{name}"))
+    }
+
+    fn secret_spans(
+        &self,
+        raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        _parents: &[&ParsedLineParts],
+        trivia: TriviaKind,
+    ) -> Vec<SecretSpan> {
+        if trivia != TriviaKind::Content {
+            return Vec::new();
+        }
+        if parsed.is_some_and(|parsed| parsed.head == "snmp-server") {
+            secret_values_after(raw, &[SECRET_KEYWORDS, SNMP_SECRET_KEYWORDS].concat())
+        } else {
+            secret_values_after(raw, SECRET_KEYWORDS)
+        }
+    }
+
+    fn interface_names(
+        &self,
+        raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Vec<InterfaceName> {
+        if trivia != TriviaKind::Content {
+            return Vec::new();
+        }
+        interface_names_in(raw, INTERFACE_TYPES)
+    }
+}
+
+fn classify_asa_trivia(raw: &str) -> TriviaKind {
+    if raw.trim().is_empty() {
+        return TriviaKind::Blank;
+    }
+
+    let trimmed = raw.trim_start();
+    if trimmed.starts_with('!') || trimmed.starts_with(':') {
+        return TriviaKind::Comment;
+    }
+
+    TriviaKind::Content
+}
+
+fn parse_asa_parts(raw: &str) -> Option<ParsedLineParts> {
+    let tokens = tokenize_asa(raw);
+    let head = tokens.first()?.clone();
+    let args = tokens.into_iter().skip(1).collect::<Vec<_>>();
+    Some(ParsedLineParts { head, args })
+}
+
+fn tokenize_asa(raw: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quote: Option<char> = None;
+    let mut escape = false;
+
+    for ch in raw.chars() {
+        if let Some(q) = in_quote {
+            if escape {
+                current.push(ch);
+                escape = false;
+                continue;
+            }
+
+            if ch == '\\' {
+                current.push(ch);
+                escape = true;
+                continue;
+            }
+
+            current.push(ch);
+            if ch == q {
+                in_quote = None;
+            }
+            continue;
+        }
+
+        match ch {
+            '"' | '\'' => {
+                current.push(ch);
+                in_quote = Some(ch);
+            }
+            c if c.is_whitespace() => {
+                if !current.trim().is_empty() {
+                    tokens.push(current.trim().to_string());
+                    current.clear();
+                }
+            }
+            _ => current.push(ch),
+        }
+    }
+
+    if !current.trim().is_empty() {
+        tokens.push(current.trim().to_string());
+    }
+
+    tokens
+}
+
+fn asa_key_hint(parsed: Option<&ParsedLineParts>) -> Option<String> {
+    let parsed = parsed?;
+    let head = parsed.head.as_str();
+    let args = parsed.args.as_slice();
+
+    match head {
+        "interface" if !args.is_empty() => {
+            Some(format!("interface:{}", canonical_interface(&args.concat())))
+        }
+        // Object names share one namespace across `network` and `service` objects.
+        "object" | "object-group" => match args {
+            [_kind, name, ..] => Some(format!("{head}:{name}")),
+            _ => None,
+        },
+        "access-list" => args.first().map(|name| format!("access-list:{name}")),
+        "crypto" => match args {
+            [next, name, seq, ..] if next == "map" => Some(format!("crypto-map:{name}:{seq}")),
+            _ => None,
+        },
+        "tunnel-group" | "group-policy" => match args {
+            [name, section, ..] => Some(format!("{head}:{name}:{section}")),
+            _ => None,
+        },
+        // `policy-map type inspect dns NAME` is named by its last token.
+        "class-map" | "policy-map" => args.last().map(|name| format!("{head}:{name}")),
+        _ => None,
+    }
+}
+
+/// Canonical spelling of interface `name`, or `name` unchanged.
+fn canonical_interface(name: &str) -> String {
+    canonical_interface_name(name, INTERFACE_TYPES).unwrap_or_else(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use netform_ir::Node;
+
+    fn hint(raw: &str) -> Option<String> {
+        asa_key_hint(parse_asa_parts(raw).as_ref())
+    }
+
+    #[test]
+    fn asa_list_hints_cover_only_access_lists() {
+        let list_hint = |raw: &str| {
+            AsaDialect.list_hint(raw, parse_asa_parts(raw).as_ref(), TriviaKind::Content)
+        };
+        assert_eq!(
+            list_hint("access-list OUTSIDE_IN extended deny ip any any").as_deref(),
+            Some("access-list:OUTSIDE_IN")
+        );
+        assert_eq!(
+            list_hint("crypto map OUTSIDE_MAP 10 set peer 198.51.100.1"),
+            None
+        );
+        assert_eq!(list_hint("object network WEB-01"), None);
+    }
+
+    #[test]
+    fn asa_comment_classification_supports_bang_and_colon() {
+        assert_eq!(classify_asa_trivia(": Saved"), TriviaKind::Comment);
+        assert_eq!(classify_asa_trivia(":end"), TriviaKind::Comment);
+        assert_eq!(classify_asa_trivia("!"), TriviaKind::Comment);
+        assert_eq!(
+            classify_asa_trivia("ASA Version 9.18(3)"),
+            TriviaKind::Content
+        );
+    }
+
+    #[test]
+    fn asa_key_hints_identify_objects_and_lists() {
+        assert_eq!(
+            hint("object network WEB-01").as_deref(),
+            Some("object:WEB-01")
+        );
+        assert_eq!(
+            hint("object-group service WEB-PORTS tcp").as_deref(),
+            Some("object-group:WEB-PORTS")
+        );
+        assert_eq!(
+            hint("access-list OUTSIDE_IN extended permit tcp any object WEB-01 eq https")
+                .as_deref(),
+            Some("access-list:OUTSIDE_IN")
+        );
+        assert_eq!(
+            hint("crypto map OUTSIDE_MAP 10 set peer 198.51.100.1").as_deref(),
+            Some("crypto-map:OUTSIDE_MAP:10")
+        );
+        assert_eq!(
+            hint("tunnel-group 198.51.100.1 ipsec-attributes").as_deref(),
+            Some("tunnel-group:198.51.100.1:ipsec-attributes")
+        );
+        assert_eq!(
+            hint("policy-map type inspect dns preset_dns_map").as_deref(),
+            Some("policy-map:preset_dns_map")
+        );
+        assert_eq!(
+            hint("interface GigabitEthernet0/0").as_deref(),
+            Some("interface:GigabitEthernet0/0")
+        );
+        assert_eq!(
+            hint("nat (inside,outside) source dynamic any interface"),
+            None
+        );
+    }
+
+    #[test]
+    fn asa_objects_are_blocks_and_access_lists_stay_flat() {
+        let cfg = ": Saved\n:\nASA Version 9.18(3)\n!\nobject network WEB-01\n host 10.0.0.5\nobject-group network DMZ-HOSTS\n network-object object WEB-01\n network-object host 10.0.0.6\naccess-list OUTSIDE_IN extended permit tcp any object-group DMZ-HOSTS eq https\naccess-list OUTSIDE_IN extended deny ip any any log\nnat (inside,outside) source dynamic any interface\n:end\n";
+        let doc = parse_asa(cfg);
+        assert_eq!(doc.render(), cfg);
+        assert!(doc.metadata.parse_findings.is_empty());
+
+        let Some(Node::Block(object)) = doc.node(doc.roots[4]) else {
+            panic!("expected object block");
+        };
+        assert_eq!(object.header.key_hint.as_deref(), Some("object:WEB-01"));
+        let Some(Node::Block(group)) = doc.node(doc.roots[5]) else {
+            panic!("expected object-group block");
+        };
+        assert_eq!(
+            group.header.key_hint.as_deref(),
+            Some("object-group:DMZ-HOSTS")
+        );
+        assert_eq!(group.children.len(), 2);
+
+        for idx in [6, 7] {
+            assert!(matches!(
+                doc.node(doc.roots[idx]),
+                Some(Node::Line(line)) if line.key_hint.as_deref() == Some("access-list:OUTSIDE_IN")
+            ));
+        }
+    }
+
+    #[test]
+    fn parse_asa_sets_named_dialect_hint() {
+        let doc = parse_asa("hostname fw-01\n");
+        assert_eq!(doc.metadata.dialect_hint, DialectHint::Named("asa".into()));
+    }
+}
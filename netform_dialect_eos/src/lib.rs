@@ -3,6 +3,11 @@
 //! This crate provides a conservative EOS profile that customizes:
 //! - comment classification (`!`, `#`)
 //! - tokenization with quoted-string preservation
+//! - `banner` and `comment` text ending at `EOF` as opaque blocks
+//! - `secret`, `password`, `key-string`, `md5`, and `pre-shared-key` values as secrets, plus
+//!   `snmp-server community` strings
+//! - abbreviated interface names (`Et1`, `Po10`) expanded in key hints and
+//!   `LineNode::interfaces`
 //!
 //! # Example
 //!
@@ -14,7 +19,35 @@
 //! assert_eq!(doc.render(), cfg);
 //! ```
 
-use netform_ir::{Dialect, DialectHint, Document, ParsedLineParts, TriviaKind, parse_with_dialect};
+use netform_ir::{
+    Dialect, DialectHint, DialectRegistry, Document, InterfaceName, LiteralEnd, LiteralRegion,
+    ParsedLineParts, SecretKeyword, SecretSpan, TriviaKind, canonical_interface_name,
+    interface_names_in, parse_with_dialect, secret_values_after,
+};
+
+/// Keywords whose following value is a secret.
+const SECRET_KEYWORDS: &[SecretKeyword] = &[
+    SecretKeyword::typed("secret"),
+    SecretKeyword::typed("password"),
+    SecretKeyword::typed("key-string"),
+    SecretKeyword::typed("md5"),
+    SecretKeyword::typed("pre-shared-key"),
+];
+
+/// Secret keywords that apply on `snmp-server` lines only, where `community`
+/// is a community string rather than a route-map or BGP community.
+const SNMP_SECRET_KEYWORDS: &[SecretKeyword] = &[SecretKeyword::plain("community")];
+
+/// Interface types as `(full name, shortest abbreviation)`.
+const INTERFACE_TYPES: &[(&str, &str)] = &[
+    ("Ethernet", "Et"),
+    ("Port-Channel", "Po"),
+    ("Loopback", "Lo"),
+    ("Management", "Ma"),
+    ("Vlan", "Vl"),
+    ("Vxlan", "Vx"),
+    ("Tunnel", "Tu"),
+];
 
 /// Dialect implementation for EOS-like configuration text.
 #[derive(Debug, Default, Clone, Copy)]
@@ -25,6 +58,11 @@ pub fn parse_eos(input: &str) -> Document {
     parse_with_dialect(input, &EosDialect)
 }
 
+/// Register [`EosDialect`] as `eos` in `registry`.
+pub fn register(registry: &mut DialectRegistry) {
+    registry.register("eos", Box::new(EosDialect));
+}
+
 impl Dialect for EosDialect {
     fn dialect_hint(&self) -> DialectHint {
         DialectHint::Named("eos".to_string())
@@ -49,6 +87,45 @@ impl Dialect for EosDialect {
         }
         eos_like_key_hint(parsed)
     }
+
+    fn secret_spans(
+        &self,
+        raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        _parents: &[&ParsedLineParts],
+        trivia: TriviaKind,
+    ) -> Vec<SecretSpan> {
+        if trivia != TriviaKind::Content {
+            return Vec::new();
+        }
+        if parsed.is_some_and(|parsed| parsed.head == "snmp-server") {
+            secret_values_after(raw, &[SECRET_KEYWORDS, SNMP_SECRET_KEYWORDS].concat())
+        } else {
+            secret_values_after(raw, SECRET_KEYWORDS)
+        }
+    }
+
+    fn interface_names(
+        &self,
+        raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Vec<InterfaceName> {
+        if trivia != TriviaKind::Content {
+            return Vec::new();
+        }
+        interface_names_in(raw, INTERFACE_TYPES)
+    }
+
+    fn literal_region(
+        &self,
+        _raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        _parents: &[&ParsedLineParts],
+        _trivia: TriviaKind,
+    ) -> Option<LiteralRegion> {
+        eos_literal_region(parsed?)
+    }
 }
 
 fn classify_eos_trivia(raw: &str) -> TriviaKind {
@@ -120,14 +197,29 @@ fn tokenize_eos(raw: &str) -> Vec<String> {
     tokens
 }
 
+fn eos_literal_region(parsed: &ParsedLineParts) -> Option<LiteralRegion> {
+    let kind_label = match (parsed.head.as_str(), parsed.args.as_slice()) {
+        ("banner", [kind]) if kind == "login" || kind == "motd" => "banner",
+        ("comment", []) => "comment",
+        _ => return None,
+    };
+    Some(LiteralRegion {
+        kind_label: kind_label.to_string(),
+        end: LiteralEnd::Marker("EOF".to_string()),
+    })
+}
+
 fn eos_like_key_hint(parsed: Option<&ParsedLineParts>) -> Option<String> {
     let parsed = parsed?;
     let head = parsed.head.as_str();
     let args = parsed.args.as_slice();
 
     match head {
-        "interface" => args.first().map(|name| format!("interface:{name}")),
+        "interface" => args
+            .first()
+            .map(|name| format!("interface:{}", canonical_interface(name))),
         "vlan" => args.first().map(|id| format!("vlan:{id}")),
+        "banner" => args.first().map(|kind| format!("banner:{kind}")),
         "vrf" => args.first().map(|name| format!("vrf:{name}")),
         "router" => match args {
             [proto, asn, ..] if proto == "bgp" => Some(format!("router:bgp:{asn}")),
@@ -155,9 +247,15 @@ fn eos_like_key_hint(parsed: Option<&ParsedLineParts>) -> Option<String> {
     }
 }
 
+/// Canonical spelling of interface `name`, or `name` unchanged.
+fn canonical_interface(name: &str) -> String {
+    canonical_interface_name(name, INTERFACE_TYPES).unwrap_or_else(|| name.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use netform_ir::Node;
 
     #[test]
     fn eos_comment_classification_supports_bang_and_hash() {
@@ -174,6 +272,49 @@ mod tests {
         assert_eq!(parsed.args, vec!["\"Transit uplink\""]);
     }
 
+    #[test]
+    fn eos_banner_and_comment_end_at_eof_marker() {
+        let cfg = "banner motd\n! lab switch\n   do not touch\nEOF\ninterface Ethernet1\n   comment\n   ! moved to rack 4\n   EOF\n   shutdown\n";
+        let doc = parse_eos(cfg);
+        assert_eq!(doc.render(), cfg);
+        assert_eq!(doc.roots.len(), 2);
+        assert!(doc.metadata.parse_findings.is_empty());
+
+        let Some(Node::Block(banner)) = doc.node(doc.roots[0]) else {
+            panic!("expected banner block");
+        };
+        assert_eq!(banner.kind_label.as_deref(), Some("banner"));
+        assert_eq!(banner.header.key_hint.as_deref(), Some("banner:motd"));
+        assert_eq!(banner.children.len(), 2);
+
+        let Some(Node::Block(interface)) = doc.node(doc.roots[1]) else {
+            panic!("expected interface block");
+        };
+        assert_eq!(interface.children.len(), 2);
+        let Some(Node::Block(comment)) = doc.node(interface.children[0]) else {
+            panic!("expected comment block");
+        };
+        assert_eq!(comment.kind_label.as_deref(), Some("comment"));
+        assert_eq!(
+            comment.footer.as_ref().map(|f| f.raw.as_str()),
+            Some("   EOF")
+        );
+    }
+
+    #[test]
+    fn eos_interface_abbreviations_expand_in_key_hints() {
+        let parsed = parse_eos_parts("interface Et1").expect("content should parse");
+        assert_eq!(
+            eos_like_key_hint(Some(&parsed)).as_deref(),
+            Some("interface:Ethernet1")
+        );
+        let parsed = parse_eos_parts("interface po10").expect("content should parse");
+        assert_eq!(
+            eos_like_key_hint(Some(&parsed)).as_deref(),
+            Some("interface:Port-Channel10")
+        );
+    }
+
     #[test]
     fn parse_eos_sets_named_dialect_hint() {
         let doc = parse_eos("hostname leaf-01\n");
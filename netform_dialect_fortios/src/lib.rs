@@ -0,0 +1,266 @@
+//! Fortinet FortiOS-oriented dialect profile for `netform_ir`.
+//!
+//! This crate provides a conservative FortiOS profile that customizes:
+//! - comment classification (`#`, including `#config-version=` headers)
+//! - tokenization with quoted-string preservation
+//! - explicit block structure: `config`/`edit` open blocks, `next`/`end` close them
+//! - key hints for `config <path>` and `edit <id>` so entries match under `KeyedStable`
+//! - `password`, `passwd`, `psksecret`, and `secret` values (after `ENC`) as secrets
+//!
+//! # Example
+//!
+//! ```rust
+//! use netform_dialect_fortios::parse_fortios;
+//!
+//! let cfg = "config firewall policy\n    edit 12\n        set action accept\n    next\nend\n";
+//! let doc = parse_fortios(cfg);
+//! assert_eq!(doc.render(), cfg);
+//! ```
+
+use netform_ir::{
+    BlockRole, Dialect, DialectHint, DialectRegistry, Document, ParsedLineParts, SecretKeyword,
+    SecretSpan, TriviaKind, parse_with_dialect, secret_values_after,
+};
+
+/// Keywords whose following value is a secret.
+const SECRET_KEYWORDS: &[SecretKeyword] = &[
+    SecretKeyword::typed("password"),
+    SecretKeyword::typed("passwd"),
+    SecretKeyword::typed("psksecret"),
+    SecretKeyword::typed("secret"),
+];
+
+/// Dialect implementation for FortiOS-like configuration text.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FortiosDialect;
+
+/// Parse text using [`FortiosDialect`].
+pub fn parse_fortios(input: &str) -> Document {
+    parse_with_dialect(input, &FortiosDialect)
+}
+
+/// Register [`FortiosDialect`] as `fortios` in `registry`.
+pub fn register(registry: &mut DialectRegistry) {
+    registry.register("fortios", Box::new(FortiosDialect));
+}
+
+impl Dialect for FortiosDialect {
+    fn dialect_hint(&self) -> DialectHint {
+        DialectHint::Named("fortios".to_string())
+    }
+
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        classify_fortios_trivia(raw)
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        parse_fortios_parts(raw)
+    }
+
+    fn key_hint(
+        &self,
+        _raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Option<String> {
+        if trivia != TriviaKind::Content {
+            return None;
+        }
+        fortios_key_hint(parsed)
+    }
+
+    fn secret_spans(
+        &self,
+        raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        _parents: &[&ParsedLineParts],
+        trivia: TriviaKind,
+    ) -> Vec<SecretSpan> {
+        if trivia != TriviaKind::Content {
+            return Vec::new();
+        }
+        secret_values_after(raw, SECRET_KEYWORDS)
+    }
+
+    fn block_role(
+        &self,
+        _raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Option<BlockRole> {
+        if trivia != TriviaKind::Content {
+            return None;
+        }
+        fortios_block_role(parsed?)
+    }
+}
+
+fn classify_fortios_trivia(raw: &str) -> TriviaKind {
+    if raw.trim().is_empty() {
+        return TriviaKind::Blank;
+    }
+
+    if raw.trim_start().starts_with('#') {
+        return TriviaKind::Comment;
+    }
+
+    TriviaKind::Content
+}
+
+fn parse_fortios_parts(raw: &str) -> Option<ParsedLineParts> {
+    let tokens = tokenize_fortios(raw);
+    let head = tokens.first()?.clone();
+    let args = tokens.into_iter().skip(1).collect::<Vec<_>>();
+    Some(ParsedLineParts { head, args })
+}
+
+fn tokenize_fortios(raw: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quote: Option<char> = None;
+    let mut escape = false;
+
+    for ch in raw.chars() {
+        if let Some(q) = in_quote {
+            if escape {
+                current.push(ch);
+                escape = false;
+                continue;
+            }
+
+            if ch == '\\' {
+                current.push(ch);
+                escape = true;
+                continue;
+            }
+
+            current.push(ch);
+            if ch == q {
+                in_quote = None;
+            }
+            continue;
+        }
+
+        match ch {
+            '"' | '\'' => {
+                current.push(ch);
+                in_quote = Some(ch);
+            }
+            c if c.is_whitespace() => {
+                if !current.trim().is_empty() {
+                    tokens.push(current.trim().to_string());
+                    current.clear();
+                }
+            }
+            _ => current.push(ch),
+        }
+    }
+
+    if !current.trim().is_empty() {
+        tokens.push(current.trim().to_string());
+    }
+
+    tokens
+}
+
+fn fortios_block_role(parsed: &ParsedLineParts) -> Option<BlockRole> {
+    match (parsed.head.as_str(), parsed.args.is_empty()) {
+        ("config" | "edit", false) => Some(BlockRole::Open),
+        ("next" | "end", true) => Some(BlockRole::Close),
+        _ => None,
+    }
+}
+
+fn fortios_key_hint(parsed: Option<&ParsedLineParts>) -> Option<String> {
+    let parsed = parsed?;
+    let args = parsed.args.as_slice();
+
+    match parsed.head.as_str() {
+        "config" if !args.is_empty() => Some(format!("config:{}", args.join(" "))),
+        // `edit "port1"` and `edit port1` address the same table entry.
+        "edit" => args.first().map(|id| format!("edit:{}", unquote(id))),
+        _ => None,
+    }
+}
+
+fn unquote(token: &str) -> &str {
+    token
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .unwrap_or(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use netform_ir::Node;
+
+    #[test]
+    fn fortios_comment_classification_supports_hash_headers() {
+        assert_eq!(
+            classify_fortios_trivia("#config-version=FGT60F-7.2.5-FW-build1517-230606:opmode=0"),
+            TriviaKind::Comment
+        );
+        assert_eq!(
+            classify_fortios_trivia("config system global"),
+            TriviaKind::Content
+        );
+    }
+
+    #[test]
+    fn fortios_tokenization_keeps_quoted_values_together() {
+        let parsed =
+            parse_fortios_parts("set srcaddr \"lan net\" \"dmz\"").expect("content should parse");
+        assert_eq!(parsed.head, "set");
+        assert_eq!(parsed.args, vec!["srcaddr", "\"lan net\"", "\"dmz\""]);
+    }
+
+    #[test]
+    fn fortios_config_and_edit_blocks_close_on_next_and_end() {
+        let cfg = "config firewall policy\n    edit 12\n        set name \"allow-web\"\n        set action accept\n    next\n    edit 13\n        set action deny\n    next\nend\nconfig system interface\n    edit \"port1\"\n        config ipv6\n            set ip6-mode static\n        end\n    next\nend\n";
+        let doc = parse_fortios(cfg);
+        assert_eq!(doc.render(), cfg);
+        assert!(doc.metadata.parse_findings.is_empty());
+        assert_eq!(doc.roots.len(), 2);
+
+        let Some(Node::Block(policy)) = doc.node(doc.roots[0]) else {
+            panic!("expected config block");
+        };
+        assert_eq!(
+            policy.header.key_hint.as_deref(),
+            Some("config:firewall policy")
+        );
+        assert_eq!(policy.footer.as_ref().map(|f| f.raw.as_str()), Some("end"));
+        assert_eq!(policy.children.len(), 2);
+
+        let Some(Node::Block(entry)) = doc.node(policy.children[0]) else {
+            panic!("expected edit block");
+        };
+        assert_eq!(entry.header.key_hint.as_deref(), Some("edit:12"));
+        assert_eq!(
+            entry.footer.as_ref().map(|f| f.raw.as_str()),
+            Some("    next")
+        );
+
+        let Some(Node::Block(interfaces)) = doc.node(doc.roots[1]) else {
+            panic!("expected config block");
+        };
+        let Some(Node::Block(port)) = doc.node(interfaces.children[0]) else {
+            panic!("expected edit block");
+        };
+        assert_eq!(port.header.key_hint.as_deref(), Some("edit:port1"));
+        assert!(matches!(
+            doc.node(port.children[0]),
+            Some(Node::Block(nested)) if nested.footer.is_some()
+        ));
+    }
+
+    #[test]
+    fn parse_fortios_sets_named_dialect_hint() {
+        let doc = parse_fortios("config system global\nend\n");
+        assert_eq!(
+            doc.metadata.dialect_hint,
+            DialectHint::Named("fortios".into())
+        );
+    }
+}
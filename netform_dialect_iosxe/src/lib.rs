@@ -3,6 +3,11 @@
 //! This crate provides a conservative IOS XE profile that customizes:
 //! - comment classification (`!`, `#`)
 //! - tokenization with quoted-string preservation
+//! - delimited `banner` text and `crypto pki certificate chain` certificate hex as opaque blocks
+//! - `secret`, `password`, `key-string`, `md5`, and `pre-shared-key` values as secrets, plus
+//!   `snmp-server community` strings
+//! - abbreviated interface names (`Gi0/1`, `Po10`) expanded in key hints and
+//!   `LineNode::interfaces`
 //!
 //! # Example
 //!
@@ -14,7 +19,43 @@
 //! assert_eq!(doc.render(), cfg);
 //! ```
 
-use netform_ir::{Dialect, DialectHint, Document, ParsedLineParts, TriviaKind, parse_with_dialect};
+use netform_ir::{
+    Dialect, DialectHint, DialectRegistry, Document, InterfaceName, LiteralEnd, LiteralRegion,
+    ParsedLineParts, SecretKeyword, SecretSpan, TriviaKind, canonical_interface_name,
+    interface_names_in, parse_with_dialect, secret_values_after,
+};
+
+/// Keywords whose following value is a secret.
+const SECRET_KEYWORDS: &[SecretKeyword] = &[
+    SecretKeyword::typed("secret"),
+    SecretKeyword::typed("password"),
+    SecretKeyword::typed("key-string"),
+    SecretKeyword::typed("md5"),
+    SecretKeyword::typed("pre-shared-key"),
+];
+
+/// Secret keywords that apply on `snmp-server` lines only, where `community`
+/// is a community string rather than a route-map or BGP community.
+const SNMP_SECRET_KEYWORDS: &[SecretKeyword] = &[SecretKeyword::plain("community")];
+
+/// Interface types as `(full name, shortest abbreviation)`.
+const INTERFACE_TYPES: &[(&str, &str)] = &[
+    ("GigabitEthernet", "Gi"),
+    ("TenGigabitEthernet", "Te"),
+    ("FastEthernet", "Fa"),
+    ("FortyGigabitEthernet", "Fo"),
+    ("FiveGigabitEthernet", "Fi"),
+    ("TwentyFiveGigE", "Twe"),
+    ("TwoGigabitEthernet", "Tw"),
+    ("HundredGigE", "Hu"),
+    ("AppGigabitEthernet", "Ap"),
+    ("Ethernet", "Et"),
+    ("Port-channel", "Po"),
+    ("Loopback", "Lo"),
+    ("Vlan", "Vl"),
+    ("Tunnel", "Tu"),
+    ("Serial", "Se"),
+];
 
 /// Dialect implementation for IOS XE-like configuration text.
 #[derive(Debug, Default, Clone, Copy)]
@@ -25,6 +66,11 @@ pub fn parse_iosxe(input: &str) -> Document {
     parse_with_dialect(input, &IosxeDialect)
 }
 
+/// Register [`IosxeDialect`] as `iosxe` in `registry`.
+pub fn register(registry: &mut DialectRegistry) {
+    registry.register("iosxe", Box::new(IosxeDialect));
+}
+
 impl Dialect for IosxeDialect {
     fn dialect_hint(&self) -> DialectHint {
         DialectHint::Named("iosxe".to_string())
@@ -49,6 +95,45 @@ impl Dialect for IosxeDialect {
         }
         ios_like_key_hint(parsed)
     }
+
+    fn secret_spans(
+        &self,
+        raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        _parents: &[&ParsedLineParts],
+        trivia: TriviaKind,
+    ) -> Vec<SecretSpan> {
+        if trivia != TriviaKind::Content {
+            return Vec::new();
+        }
+        if parsed.is_some_and(|parsed| parsed.head == "snmp-server") {
+            secret_values_after(raw, &[SECRET_KEYWORDS, SNMP_SECRET_KEYWORDS].concat())
+        } else {
+            secret_values_after(raw, SECRET_KEYWORDS)
+        }
+    }
+
+    fn interface_names(
+        &self,
+        raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Vec<InterfaceName> {
+        if trivia != TriviaKind::Content {
+            return Vec::new();
+        }
+        interface_names_in(raw, INTERFACE_TYPES)
+    }
+
+    fn literal_region(
+        &self,
+        _raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        parents: &[&ParsedLineParts],
+        _trivia: TriviaKind,
+    ) -> Option<LiteralRegion> {
+        iosxe_literal_region(parsed?, parents)
+    }
 }
 
 fn classify_iosxe_trivia(raw: &str) -> TriviaKind {
@@ -120,14 +205,59 @@ fn tokenize_iosxe(raw: &str) -> Vec<String> {
     tokens
 }
 
+fn iosxe_literal_region(
+    parsed: &ParsedLineParts,
+    parents: &[&ParsedLineParts],
+) -> Option<LiteralRegion> {
+    match parsed.head.as_str() {
+        "banner" => {
+            // `banner <kind> <delim>text...<delim>`; `^C` is the caret-notation delimiter.
+            let (_kind, rest) = parsed.args.split_first()?;
+            let text = rest.join(" ");
+            let delimiter = if text.starts_with("^C") {
+                "^C".to_string()
+            } else {
+                text.chars().next()?.to_string()
+            };
+            if text[delimiter.len()..].contains(delimiter.as_str()) {
+                // Single-line banner, already closed on the header line.
+                return None;
+            }
+            Some(LiteralRegion {
+                kind_label: "banner".to_string(),
+                end: LiteralEnd::Delimiter(delimiter),
+            })
+        }
+        // Certificate hex only follows `certificate` inside
+        // `crypto pki certificate chain <name>`.
+        "certificate"
+            if !parsed.args.is_empty()
+                && parents.last().is_some_and(|parent| {
+                    parent.head == "crypto"
+                        && parent.args.len() > 3
+                        && parent.args[..3] == ["pki", "certificate", "chain"]
+                }) =>
+        {
+            Some(LiteralRegion {
+                kind_label: "certificate".to_string(),
+                end: LiteralEnd::Marker("quit".to_string()),
+            })
+        }
+        _ => None,
+    }
+}
+
 fn ios_like_key_hint(parsed: Option<&ParsedLineParts>) -> Option<String> {
     let parsed = parsed?;
     let head = parsed.head.as_str();
     let args = parsed.args.as_slice();
 
     match head {
-        "interface" => args.first().map(|name| format!("interface:{name}")),
+        "interface" => args
+            .first()
+            .map(|name| format!("interface:{}", canonical_interface(name))),
         "vlan" => args.first().map(|id| format!("vlan:{id}")),
+        "banner" => args.first().map(|kind| format!("banner:{kind}")),
         "vrf" => args.first().map(|name| format!("vrf:{name}")),
         "router" => match args {
             [proto, asn, ..] if proto == "bgp" => Some(format!("router:bgp:{asn}")),
@@ -155,9 +285,15 @@ fn ios_like_key_hint(parsed: Option<&ParsedLineParts>) -> Option<String> {
     }
 }
 
+/// Canonical spelling of interface `name`, or `name` unchanged.
+fn canonical_interface(name: &str) -> String {
+    canonical_interface_name(name, INTERFACE_TYPES).unwrap_or_else(|| name.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use netform_ir::{LineNode, Node};
 
     #[test]
     fn iosxe_comment_classification_supports_bang_and_hash() {
@@ -176,6 +312,159 @@ mod tests {
         assert_eq!(parsed.args, vec!["\"WAN uplink\""]);
     }
 
+    #[test]
+    fn iosxe_reports_secret_values_after_type_markers() {
+        let doc = parse_iosxe(
+            "enable secret 9 $9$abc\n!\ninterface Gi1\n ip ospf message-digest-key 1 md5 k3y\n",
+        );
+        let values = doc
+            .arena
+            .iter()
+            .map(|node| match node {
+                Node::Line(line) => line,
+                Node::Block(block) => &block.header,
+            })
+            .flat_map(|line| {
+                line.secrets
+                    .iter()
+                    .map(|span| line.raw[span.start..span.end].to_string())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec!["$9$abc", "k3y"]);
+    }
+
+    #[test]
+    fn iosxe_communities_are_secret_only_on_snmp_server_lines() {
+        let doc = parse_iosxe(
+            "snmp-server community 12 RO\nroute-map RM permit 10\n match community CL-A\n set community 65000:100\n",
+        );
+        let values = doc
+            .arena
+            .iter()
+            .map(|node| match node {
+                Node::Line(line) => line,
+                Node::Block(block) => &block.header,
+            })
+            .flat_map(|line| {
+                line.secrets
+                    .iter()
+                    .map(|span| line.raw[span.start..span.end].to_string())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec!["12"]);
+    }
+
+    #[test]
+    fn iosxe_interface_abbreviations_share_canonical_key_hints() {
+        let abbreviated = parse_iosxe("interface Gi0/1\n channel-group 10\ninterface Po10\n");
+        let full = parse_iosxe(
+            "interface GigabitEthernet0/1\n channel-group 10\ninterface Port-channel10\n",
+        );
+        let headers = |doc: &Document| {
+            doc.roots
+                .iter()
+                .filter_map(|id| match doc.node(*id) {
+                    Some(Node::Block(block)) => block.header.key_hint.clone(),
+                    Some(Node::Line(line)) => line.key_hint.clone(),
+                    None => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            headers(&abbreviated),
+            vec!["interface:GigabitEthernet0/1", "interface:Port-channel10"]
+        );
+        assert_eq!(headers(&abbreviated), headers(&full));
+
+        let route = LineNode::from_dialect("ip route 0.0.0.0 0.0.0.0 Te1/1/1", &IosxeDialect);
+        assert_eq!(route.interfaces.len(), 1);
+        assert_eq!(route.interfaces[0].canonical, "TenGigabitEthernet1/1/1");
+        assert!(
+            LineNode::from_dialect("interface TenGigabitEthernet1/1/1", &IosxeDialect)
+                .interfaces
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn iosxe_banner_is_kept_as_one_opaque_block() {
+        let cfg = "banner motd ^C\n! Authorized access only !\nviolators prosecuted\n^C\nhostname edge-1\n";
+        let doc = parse_iosxe(cfg);
+        assert_eq!(doc.render(), cfg);
+        assert_eq!(doc.roots.len(), 2);
+
+        let Some(Node::Block(banner)) = doc.node(doc.roots[0]) else {
+            panic!("expected banner block");
+        };
+        assert_eq!(banner.kind_label.as_deref(), Some("banner"));
+        assert_eq!(banner.header.key_hint.as_deref(), Some("banner:motd"));
+        assert_eq!(banner.children.len(), 2);
+        assert_eq!(banner.footer.as_ref().map(|f| f.raw.as_str()), Some("^C"));
+
+        let Some(Node::Line(text)) = doc.node(banner.children[0]) else {
+            panic!("expected banner text line");
+        };
+        assert_eq!(text.trivia, TriviaKind::Content);
+        assert!(text.parsed.is_none());
+    }
+
+    #[test]
+    fn iosxe_single_line_banner_and_custom_delimiter() {
+        assert!(
+            iosxe_literal_region(
+                &parse_iosxe_parts("banner login #closed#").expect("parts"),
+                &[]
+            )
+            .is_none()
+        );
+
+        let doc = parse_iosxe("banner exec %\nwelcome\n  %\n");
+        let Some(Node::Block(banner)) = doc.node(doc.roots[0]) else {
+            panic!("expected banner block");
+        };
+        assert_eq!(banner.children.len(), 1);
+        assert_eq!(banner.footer.as_ref().map(|f| f.raw.as_str()), Some("  %"));
+        assert!(doc.metadata.parse_findings.is_empty());
+    }
+
+    #[test]
+    fn iosxe_certificate_hex_ends_at_quit() {
+        let cfg = "crypto pki certificate chain TP-self-signed\n certificate self-signed 01\n  3082022B 30820194 A0030201\n  02020101\n  \tquit\n!\n";
+        let doc = parse_iosxe(cfg);
+        assert_eq!(doc.render(), cfg);
+
+        let Some(Node::Block(chain)) = doc.node(doc.roots[0]) else {
+            panic!("expected certificate chain block");
+        };
+        let Some(Node::Block(cert)) = doc.node(chain.children[0]) else {
+            panic!("expected certificate block");
+        };
+        assert_eq!(cert.kind_label.as_deref(), Some("certificate"));
+        assert_eq!(cert.children.len(), 2);
+        assert!(cert.footer.is_some());
+    }
+
+    #[test]
+    fn iosxe_certificate_outside_a_chain_is_plain_config() {
+        let cfg = "crypto pki trustpoint TP\n certificate self-signed 01\nhostname edge-1\ninterface Gi1\n shutdown\n";
+        let doc = parse_iosxe(cfg);
+        assert_eq!(doc.render(), cfg);
+        assert!(doc.metadata.parse_findings.is_empty());
+        assert_eq!(doc.roots.len(), 3);
+        assert!(doc.arena.iter().all(|node| match node {
+            Node::Block(block) => block.kind_label.is_none(),
+            Node::Line(_) => true,
+        }));
+
+        let Some(Node::Block(interface)) = doc.node(doc.roots[2]) else {
+            panic!("expected interface block");
+        };
+        assert_eq!(
+            interface.header.key_hint.as_deref(),
+            Some("interface:GigabitEthernet1")
+        );
+    }
+
     #[test]
     fn parse_iosxe_sets_named_dialect_hint() {
         let doc = parse_iosxe("hostname edge-1\n");
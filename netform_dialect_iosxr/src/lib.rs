@@ -0,0 +1,371 @@
+//! Cisco IOS XR-oriented dialect profile for `netform_ir`.
+//!
+//! This crate provides a conservative IOS XR profile that customizes:
+//! - comment classification (`!`, `!!`, `#`)
+//! - tokenization with quoted-string preservation
+//! - block terminators: `!` and RPL `endif` close the same-indent block, while
+//!   `end-policy`, `end-set`, and `end-group` close RPL sets and config groups
+//! - `secret`, `password`, `key-string`, `md5`, and `pre-shared-key` values as secrets, plus
+//!   `snmp-server community` strings
+//! - abbreviated interface names (`Gi0/0/0/1`, `Te0/0/0/0`) expanded in key hints and
+//!   `LineNode::interfaces`
+//!
+//! Terminator lines are stored in `BlockNode::footer`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use netform_dialect_iosxr::parse_iosxr;
+//!
+//! let cfg = "router bgp 65000\n neighbor 192.0.2.1\n  remote-as 65001\n !\n!\n";
+//! let doc = parse_iosxr(cfg);
+//! assert_eq!(doc.render(), cfg);
+//! ```
+
+use netform_ir::{
+    BlockRole, Dialect, DialectHint, DialectRegistry, Document, InterfaceName, ParsedLineParts,
+    SecretKeyword, SecretSpan, TriviaKind, canonical_interface_name, interface_names_in,
+    parse_with_dialect, secret_values_after,
+};
+
+/// Top-level RPL and group statements closed by an `end-*` line.
+const EXPLICIT_OPENERS: &[&str] = &[
+    "route-policy",
+    "prefix-set",
+    "as-path-set",
+    "community-set",
+    "extcommunity-set",
+    "large-community-set",
+    "rd-set",
+    "tag-set",
+    "group",
+];
+
+/// Keywords whose following value is a secret.
+const SECRET_KEYWORDS: &[SecretKeyword] = &[
+    SecretKeyword::typed("secret"),
+    SecretKeyword::typed("password"),
+    SecretKeyword::typed("key-string"),
+    SecretKeyword::typed("md5"),
+    SecretKeyword::typed("pre-shared-key"),
+];
+
+/// Secret keywords that apply on `snmp-server` lines only, where `community`
+/// is a community string rather than a route-map or BGP community.
+const SNMP_SECRET_KEYWORDS: &[SecretKeyword] = &[SecretKeyword::plain("community")];
+
+/// Interface types as `(full name, shortest abbreviation)`.
+const INTERFACE_TYPES: &[(&str, &str)] = &[
+    ("GigabitEthernet", "Gi"),
+    ("TenGigE", "Te"),
+    ("TwentyFiveGigE", "Tw"),
+    ("FourHundredGigE", "Fou"),
+    ("FortyGigE", "Fo"),
+    ("HundredGigE", "Hu"),
+    ("Bundle-Ether", "Bu"),
+    ("Loopback", "Lo"),
+    ("MgmtEth", "Mg"),
+];
+
+/// Dialect implementation for IOS XR-like configuration text.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IosxrDialect;
+
+/// Parse text using [`IosxrDialect`].
+pub fn parse_iosxr(input: &str) -> Document {
+    parse_with_dialect(input, &IosxrDialect)
+}
+
+/// Register [`IosxrDialect`] as `iosxr` in `registry`.
+pub fn register(registry: &mut DialectRegistry) {
+    registry.register("iosxr", Box::new(IosxrDialect));
+}
+
+impl Dialect for IosxrDialect {
+    fn dialect_hint(&self) -> DialectHint {
+        DialectHint::Named("iosxr".to_string())
+    }
+
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        classify_iosxr_trivia(raw)
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        parse_iosxr_parts(raw)
+    }
+
+    fn key_hint(
+        &self,
+        _raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Option<String> {
+        if trivia != TriviaKind::Content {
+            return None;
+        }
+        iosxr_key_hint(parsed)
+    }
+
+    fn secret_spans(
+        &self,
+        raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        _parents: &[&ParsedLineParts],
+        trivia: TriviaKind,
+    ) -> Vec<SecretSpan> {
+        if trivia != TriviaKind::Content {
+            return Vec::new();
+        }
+        if parsed.is_some_and(|parsed| parsed.head == "snmp-server") {
+            secret_values_after(raw, &[SECRET_KEYWORDS, SNMP_SECRET_KEYWORDS].concat())
+        } else {
+            secret_values_after(raw, SECRET_KEYWORDS)
+        }
+    }
+
+    fn interface_names(
+        &self,
+        raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Vec<InterfaceName> {
+        if trivia != TriviaKind::Content {
+            return Vec::new();
+        }
+        interface_names_in(raw, INTERFACE_TYPES)
+    }
+
+    fn block_role(
+        &self,
+        raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        _trivia: TriviaKind,
+    ) -> Option<BlockRole> {
+        if raw.trim() == "!" {
+            // A lone `!` ends the block opened at its indentation; `!!` and `! text` stay comments.
+            return Some(BlockRole::Terminator);
+        }
+        iosxr_block_role(raw, parsed?)
+    }
+}
+
+fn classify_iosxr_trivia(raw: &str) -> TriviaKind {
+    if raw.trim().is_empty() {
+        return TriviaKind::Blank;
+    }
+
+    let trimmed = raw.trim_start();
+    if trimmed.starts_with('!') || trimmed.starts_with('#') {
+        return TriviaKind::Comment;
+    }
+
+    TriviaKind::Content
+}
+
+fn parse_iosxr_parts(raw: &str) -> Option<ParsedLineParts> {
+    let tokens = tokenize_iosxr(raw);
+    let head = tokens.first()?.clone();
+    let args = tokens.into_iter().skip(1).collect::<Vec<_>>();
+    Some(ParsedLineParts { head, args })
+}
+
+fn tokenize_iosxr(raw: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quote: Option<char> = None;
+    let mut escape = false;
+
+    for ch in raw.chars() {
+        if let Some(q) = in_quote {
+            if escape {
+                current.push(ch);
+                escape = false;
+                continue;
+            }
+
+            if ch == '\\' {
+                current.push(ch);
+                escape = true;
+                continue;
+            }
+
+            current.push(ch);
+            if ch == q {
+                in_quote = None;
+            }
+            continue;
+        }
+
+        match ch {
+            '"' | '\'' => {
+                current.push(ch);
+                in_quote = Some(ch);
+            }
+            c if c.is_whitespace() => {
+                if !current.trim().is_empty() {
+                    tokens.push(current.trim().to_string());
+                    current.clear();
+                }
+            }
+            _ => current.push(ch),
+        }
+    }
+
+    if !current.trim().is_empty() {
+        tokens.push(current.trim().to_string());
+    }
+
+    tokens
+}
+
+fn iosxr_block_role(raw: &str, parsed: &ParsedLineParts) -> Option<BlockRole> {
+    match parsed.head.as_str() {
+        "endif" if parsed.args.is_empty() => Some(BlockRole::Terminator),
+        "end-policy" | "end-set" | "end-group" => Some(BlockRole::Close),
+        head if EXPLICIT_OPENERS.contains(&head)
+            && !parsed.args.is_empty()
+            && !raw.starts_with(char::is_whitespace) =>
+        {
+            Some(BlockRole::Open)
+        }
+        _ => None,
+    }
+}
+
+fn iosxr_key_hint(parsed: Option<&ParsedLineParts>) -> Option<String> {
+    let parsed = parsed?;
+    let head = parsed.head.as_str();
+    let args = parsed.args.as_slice();
+
+    match head {
+        "interface" => match args {
+            [kind, name, ..] if kind == "preconfigure" => {
+                Some(format!("interface:{}", canonical_interface(name)))
+            }
+            [name, ..] => Some(format!("interface:{}", canonical_interface(name))),
+            _ => None,
+        },
+        "vrf" => args.first().map(|name| format!("vrf:{name}")),
+        "neighbor" => args.first().map(|addr| format!("neighbor:{addr}")),
+        "neighbor-group" => args.first().map(|name| format!("neighbor-group:{name}")),
+        "route-policy" => args.first().map(|name| format!("route-policy:{name}")),
+        "prefix-set" => args.first().map(|name| format!("prefix-set:{name}")),
+        "community-set" | "as-path-set" | "large-community-set" => {
+            args.first().map(|name| format!("{head}:{name}"))
+        }
+        "router" => match args {
+            [proto, asn, ..] if proto == "bgp" => Some(format!("router:bgp:{asn}")),
+            [proto, tag, ..] => Some(format!("router:{proto}:{tag}")),
+            [proto] => Some(format!("router:{proto}")),
+            _ => None,
+        },
+        "group" => args.first().map(|name| format!("group:{name}")),
+        _ => None,
+    }
+}
+
+/// Canonical spelling of interface `name`, or `name` unchanged.
+fn canonical_interface(name: &str) -> String {
+    canonical_interface_name(name, INTERFACE_TYPES).unwrap_or_else(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use netform_ir::Node;
+
+    #[test]
+    fn iosxr_comment_classification_supports_bang_and_hash() {
+        assert_eq!(
+            classify_iosxr_trivia("!! IOS XR Configuration 7.9.2"),
+            TriviaKind::Comment
+        );
+        assert_eq!(classify_iosxr_trivia(" !"), TriviaKind::Comment);
+        assert_eq!(classify_iosxr_trivia("# generated"), TriviaKind::Comment);
+        assert_eq!(
+            classify_iosxr_trivia("router bgp 65000"),
+            TriviaKind::Content
+        );
+    }
+
+    #[test]
+    fn iosxr_tokenization_keeps_quoted_values_together() {
+        let parsed =
+            parse_iosxr_parts("description \"core uplink\"").expect("content should parse");
+        assert_eq!(parsed.head, "description");
+        assert_eq!(parsed.args, vec!["\"core uplink\""]);
+    }
+
+    #[test]
+    fn iosxr_bang_terminates_same_indent_blocks() {
+        let cfg = "!! IOS XR Configuration\nhostname pe-1\n!\nrouter bgp 65000\n bgp router-id 10.0.0.1\n address-family ipv4 unicast\n !\n neighbor 192.0.2.1\n  remote-as 65001\n  address-family ipv4 unicast\n   route-policy PASS in\n  !\n !\n!\nend\n";
+        let doc = parse_iosxr(cfg);
+        assert_eq!(doc.render(), cfg);
+        assert!(doc.metadata.parse_findings.is_empty());
+        assert_eq!(doc.roots.len(), 5);
+
+        let Some(Node::Block(bgp)) = doc.node(doc.roots[3]) else {
+            panic!("expected router bgp block");
+        };
+        assert_eq!(bgp.header.key_hint.as_deref(), Some("router:bgp:65000"));
+        assert_eq!(bgp.footer.as_ref().map(|f| f.raw.as_str()), Some("!"));
+        assert_eq!(bgp.children.len(), 3);
+        // An `address-family` without children still gets its `!` as footer.
+        assert!(matches!(
+            doc.node(bgp.children[1]),
+            Some(Node::Block(af)) if af.children.is_empty() && af.footer.is_some()
+        ));
+
+        let Some(Node::Block(neighbor)) = doc.node(bgp.children[2]) else {
+            panic!("expected neighbor block");
+        };
+        assert_eq!(
+            neighbor.header.key_hint.as_deref(),
+            Some("neighbor:192.0.2.1")
+        );
+        assert_eq!(neighbor.footer.as_ref().map(|f| f.raw.as_str()), Some(" !"));
+        let Some(Node::Block(af)) = doc.node(neighbor.children[1]) else {
+            panic!("expected neighbor address-family block");
+        };
+        assert_eq!(af.footer.as_ref().map(|f| f.raw.as_str()), Some("  !"));
+    }
+
+    #[test]
+    fn iosxr_rpl_end_statements_close_explicit_blocks() {
+        let cfg = "prefix-set PS-LOOPBACKS\n  10.0.0.0/24 le 32\nend-set\n!\nroute-policy RP-IN\n  if destination in PS-LOOPBACKS then\n    pass\n  else\n    drop\n  endif\nend-policy\n!\n";
+        let doc = parse_iosxr(cfg);
+        assert_eq!(doc.render(), cfg);
+        assert!(doc.metadata.parse_findings.is_empty());
+
+        let Some(Node::Block(set)) = doc.node(doc.roots[0]) else {
+            panic!("expected prefix-set block");
+        };
+        assert_eq!(
+            set.header.key_hint.as_deref(),
+            Some("prefix-set:PS-LOOPBACKS")
+        );
+        assert_eq!(set.footer.as_ref().map(|f| f.raw.as_str()), Some("end-set"));
+
+        let Some(Node::Block(policy)) = doc.node(doc.roots[2]) else {
+            panic!("expected route-policy block");
+        };
+        assert_eq!(
+            policy.header.key_hint.as_deref(),
+            Some("route-policy:RP-IN")
+        );
+        assert_eq!(
+            policy.footer.as_ref().map(|f| f.raw.as_str()),
+            Some("end-policy")
+        );
+        assert_eq!(policy.children.len(), 2);
+    }
+
+    #[test]
+    fn parse_iosxr_sets_named_dialect_hint() {
+        let doc = parse_iosxr("hostname pe-1\n");
+        assert_eq!(
+            doc.metadata.dialect_hint,
+            DialectHint::Named("iosxr".into())
+        );
+    }
+}
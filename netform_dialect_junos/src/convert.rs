@@ -0,0 +1,338 @@
+//! Conversion between Junos curly-brace and `display set` representations.
+//!
+//! [`hierarchical_to_set`] is exact: every statement is flattened to the
+//! `set` line Junos prints for it, in configuration order. Diff an intended
+//! set-style file against `hierarchical_to_set(actual)` to avoid spurious edits.
+//!
+//! [`set_to_hierarchical`] has no schema to tell containers from leaf values,
+//! so it is best-effort: tokens shared by several `set` lines become
+//! containers, known `keyword name` pairs (`unit 0`, `family inet`, ...) share
+//! one header, and the remaining tokens of a line form one leaf statement.
+
+use std::collections::BTreeSet;
+
+use netform_ir::{Document, LineNode, Node, NodeId, TriviaKind};
+
+use crate::parse_junos;
+
+/// Keywords whose container header carries a name (`unit 0 {`).
+const NAMED_CONTAINERS: &[&str] = &[
+    "address",
+    "area",
+    "community",
+    "family",
+    "filter",
+    "group",
+    "instance",
+    "interface",
+    "neighbor",
+    "policy-statement",
+    "prefix-list",
+    "security-zone",
+    "server",
+    "term",
+    "unit",
+    "user",
+    "zone",
+];
+
+/// Top-level containers whose direct children are always named containers.
+const NAMED_LISTS: &[&str] = &["groups", "interfaces", "routing-instances", "vlans"];
+
+/// Convert a curly-brace Junos document into `display set` form.
+///
+/// Comments and annotations are dropped; `inactive:` statements also emit a
+/// `deactivate` line after their `set` lines.
+pub fn hierarchical_to_set(doc: &Document) -> Document {
+    let mut tokens = Vec::new();
+    for root in &doc.roots {
+        collect_tokens(doc, *root, &mut tokens);
+    }
+
+    let mut out = SetWriter::default();
+    let mut words: Vec<String> = Vec::new();
+    // Open containers: (path length before push, emitted anything, inactive).
+    let mut open: Vec<(usize, bool, bool)> = Vec::new();
+    let mut path: Vec<String> = Vec::new();
+
+    for token in tokens {
+        match token.as_str() {
+            "{" => {
+                let inactive = strip_inactive(&mut words);
+                open.push((path.len(), false, inactive));
+                path.append(&mut words);
+            }
+            ";" => {
+                let inactive = strip_inactive(&mut words);
+                out.leaf(&path, &words, inactive);
+                words.clear();
+                mark_emitted(&mut open);
+            }
+            "}" => {
+                if !words.is_empty() {
+                    // Junos allows the last statement before `}` to omit `;`.
+                    let inactive = strip_inactive(&mut words);
+                    out.leaf(&path, &words, inactive);
+                    words.clear();
+                    mark_emitted(&mut open);
+                }
+                let Some((len, emitted, inactive)) = open.pop() else {
+                    continue;
+                };
+                if !emitted {
+                    out.set(&path);
+                }
+                if inactive {
+                    out.deactivate(&path);
+                }
+                path.truncate(len);
+                mark_emitted(&mut open);
+            }
+            _ => words.push(token),
+        }
+    }
+
+    parse_junos(&out.text)
+}
+
+/// Convert a `display set` Junos document into curly-brace form (best-effort).
+///
+/// Only `set` and `deactivate` lines are read; other lines are dropped.
+pub fn set_to_hierarchical(doc: &Document) -> Document {
+    let mut root = TrieNode::default();
+    let mut inactive = BTreeSet::new();
+
+    for (id, _) in doc.walk() {
+        let Some(Node::Line(line)) = doc.node(id) else {
+            continue;
+        };
+        let Some(parsed) = line
+            .parsed
+            .as_ref()
+            .filter(|_| line.trivia == TriviaKind::Content)
+        else {
+            continue;
+        };
+        match parsed.head.as_str() {
+            "set" => root.insert(&parsed.args),
+            "deactivate" => {
+                inactive.insert(parsed.args.join(" "));
+            }
+            _ => {}
+        }
+    }
+
+    let mut text = String::new();
+    for (token, child) in &root.children {
+        render_node(token, child, &[], false, &inactive, &mut text);
+    }
+    parse_junos(&text)
+}
+
+/// Append content tokens of `id` in render order: header, children, footer.
+fn collect_tokens(doc: &Document, id: NodeId, out: &mut Vec<String>) {
+    match doc.node(id) {
+        Some(Node::Line(line)) => push_line_tokens(line, out),
+        Some(Node::Block(block)) => {
+            push_line_tokens(&block.header, out);
+            for child in &block.children {
+                collect_tokens(doc, *child, out);
+            }
+            if let Some(footer) = &block.footer {
+                push_line_tokens(footer, out);
+            }
+        }
+        None => {}
+    }
+}
+
+fn push_line_tokens(line: &LineNode, out: &mut Vec<String>) {
+    if line.trivia != TriviaKind::Content {
+        return;
+    }
+    if let Some(parsed) = &line.parsed {
+        out.push(parsed.head.clone());
+        out.extend(parsed.args.iter().cloned());
+    }
+}
+
+fn strip_inactive(words: &mut Vec<String>) -> bool {
+    if words.first().is_some_and(|w| w == "inactive:") {
+        words.remove(0);
+        return true;
+    }
+    false
+}
+
+fn mark_emitted(open: &mut [(usize, bool, bool)]) {
+    if let Some(last) = open.last_mut() {
+        last.1 = true;
+    }
+}
+
+#[derive(Debug, Default)]
+struct SetWriter {
+    text: String,
+}
+
+impl SetWriter {
+    fn set(&mut self, path: &[String]) {
+        self.line("set", path.iter().map(String::as_str));
+    }
+
+    fn deactivate(&mut self, path: &[String]) {
+        self.line("deactivate", path.iter().map(String::as_str));
+    }
+
+    fn leaf(&mut self, path: &[String], words: &[String], inactive: bool) {
+        // `key [ a b ]` lists flatten to one `set` line per value and are
+        // deactivated as a whole through `key`.
+        let statement = match words.iter().position(|w| w == "[") {
+            Some(open) => {
+                let prefix = &words[..open];
+                for value in words[open + 1..].iter().filter(|w| *w != "]") {
+                    let statement = path.iter().chain(prefix).chain([value]);
+                    self.line("set", statement.map(String::as_str));
+                }
+                prefix
+            }
+            None => {
+                self.line("set", path.iter().chain(words).map(String::as_str));
+                words
+            }
+        };
+        if inactive {
+            self.line(
+                "deactivate",
+                path.iter().chain(statement).map(String::as_str),
+            );
+        }
+    }
+
+    fn line<'a>(&mut self, verb: &str, tokens: impl Iterator<Item = &'a str>) {
+        self.text.push_str(verb);
+        for token in tokens {
+            self.text.push(' ');
+            self.text.push_str(token);
+        }
+        self.text.push('\n');
+    }
+}
+
+#[derive(Debug, Default)]
+struct TrieNode {
+    /// Children in first-seen order, matching `display set` ordering.
+    children: Vec<(String, TrieNode)>,
+}
+
+impl TrieNode {
+    fn insert(&mut self, tokens: &[String]) {
+        let Some((first, rest)) = tokens.split_first() else {
+            return;
+        };
+        let idx = match self.children.iter().position(|(token, _)| token == first) {
+            Some(idx) => idx,
+            None => {
+                self.children.push((first.clone(), TrieNode::default()));
+                self.children.len() - 1
+            }
+        };
+        self.children[idx].1.insert(rest);
+    }
+
+    /// Tokens of a single-child chain below this node, or `None` if it branches.
+    fn chain(&self) -> Option<Vec<String>> {
+        let mut out = Vec::new();
+        let mut node = self;
+        loop {
+            match node.children.as_slice() {
+                [] => return Some(out),
+                [(token, only)] => {
+                    out.push(token.clone());
+                    node = only;
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+/// Render `token` and its subtree as leaf statements or container blocks.
+fn render_node(
+    token: &str,
+    node: &TrieNode,
+    parent_path: &[String],
+    force_container: bool,
+    inactive: &BTreeSet<String>,
+    out: &mut String,
+) {
+    if NAMED_CONTAINERS.contains(&token) && !node.children.is_empty() {
+        // One `keyword name` header per name (`unit 0 {`, `unit 1 {`); a
+        // deactivated `keyword` (a value list) deactivates every name.
+        let mut path = parent_path.to_vec();
+        path.push(token.to_string());
+        let deactivated = inactive.contains(&path.join(" "));
+        for (name, child) in &node.children {
+            render_block(
+                &format!("{token} {name}"),
+                child,
+                parent_path,
+                deactivated,
+                inactive,
+                out,
+            );
+        }
+        return;
+    }
+
+    if !force_container {
+        // `keyword` and `keyword value` chains are leaves; longer chains nest.
+        if let Some(rest) = node.chain().filter(|rest| rest.len() <= 1) {
+            let statement = std::iter::once(token.to_string())
+                .chain(rest)
+                .collect::<Vec<_>>()
+                .join(" ");
+            render_block(
+                &statement,
+                &TrieNode::default(),
+                parent_path,
+                false,
+                inactive,
+                out,
+            );
+            return;
+        }
+    }
+
+    render_block(token, node, parent_path, false, inactive, out);
+}
+
+fn render_block(
+    header: &str,
+    node: &TrieNode,
+    parent_path: &[String],
+    deactivated: bool,
+    inactive: &BTreeSet<String>,
+    out: &mut String,
+) {
+    let indent = "    ".repeat(parent_path.len());
+    let mut path = parent_path.to_vec();
+    path.push(header.to_string());
+    let prefix = if deactivated || inactive.contains(&path.join(" ")) {
+        "inactive: "
+    } else {
+        ""
+    };
+
+    if node.children.is_empty() {
+        out.push_str(&format!("{indent}{prefix}{header};\n"));
+        return;
+    }
+
+    out.push_str(&format!("{indent}{prefix}{header} {{\n"));
+    let named_list = parent_path.is_empty() && NAMED_LISTS.contains(&header);
+    for (child_token, child) in &node.children {
+        render_node(child_token, child, &path, named_list, inactive, out);
+    }
+    out.push_str(&format!("{indent}}}\n"));
+}
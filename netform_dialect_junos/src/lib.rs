@@ -3,6 +3,11 @@
 //! This crate provides a conservative Junos profile that customizes:
 //! - comment classification (`#`, `/*`, `*`, `*/`)
 //! - line tokenization for braces/semicolons and quoted strings
+//! - brace-driven block structure, independent of indentation
+//! - conversion between curly-brace and `display set` forms ([`hierarchical_to_set`],
+//!   [`set_to_hierarchical`])
+//! - `encrypted-password`, `authentication-key`, `secret`, `ascii-text`, and `hexadecimal`
+//!   values as secrets, plus `community` names inside `snmp`
 //!
 //! # Example
 //!
@@ -14,7 +19,27 @@
 //! assert_eq!(doc.render(), cfg);
 //! ```
 
-use netform_ir::{Dialect, DialectHint, Document, ParsedLineParts, TriviaKind, parse_with_dialect};
+mod convert;
+
+pub use convert::{hierarchical_to_set, set_to_hierarchical};
+
+use netform_ir::{
+    BlockRole, Dialect, DialectHint, DialectRegistry, Document, ParsedLineParts, SecretKeyword,
+    SecretSpan, TriviaKind, parse_with_dialect, secret_values_after,
+};
+
+/// Keywords whose following value is a secret.
+const SECRET_KEYWORDS: &[SecretKeyword] = &[
+    SecretKeyword::plain("encrypted-password"),
+    SecretKeyword::plain("authentication-key"),
+    SecretKeyword::plain("secret"),
+    SecretKeyword::plain("ascii-text"),
+    SecretKeyword::plain("hexadecimal"),
+];
+
+/// Secret keywords that apply inside `snmp` only; elsewhere `community` names
+/// a routing policy community.
+const SNMP_SECRET_KEYWORDS: &[SecretKeyword] = &[SecretKeyword::plain("community")];
 
 /// Dialect implementation for Junos-like configuration text.
 #[derive(Debug, Default, Clone, Copy)]
@@ -25,6 +50,11 @@ pub fn parse_junos(input: &str) -> Document {
     parse_with_dialect(input, &JunosDialect)
 }
 
+/// Register [`JunosDialect`] as `junos` in `registry`.
+pub fn register(registry: &mut DialectRegistry) {
+    registry.register("junos", Box::new(JunosDialect));
+}
+
 impl Dialect for JunosDialect {
     fn dialect_hint(&self) -> DialectHint {
         DialectHint::Named("junos".to_string())
@@ -49,6 +79,39 @@ impl Dialect for JunosDialect {
         }
         junos_key_hint(parsed)
     }
+
+    fn secret_spans(
+        &self,
+        raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        parents: &[&ParsedLineParts],
+        trivia: TriviaKind,
+    ) -> Vec<SecretSpan> {
+        if trivia != TriviaKind::Content {
+            return Vec::new();
+        }
+        if parents.iter().any(|parent| parent.head == "snmp")
+            || parsed.is_some_and(|parsed| {
+                parsed.head == "set" && parsed.args.first().is_some_and(|arg| arg == "snmp")
+            })
+        {
+            secret_values_after(raw, &[SECRET_KEYWORDS, SNMP_SECRET_KEYWORDS].concat())
+        } else {
+            secret_values_after(raw, SECRET_KEYWORDS)
+        }
+    }
+
+    fn block_role(
+        &self,
+        _raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Option<BlockRole> {
+        if trivia != TriviaKind::Content {
+            return None;
+        }
+        junos_block_role(parsed?)
+    }
 }
 
 fn classify_junos_trivia(raw: &str) -> TriviaKind {
@@ -131,6 +194,29 @@ fn tokenize_junos(raw: &str) -> Vec<String> {
     tokens
 }
 
+fn junos_block_role(parsed: &ParsedLineParts) -> Option<BlockRole> {
+    let tokens = std::iter::once(&parsed.head).chain(parsed.args.iter());
+    let mut depth = 0isize;
+    let mut terminated = false;
+    for token in tokens {
+        match token.as_str() {
+            "{" => depth += 1,
+            "}" => depth -= 1,
+            ";" => terminated = true,
+            _ => {}
+        }
+    }
+
+    match depth {
+        d if d > 0 => Some(BlockRole::Open),
+        d if d < 0 => Some(BlockRole::Close),
+        // Balanced one-line blocks and `;` statements are leaves in curly syntax.
+        _ if terminated || parsed.args.iter().any(|arg| arg == "{") => Some(BlockRole::Leaf),
+        // Set-style and other untokenized shapes fall back to indentation.
+        _ => None,
+    }
+}
+
 fn junos_key_hint(parsed: Option<&ParsedLineParts>) -> Option<String> {
     let parsed = parsed?;
     let head = parsed.head.as_str();
@@ -161,6 +247,7 @@ fn set_style_key_hint(args: &[String]) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use netform_ir::Node;
 
     #[test]
     fn junos_comment_classification_supports_hash_and_block_styles() {
@@ -170,6 +257,57 @@ mod tests {
         assert_eq!(classify_junos_trivia("interfaces {"), TriviaKind::Content);
     }
 
+    #[test]
+    fn junos_reports_quoted_secret_values() {
+        let raw = "    encrypted-password \"$6$salt$hash\";";
+        let spans = JunosDialect.secret_spans(raw, None, &[], TriviaKind::Content);
+        assert_eq!(
+            spans
+                .iter()
+                .map(|span| &raw[span.start..span.end])
+                .collect::<Vec<_>>(),
+            vec!["$6$salt$hash"]
+        );
+        assert!(
+            JunosDialect
+                .secret_spans("# encrypted-password x;", None, &[], TriviaKind::Comment)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn junos_communities_are_secret_only_inside_snmp() {
+        let doc = parse_junos(
+            "snmp {\n    community s3cret {\n        authorization read-only;\n    }\n}\npolicy-options {\n    community CL-A members 65000:100;\n}\n",
+        );
+        let values = doc
+            .arena
+            .iter()
+            .map(|node| match node {
+                Node::Line(line) => line,
+                Node::Block(block) => &block.header,
+            })
+            .flat_map(|line| {
+                line.secrets
+                    .iter()
+                    .map(|span| line.raw[span.start..span.end].to_string())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec!["s3cret"]);
+
+        let set = parse_junos(
+            "set snmp community s3cret authorization read-only\nset policy-options community CL-A members 65000:100\n",
+        );
+        let Some(Node::Line(line)) = set.node(set.roots[0]) else {
+            panic!("expected set line");
+        };
+        assert_eq!(line.secrets.len(), 1);
+        let Some(Node::Line(line)) = set.node(set.roots[1]) else {
+            panic!("expected set line");
+        };
+        assert!(line.secrets.is_empty());
+    }
+
     #[test]
     fn junos_tokenization_keeps_brace_and_semicolon_tokens() {
         let parsed = parse_junos_parts("interfaces {").expect("content should parse");
@@ -182,6 +320,122 @@ mod tests {
         assert_eq!(parsed.args, vec!["\"Uplink to core\"", ";"]);
     }
 
+    #[test]
+    fn junos_block_role_follows_brace_tokens() {
+        let role = |raw: &str| junos_block_role(&parse_junos_parts(raw).expect("content"));
+        assert_eq!(role("interfaces {"), Some(BlockRole::Open));
+        assert_eq!(role("}"), Some(BlockRole::Close));
+        assert_eq!(role("disable;"), Some(BlockRole::Leaf));
+        assert_eq!(role("ge-0/0/0 { disable; }"), Some(BlockRole::Leaf));
+        assert_eq!(
+            role("description \"{ not a brace }\";"),
+            Some(BlockRole::Leaf)
+        );
+        assert_eq!(role("set system host-name edge-1"), None);
+    }
+
+    #[test]
+    fn parse_junos_builds_blocks_from_braces_despite_broken_indentation() {
+        let cfg = "interfaces {\nge-0/0/0 {\n      description \"uplink\";\n  disable;\n        }\n}\nsystem {\n    host-name edge-1;\n}\n";
+        let doc = parse_junos(cfg);
+        assert_eq!(doc.render(), cfg);
+        assert_eq!(doc.roots.len(), 2);
+
+        let Some(Node::Block(interfaces)) = doc.node(doc.roots[0]) else {
+            panic!("expected interfaces block");
+        };
+        assert_eq!(interfaces.children.len(), 1);
+        assert_eq!(
+            interfaces.footer.as_ref().map(|f| f.raw.as_str()),
+            Some("}")
+        );
+
+        let Some(Node::Block(port)) = doc.node(interfaces.children[0]) else {
+            panic!("expected ge-0/0/0 block");
+        };
+        assert_eq!(port.children.len(), 2);
+        assert_eq!(
+            port.footer.as_ref().map(|f| f.raw.as_str()),
+            Some("        }")
+        );
+        assert!(doc.metadata.parse_findings.is_empty());
+    }
+
+    #[test]
+    fn parse_junos_reports_unbalanced_braces() {
+        let cfg = "}\ninterfaces {\n    ge-0/0/0 {\n        disable;\n    }\n";
+        let doc = parse_junos(cfg);
+        assert_eq!(doc.render(), cfg);
+
+        let codes = doc
+            .metadata
+            .parse_findings
+            .iter()
+            .map(|f| f.code.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(codes, vec!["unmatched-block-close", "unclosed-block"]);
+    }
+
+    #[test]
+    fn hierarchical_to_set_flattens_statements_in_order() {
+        let cfg = "system {\n    host-name edge-1;\n    services { ssh; }\n}\ninterfaces {\n    inactive: ge-0/0/0 {\n        unit 0 {\n            family inet {\n                address 10.0.0.1/31;\n            }\n        }\n    }\n    ge-0/0/1 {\n        disable;\n    }\n}\nvlans {\n    v10 {\n        interface [ ge-0/0/2 ge-0/0/3 ];\n    }\n}\n";
+        let doc = hierarchical_to_set(&parse_junos(cfg));
+        assert_eq!(
+            doc.render(),
+            "set system host-name edge-1\n\
+             set system services ssh\n\
+             set interfaces ge-0/0/0 unit 0 family inet address 10.0.0.1/31\n\
+             deactivate interfaces ge-0/0/0\n\
+             set interfaces ge-0/0/1 disable\n\
+             set vlans v10 interface ge-0/0/2\n\
+             set vlans v10 interface ge-0/0/3\n"
+        );
+        assert_eq!(
+            doc.metadata.dialect_hint,
+            DialectHint::Named("junos".into())
+        );
+    }
+
+    #[test]
+    fn set_to_hierarchical_nests_shared_prefixes() {
+        let set = "set system host-name edge-1\nset system ntp server 192.0.2.1\nset interfaces ge-0/0/0 disable\ndeactivate interfaces ge-0/0/0\nset interfaces ge-0/0/1 unit 0 family inet address 10.0.0.1/31\nset interfaces ge-0/0/1 unit 1 description \"core link\"\n";
+        let doc = set_to_hierarchical(&parse_junos(set));
+        assert_eq!(
+            doc.render(),
+            "system {\n    host-name edge-1;\n    ntp {\n        server 192.0.2.1;\n    }\n}\n\
+             interfaces {\n    inactive: ge-0/0/0 {\n        disable;\n    }\n    ge-0/0/1 {\n\
+             \x20       unit 0 {\n            family inet {\n                address 10.0.0.1/31;\n\
+             \x20           }\n        }\n        unit 1 {\n            description \"core link\";\n\
+             \x20       }\n    }\n}\n"
+        );
+        assert!(doc.metadata.parse_findings.is_empty());
+        assert_eq!(hierarchical_to_set(&doc).render(), set);
+    }
+
+    #[test]
+    fn inactive_value_lists_round_trip_through_set_form() {
+        let set = hierarchical_to_set(&parse_junos("inactive: foo [ a b ];\n"));
+        assert_eq!(set.render(), "set foo a\nset foo b\ndeactivate foo\n");
+        let curly = set_to_hierarchical(&set);
+        assert_eq!(curly.render(), "inactive: foo {\n    a;\n    b;\n}\n");
+        assert_eq!(hierarchical_to_set(&curly).render(), set.render());
+
+        // Named lists come back as one inactive statement per value.
+        let cfg =
+            "vlans {\n    v10 {\n        inactive: interface [ ge-0/0/2 ge-0/0/3 ];\n    }\n}\n";
+        let set = hierarchical_to_set(&parse_junos(cfg));
+        assert_eq!(
+            set.render(),
+            "set vlans v10 interface ge-0/0/2\n\
+             set vlans v10 interface ge-0/0/3\n\
+             deactivate vlans v10 interface\n"
+        );
+        assert_eq!(
+            set_to_hierarchical(&set).render(),
+            "vlans {\n    v10 {\n        inactive: interface ge-0/0/2;\n        inactive: interface ge-0/0/3;\n    }\n}\n"
+        );
+    }
+
     #[test]
     fn parse_junos_sets_named_dialect_hint() {
         let doc = parse_junos("set system host-name router-1\n");
@@ -0,0 +1,335 @@
+//! Cisco NX-OS-oriented dialect profile for `netform_ir`.
+//!
+//! This crate provides a conservative NX-OS profile that customizes:
+//! - comment classification (`!`, `#`, including `!Command:`/`!Time:` banners)
+//! - tokenization with quoted-string preservation
+//! - key hints for data-center stanzas (`feature`, `vrf context`, `vpc domain`, ...)
+//! - `secret`, `password`, `key-string`, `md5`, and `pre-shared-key` values as secrets, plus
+//!   `snmp-server community` strings
+//! - abbreviated interface names (`Eth1/1`, `po10`) expanded in key hints and
+//!   `LineNode::interfaces`
+//!
+//! # Example
+//!
+//! ```rust
+//! use netform_dialect_nxos::parse_nxos;
+//!
+//! let cfg = "interface Ethernet1/1\n  description \"Uplink\"\n";
+//! let doc = parse_nxos(cfg);
+//! assert_eq!(doc.render(), cfg);
+//! ```
+
+use netform_ir::{
+    Dialect, DialectHint, DialectRegistry, Document, InterfaceName, ParsedLineParts, SecretKeyword,
+    SecretSpan, TriviaKind, canonical_interface_name, interface_names_in, parse_with_dialect,
+    secret_values_after,
+};
+
+/// Keywords whose following value is a secret.
+const SECRET_KEYWORDS: &[SecretKeyword] = &[
+    SecretKeyword::typed("secret"),
+    SecretKeyword::typed("password"),
+    SecretKeyword::typed("key-string"),
+    SecretKeyword::typed("md5"),
+    SecretKeyword::typed("pre-shared-key"),
+];
+
+/// Secret keywords that apply on `snmp-server` lines only, where `community`
+/// is a community string rather than a route-map or BGP community.
+const SNMP_SECRET_KEYWORDS: &[SecretKeyword] = &[SecretKeyword::plain("community")];
+
+/// Interface types as `(full name, shortest abbreviation)`.
+const INTERFACE_TYPES: &[(&str, &str)] = &[
+    ("Ethernet", "Eth"),
+    ("port-channel", "po"),
+    ("loopback", "lo"),
+    ("mgmt", "mgmt"),
+    ("Vlan", "Vl"),
+    ("Tunnel", "Tu"),
+];
+
+/// Dialect implementation for NX-OS-like configuration text.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NxosDialect;
+
+/// Parse text using [`NxosDialect`].
+pub fn parse_nxos(input: &str) -> Document {
+    parse_with_dialect(input, &NxosDialect)
+}
+
+/// Register [`NxosDialect`] as `nxos` in `registry`.
+pub fn register(registry: &mut DialectRegistry) {
+    registry.register("nxos", Box::new(NxosDialect));
+}
+
+impl Dialect for NxosDialect {
+    fn dialect_hint(&self) -> DialectHint {
+        DialectHint::Named("nxos".to_string())
+    }
+
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        classify_nxos_trivia(raw)
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        parse_nxos_parts(raw)
+    }
+
+    fn key_hint(
+        &self,
+        _raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Option<String> {
+        if trivia != TriviaKind::Content {
+            return None;
+        }
+        nxos_key_hint(parsed)
+    }
+
+    fn secret_spans(
+        &self,
+        raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        _parents: &[&ParsedLineParts],
+        trivia: TriviaKind,
+    ) -> Vec<SecretSpan> {
+        if trivia != TriviaKind::Content {
+            return Vec::new();
+        }
+        if parsed.is_some_and(|parsed| parsed.head == "snmp-server") {
+            secret_values_after(raw, &[SECRET_KEYWORDS, SNMP_SECRET_KEYWORDS].concat())
+        } else {
+            secret_values_after(raw, SECRET_KEYWORDS)
+        }
+    }
+
+    fn interface_names(
+        &self,
+        raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Vec<InterfaceName> {
+        if trivia != TriviaKind::Content {
+            return Vec::new();
+        }
+        interface_names_in(raw, INTERFACE_TYPES)
+    }
+}
+
+fn classify_nxos_trivia(raw: &str) -> TriviaKind {
+    if raw.trim().is_empty() {
+        return TriviaKind::Blank;
+    }
+
+    let trimmed = raw.trim_start();
+    if trimmed.starts_with('!') || trimmed.starts_with('#') {
+        return TriviaKind::Comment;
+    }
+
+    TriviaKind::Content
+}
+
+fn parse_nxos_parts(raw: &str) -> Option<ParsedLineParts> {
+    let tokens = tokenize_nxos(raw);
+    let head = tokens.first()?.clone();
+    let args = tokens.into_iter().skip(1).collect::<Vec<_>>();
+    Some(ParsedLineParts { head, args })
+}
+
+fn tokenize_nxos(raw: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quote: Option<char> = None;
+    let mut escape = false;
+
+    for ch in raw.chars() {
+        if let Some(q) = in_quote {
+            if escape {
+                current.push(ch);
+                escape = false;
+                continue;
+            }
+
+            if ch == '\\' {
+                current.push(ch);
+                escape = true;
+                continue;
+            }
+
+            current.push(ch);
+            if ch == q {
+                in_quote = None;
+            }
+            continue;
+        }
+
+        match ch {
+            '"' | '\'' => {
+                current.push(ch);
+                in_quote = Some(ch);
+            }
+            c if c.is_whitespace() => {
+                if !current.trim().is_empty() {
+                    tokens.push(current.trim().to_string());
+                    current.clear();
+                }
+            }
+            _ => current.push(ch),
+        }
+    }
+
+    if !current.trim().is_empty() {
+        tokens.push(current.trim().to_string());
+    }
+
+    tokens
+}
+
+fn nxos_key_hint(parsed: Option<&ParsedLineParts>) -> Option<String> {
+    let parsed = parsed?;
+    let head = parsed.head.as_str();
+    let args = parsed.args.as_slice();
+
+    match head {
+        // `interface port-channel 10` and `interface port-channel10` name the same
+        // port; range members are canonicalized one by one and joined with `, `.
+        "interface" if !args.is_empty() => {
+            let members = args
+                .join(" ")
+                .split(',')
+                .map(|member| member.split_whitespace().collect::<String>())
+                .filter(|member| !member.is_empty())
+                .map(|member| canonical_interface(&member))
+                .collect::<Vec<_>>();
+            Some(format!("interface:{}", members.join(", ")))
+        }
+        "vlan" => args.first().map(|id| format!("vlan:{id}")),
+        "feature" => args.first().map(|name| format!("feature:{name}")),
+        "port-channel" => args.first().map(|kind| format!("port-channel:{kind}")),
+        "vrf" => match args {
+            [next, name, ..] if next == "context" => Some(format!("vrf:{name}")),
+            _ => None,
+        },
+        "vpc" => match args {
+            [next, id, ..] if next == "domain" => Some(format!("vpc-domain:{id}")),
+            _ => None,
+        },
+        "router" => match args {
+            [proto, asn, ..] if proto == "bgp" => Some(format!("router:bgp:{asn}")),
+            [proto, tag, ..] => Some(format!("router:{proto}:{tag}")),
+            [proto] => Some(format!("router:{proto}")),
+            _ => None,
+        },
+        "route-map" => match args {
+            [name, action, seq, ..] => Some(format!("route-map:{name}:{action}:{seq}")),
+            [name, action] => Some(format!("route-map:{name}:{action}")),
+            _ => None,
+        },
+        "ip" => match args {
+            [next, name, ..] if next == "access-list" => Some(format!("ip-access-list:{name}")),
+            [next, name, ..] if next == "prefix-list" => Some(format!("prefix-list:{name}")),
+            _ => None,
+        },
+        "line" => args.first().map(|kind| format!("line:{kind}")),
+        _ => None,
+    }
+}
+
+/// Canonical spelling of interface `name`, or `name` unchanged.
+fn canonical_interface(name: &str) -> String {
+    canonical_interface_name(name, INTERFACE_TYPES).unwrap_or_else(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hint(raw: &str) -> Option<String> {
+        nxos_key_hint(parse_nxos_parts(raw).as_ref())
+    }
+
+    #[test]
+    fn nxos_comment_classification_supports_bang_and_hash() {
+        assert_eq!(
+            classify_nxos_trivia("!Command: show running-config"),
+            TriviaKind::Comment
+        );
+        assert_eq!(classify_nxos_trivia("# generated"), TriviaKind::Comment);
+        assert_eq!(classify_nxos_trivia("feature lacp"), TriviaKind::Content);
+    }
+
+    #[test]
+    fn nxos_tokenization_keeps_quoted_values_together() {
+        let parsed =
+            parse_nxos_parts("description \"vPC peer link\"").expect("content should parse");
+        assert_eq!(parsed.head, "description");
+        assert_eq!(parsed.args, vec!["\"vPC peer link\""]);
+    }
+
+    #[test]
+    fn nxos_key_hints_cover_data_center_stanzas() {
+        assert_eq!(
+            hint("interface Ethernet1/1").as_deref(),
+            Some("interface:Ethernet1/1")
+        );
+        assert_eq!(
+            hint("interface port-channel 10").as_deref(),
+            Some("interface:port-channel10")
+        );
+        assert_eq!(
+            hint("interface Eth1/1").as_deref(),
+            Some("interface:Ethernet1/1")
+        );
+        assert_eq!(
+            hint("interface po 10").as_deref(),
+            Some("interface:port-channel10")
+        );
+        assert_eq!(
+            hint("interface Ethernet1/1, Ethernet1/2").as_deref(),
+            Some("interface:Ethernet1/1, Ethernet1/2")
+        );
+        assert_eq!(
+            hint("interface Eth1/1,Eth1/2").as_deref(),
+            Some("interface:Ethernet1/1, Ethernet1/2")
+        );
+        assert_ne!(
+            hint("interface Ethernet1/1, Ethernet1/2"),
+            hint("interface Ethernet1/1")
+        );
+        assert_eq!(hint("vlan 10").as_deref(), Some("vlan:10"));
+        assert_eq!(
+            hint("vrf context TENANT-A").as_deref(),
+            Some("vrf:TENANT-A")
+        );
+        assert_eq!(
+            hint("router bgp 65001").as_deref(),
+            Some("router:bgp:65001")
+        );
+        assert_eq!(
+            hint("router ospf UNDERLAY").as_deref(),
+            Some("router:ospf:UNDERLAY")
+        );
+        assert_eq!(hint("feature nv overlay").as_deref(), Some("feature:nv"));
+        assert_eq!(
+            hint("route-map RM-OUT permit 10").as_deref(),
+            Some("route-map:RM-OUT:permit:10")
+        );
+        assert_eq!(
+            hint("ip prefix-list PL-LOOPBACKS seq 5 permit 10.0.0.0/24 le 32").as_deref(),
+            Some("prefix-list:PL-LOOPBACKS")
+        );
+        assert_eq!(
+            hint("port-channel load-balance src-dst ip").as_deref(),
+            Some("port-channel:load-balance")
+        );
+        assert_eq!(hint("vpc domain 100").as_deref(), Some("vpc-domain:100"));
+        assert_eq!(hint("vrf member TENANT-A"), None);
+    }
+
+    #[test]
+    fn parse_nxos_sets_named_dialect_hint() {
+        let doc = parse_nxos("hostname leaf-01\n");
+        assert_eq!(doc.metadata.dialect_hint, DialectHint::Named("nxos".into()));
+    }
+}
@@ -0,0 +1,423 @@
+//! MikroTik RouterOS-oriented dialect profile for `netform_ir`.
+//!
+//! This crate provides a conservative RouterOS `/export` profile that customizes:
+//! - comment classification (`#`, including the export header)
+//! - `\` line continuations, joined into one line that keeps the original breaks
+//! - tokenization for quoted values, `key=value` args, and `[ find ... ]` selectors
+//! - path lines (`/ip address`) as sections holding every entry up to the next path
+//! - key hints from identifying attributes (`name=`, `default-name=`, `address=`)
+//! - `password=`, `secret=`, pre-shared key, and `tcp-md5-key=` values as secrets
+//!
+//! # Example
+//!
+//! ```rust
+//! use netform_dialect_routeros::parse_routeros;
+//!
+//! let cfg = "/interface bridge\nadd name=bridge-lan \\\n    protocol-mode=none\n";
+//! let doc = parse_routeros(cfg);
+//! assert_eq!(doc.render(), cfg);
+//! ```
+
+use netform_ir::{
+    BlockRole, Dialect, DialectHint, DialectRegistry, Document, ParsedLineParts, SecretSpan,
+    TriviaKind, parse_with_dialect,
+};
+
+/// Attributes that identify an entry within its path, in priority order.
+const IDENTITY_ATTRS: &[&str] = &["name", "default-name", "address"];
+
+/// Attributes whose value is a secret.
+const SECRET_ATTRS: &[&str] = &[
+    "password",
+    "secret",
+    "wpa-pre-shared-key",
+    "wpa2-pre-shared-key",
+    "tcp-md5-key",
+];
+
+/// Dialect implementation for RouterOS export text.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RouterosDialect;
+
+/// Parse text using [`RouterosDialect`].
+pub fn parse_routeros(input: &str) -> Document {
+    parse_with_dialect(input, &RouterosDialect)
+}
+
+/// Register [`RouterosDialect`] as `routeros` in `registry`.
+pub fn register(registry: &mut DialectRegistry) {
+    registry.register("routeros", Box::new(RouterosDialect));
+}
+
+/// `key=value` attributes of an entry line, with surrounding quotes removed.
+///
+/// Attributes inside a `[ find ... ]` selector are skipped; they pick the
+/// entry rather than configure it.
+pub fn entry_attributes(parsed: &ParsedLineParts) -> Vec<(&str, &str)> {
+    let mut depth = 0usize;
+    let mut out = Vec::new();
+    for arg in &parsed.args {
+        match arg.as_str() {
+            "[" => depth += 1,
+            "]" => depth = depth.saturating_sub(1),
+            _ if depth == 0 => {
+                if let Some((key, value)) = split_attribute(arg) {
+                    out.push((key, value));
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+impl Dialect for RouterosDialect {
+    fn dialect_hint(&self) -> DialectHint {
+        DialectHint::Named("routeros".to_string())
+    }
+
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        classify_routeros_trivia(raw)
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        parse_routeros_parts(raw)
+    }
+
+    fn key_hint(
+        &self,
+        _raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Option<String> {
+        if trivia != TriviaKind::Content {
+            return None;
+        }
+        routeros_key_hint(parsed)
+    }
+
+    fn secret_spans(
+        &self,
+        raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        _parents: &[&ParsedLineParts],
+        trivia: TriviaKind,
+    ) -> Vec<SecretSpan> {
+        if trivia != TriviaKind::Content {
+            return Vec::new();
+        }
+        routeros_secret_spans(raw)
+    }
+
+    fn block_role(
+        &self,
+        _raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Option<BlockRole> {
+        if trivia != TriviaKind::Content {
+            return None;
+        }
+        parsed?.head.starts_with('/').then_some(BlockRole::Section)
+    }
+
+    fn continues_line(&self, raw: &str) -> bool {
+        raw.trim_end().ends_with('\\')
+    }
+}
+
+fn classify_routeros_trivia(raw: &str) -> TriviaKind {
+    if raw.trim().is_empty() {
+        return TriviaKind::Blank;
+    }
+
+    if raw.trim_start().starts_with('#') {
+        return TriviaKind::Comment;
+    }
+
+    TriviaKind::Content
+}
+
+fn parse_routeros_parts(raw: &str) -> Option<ParsedLineParts> {
+    let tokens = tokenize_routeros(raw);
+    let head = tokens.first()?.clone();
+    let args = tokens.into_iter().skip(1).collect::<Vec<_>>();
+    Some(ParsedLineParts { head, args })
+}
+
+fn tokenize_routeros(raw: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quote = false;
+    let mut chars = raw.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch == '\\' && matches!(chars.peek(), Some('\r' | '\n')) {
+            // Continuation: drop the break and the next line's indentation.
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            if !in_quote && !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        if in_quote {
+            current.push(ch);
+            if ch == '\\' {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            } else if ch == '"' {
+                in_quote = false;
+            }
+            continue;
+        }
+
+        match ch {
+            '"' => {
+                current.push(ch);
+                in_quote = true;
+            }
+            '[' | ']' => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+                tokens.push(ch.to_string());
+            }
+            c if c.is_whitespace() => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            _ => current.push(ch),
+        }
+    }
+
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+fn routeros_key_hint(parsed: Option<&ParsedLineParts>) -> Option<String> {
+    let parsed = parsed?;
+    let args = parsed.args.as_slice();
+
+    if parsed.head.starts_with('/') {
+        let path = std::iter::once(&parsed.head)
+            .chain(args)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+        return Some(format!("path:{path}"));
+    }
+
+    match parsed.head.as_str() {
+        "add" => identity(args),
+        "set" => match args.first().map(String::as_str) {
+            // `set [ find default-name=ether1 ] ...` is keyed by its selector.
+            Some("[") => {
+                let end = args.iter().position(|arg| arg == "]")?;
+                identity(&args[1..end])
+            }
+            // `set ether1 ...` names the entry positionally.
+            Some(item) if split_attribute(item).is_none() => Some(format!("item:{item}")),
+            // Singleton menus (`/system identity`) hold one `set` line.
+            _ => Some("set".to_string()),
+        },
+        _ => None,
+    }
+}
+
+/// Value spans of [`SECRET_ATTRS`] attributes, without surrounding quotes.
+fn routeros_secret_spans(raw: &str) -> Vec<SecretSpan> {
+    let mut out = Vec::new();
+    let mut pos = 0usize;
+    while let Some(offset) = raw[pos..].find(|c: char| !c.is_whitespace()) {
+        let start = pos + offset;
+        let end = token_end(raw, start);
+        if let Some((key, value)) = raw[start..end].split_once('=')
+            && SECRET_ATTRS.contains(&key)
+        {
+            let value_start = start + key.len() + 1;
+            let unquoted = unquote(value);
+            let value_start = value_start + usize::from(unquoted.len() != value.len());
+            if !unquoted.is_empty() {
+                out.push(SecretSpan {
+                    start: value_start,
+                    end: value_start + unquoted.len(),
+                });
+            }
+        }
+        pos = end;
+    }
+    out
+}
+
+/// End of the token starting at `start`; quoted runs may hold spaces.
+fn token_end(raw: &str, start: usize) -> usize {
+    let mut in_quote = false;
+    let mut chars = raw[start..].char_indices();
+    while let Some((idx, ch)) = chars.next() {
+        match ch {
+            '\\' if in_quote => {
+                chars.next();
+            }
+            '"' => in_quote = !in_quote,
+            c if c.is_whitespace() && !in_quote => return start + idx,
+            _ => {}
+        }
+    }
+    raw.len()
+}
+
+fn identity(args: &[String]) -> Option<String> {
+    IDENTITY_ATTRS.iter().find_map(|attr| {
+        args.iter()
+            .filter_map(|arg| split_attribute(arg))
+            .find(|(key, _)| key == attr)
+            .map(|(_, value)| format!("{attr}:{value}"))
+    })
+}
+
+fn split_attribute(token: &str) -> Option<(&str, &str)> {
+    let (key, value) = token.split_once('=')?;
+    (!key.is_empty() && !key.starts_with('"')).then_some((key, unquote(value)))
+}
+
+fn unquote(token: &str) -> &str {
+    token
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .unwrap_or(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use netform_ir::Node;
+
+    fn hint(raw: &str) -> Option<String> {
+        routeros_key_hint(parse_routeros_parts(raw).as_ref())
+    }
+
+    #[test]
+    fn routeros_tokenization_handles_quotes_selectors_and_continuations() {
+        let parsed = parse_routeros_parts(
+            "set [find default-name=ether1] comment=\"wan \\\n    uplink\" \\\n    disabled=no",
+        )
+        .expect("content should parse");
+        assert_eq!(parsed.head, "set");
+        assert_eq!(
+            parsed.args,
+            vec![
+                "[",
+                "find",
+                "default-name=ether1",
+                "]",
+                "comment=\"wan uplink\"",
+                "disabled=no"
+            ]
+        );
+        assert_eq!(
+            entry_attributes(&parsed),
+            vec![("comment", "wan uplink"), ("disabled", "no")]
+        );
+    }
+
+    #[test]
+    fn routeros_key_hints_use_identifying_attributes() {
+        assert_eq!(hint("/ip address").as_deref(), Some("path:/ip address"));
+        assert_eq!(
+            hint("add name=bridge-lan protocol-mode=none").as_deref(),
+            Some("name:bridge-lan")
+        );
+        assert_eq!(
+            hint("add address=192.168.88.1/24 interface=bridge-lan network=192.168.88.0")
+                .as_deref(),
+            Some("address:192.168.88.1/24")
+        );
+        assert_eq!(
+            hint("set [ find default-name=ether1 ] name=wan").as_deref(),
+            Some("default-name:ether1")
+        );
+        assert_eq!(
+            hint("set ether2 comment=lan").as_deref(),
+            Some("item:ether2")
+        );
+        assert_eq!(hint("set name=edge-01").as_deref(), Some("set"));
+        assert_eq!(hint("add action=accept chain=input"), None);
+    }
+
+    #[test]
+    fn routeros_paths_group_entries_and_join_continuations() {
+        let cfg = "# 2024-05-01 12:00:00 by RouterOS 7.14.3\n# software id = ABCD-1234\n/interface bridge\nadd admin-mac=AA:BB:CC:00:00:01 auto-mac=no comment=defconf \\\n    name=bridge-lan\n/interface ethernet\nset [ find default-name=ether1 ] comment=wan\n/ip address\nadd address=192.168.88.1/24 interface=bridge-lan network=192.168.88.0\n/system identity\nset name=edge-01\n";
+        let doc = parse_routeros(cfg);
+        assert_eq!(doc.render(), cfg);
+        assert!(doc.metadata.parse_findings.is_empty());
+        assert_eq!(doc.roots.len(), 6);
+
+        let Some(Node::Block(bridge)) = doc.node(doc.roots[2]) else {
+            panic!("expected path block");
+        };
+        assert_eq!(
+            bridge.header.key_hint.as_deref(),
+            Some("path:/interface bridge")
+        );
+        let Some(Node::Line(entry)) = doc.node(bridge.children[0]) else {
+            panic!("expected joined entry line");
+        };
+        assert_eq!(entry.key_hint.as_deref(), Some("name:bridge-lan"));
+        assert!(entry.raw.contains('\n'));
+
+        let Some(Node::Block(ethernet)) = doc.node(doc.roots[3]) else {
+            panic!("expected path block");
+        };
+        assert!(matches!(
+            doc.node(ethernet.children[0]),
+            Some(Node::Line(line)) if line.key_hint.as_deref() == Some("default-name:ether1")
+        ));
+    }
+
+    #[test]
+    fn routeros_recomputed_spans_count_continuation_lines() {
+        let cfg = "/ip address\nadd address=10.0.0.1/24 \\\n    interface=ether1\nadd address=10.0.1.1/24 interface=ether2\n";
+        let doc = parse_routeros(cfg);
+        assert_eq!(doc.metadata.line_count, 4);
+
+        let mut recomputed = doc.clone();
+        recomputed.recompute_spans();
+        assert_eq!(recomputed.render(), cfg);
+        assert_eq!(recomputed.metadata.line_count, 4);
+        assert_eq!(recomputed.metadata.original_bytes, cfg.len());
+
+        let Some(Node::Block(path)) = recomputed.node(recomputed.roots[0]) else {
+            panic!("expected path block");
+        };
+        let Some(Node::Line(last)) = recomputed.node(path.children[1]) else {
+            panic!("expected entry line");
+        };
+        assert_eq!(last.span.line, 4);
+        assert_eq!(recomputed, doc);
+    }
+
+    #[test]
+    fn routeros_secret_attributes_are_reported() {
+        let raw = "add name=vpn password=\"s3cr et\" profile=default secret=abc";
+        let values = routeros_secret_spans(raw)
+            .into_iter()
+            .map(|span| &raw[span.start..span.end])
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec!["s3cr et", "abc"]);
+    }
+
+    #[test]
+    fn parse_routeros_sets_named_dialect_hint() {
+        let doc = parse_routeros("/system identity\nset name=edge-01\n");
+        assert_eq!(
+            doc.metadata.dialect_hint,
+            DialectHint::Named("routeros".into())
+        );
+    }
+}
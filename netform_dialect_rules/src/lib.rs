@@ -0,0 +1,647 @@
+//! Declarative dialect profiles for `netform_ir`, loaded from TOML or YAML rule files.
+//!
+//! A rule file describes a dialect as data instead of code:
+//! - `name`: dialect name recorded in `DocumentMetadata::dialect_hint`
+//! - `comment_prefixes`: lines starting with one of these (after indentation) are comments
+//! - `quote_chars`: characters that open and close quoted tokens (default `"`)
+//! - `separators`: lines that close the enclosing block as its footer, matched
+//!   with trailing whitespace removed so indentation counts (`#` only matches column 0)
+//! - `block_terminators`: trimmed lines that close the same-indent block as its footer
+//! - `key_hints`: `pattern`/`template` pairs; `{N}` in a pattern captures one token,
+//!   `*` skips one token, and the first matching pattern renders its template
+//!
+//! [`RuleDialect::new`] validates rules up front so a bad rule file fails with a
+//! [`RulesError`] naming the offending field instead of parsing text oddly.
+//!
+//! # Example
+//!
+//! ```rust
+//! use netform_dialect_rules::{DialectRules, RuleDialect};
+//!
+//! let rules = DialectRules::from_toml_str(
+//!     r#"
+//! name = "acme"
+//! comment_prefixes = ["!"]
+//! block_terminators = ["exit"]
+//!
+//! [[key_hints]]
+//! pattern = "interface {0}"
+//! template = "interface:{0}"
+//! "#,
+//! )
+//! .unwrap();
+//! let dialect = RuleDialect::new(rules).unwrap();
+//!
+//! let cfg = "interface ge1\n mtu 9000\nexit\n";
+//! let doc = dialect.parse(cfg);
+//! assert_eq!(doc.render(), cfg);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use netform_ir::{
+    BlockRole, Dialect, DialectHint, Document, ParsedLineParts, TriviaKind, parse_with_dialect,
+};
+use serde::{Deserialize, Serialize};
+
+/// Dialect description as written in a rule file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DialectRules {
+    /// Dialect name, recorded as [`DialectHint::Named`].
+    pub name: String,
+    /// Prefixes marking comment lines, checked after leading indentation.
+    #[serde(default)]
+    pub comment_prefixes: Vec<String>,
+    /// Single-character strings that quote tokens.
+    #[serde(default = "default_quote_chars")]
+    pub quote_chars: Vec<String>,
+    /// Lines (trailing whitespace removed) that close the enclosing block.
+    #[serde(default)]
+    pub separators: Vec<String>,
+    /// Trimmed lines that close the same-indent block.
+    #[serde(default)]
+    pub block_terminators: Vec<String>,
+    /// Key-hint rules, tried in order.
+    #[serde(default)]
+    pub key_hints: Vec<KeyHintRule>,
+}
+
+/// One `pattern` -> `template` key-hint rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeyHintRule {
+    /// Whitespace-separated tokens: literals, `{N}` captures, or `*`.
+    pub pattern: String,
+    /// Key text with `{N}` replaced by the captured tokens.
+    pub template: String,
+}
+
+fn default_quote_chars() -> Vec<String> {
+    vec!["\"".to_string()]
+}
+
+/// Error returned when a rule file cannot be loaded or fails validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RulesError {
+    /// The rule file could not be read.
+    Io { path: PathBuf, message: String },
+    /// The file extension is not `.toml`, `.yaml`, or `.yml`.
+    UnsupportedFormat(PathBuf),
+    /// The text is not valid TOML/YAML or does not match the rule schema.
+    Syntax {
+        format: &'static str,
+        message: String,
+    },
+    /// A field holds a value the dialect cannot use.
+    Invalid { field: String, message: String },
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, message } => write!(f, "cannot read {}: {message}", path.display()),
+            Self::UnsupportedFormat(path) => write!(
+                f,
+                "unsupported rule file {}: expected a .toml, .yaml, or .yml extension",
+                path.display()
+            ),
+            Self::Syntax { format, message } => write!(f, "invalid {format} rule file: {message}"),
+            Self::Invalid { field, message } => write!(f, "invalid `{field}`: {message}"),
+        }
+    }
+}
+
+impl Error for RulesError {}
+
+impl DialectRules {
+    /// Deserialize rules from TOML text.
+    pub fn from_toml_str(text: &str) -> Result<Self, RulesError> {
+        toml::from_str(text).map_err(|err| RulesError::Syntax {
+            format: "toml",
+            message: err.to_string().trim_end().to_string(),
+        })
+    }
+
+    /// Deserialize rules from YAML text.
+    pub fn from_yaml_str(text: &str) -> Result<Self, RulesError> {
+        serde_yaml::from_str(text).map_err(|err| RulesError::Syntax {
+            format: "yaml",
+            message: err.to_string(),
+        })
+    }
+
+    /// Read rules from a `.toml`, `.yaml`, or `.yml` file.
+    pub fn load(path: &Path) -> Result<Self, RulesError> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        let from_str = match extension.as_deref() {
+            Some("toml") => Self::from_toml_str,
+            Some("yaml" | "yml") => Self::from_yaml_str,
+            _ => return Err(RulesError::UnsupportedFormat(path.to_path_buf())),
+        };
+        let text = fs::read_to_string(path).map_err(|err| RulesError::Io {
+            path: path.to_path_buf(),
+            message: err.to_string(),
+        })?;
+        from_str(&text)
+    }
+}
+
+/// Dialect implementation driven by validated [`DialectRules`].
+#[derive(Debug, Clone)]
+pub struct RuleDialect {
+    rules: DialectRules,
+    quote_chars: Vec<char>,
+    key_hints: Vec<CompiledKeyHint>,
+}
+
+#[derive(Debug, Clone)]
+struct CompiledKeyHint {
+    pattern: Vec<PatternToken>,
+    template: Vec<TemplatePart>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PatternToken {
+    Literal(String),
+    Capture(usize),
+    Any,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplatePart {
+    Text(String),
+    Capture(usize),
+}
+
+impl RuleDialect {
+    /// Validate `rules` and build the dialect.
+    pub fn new(rules: DialectRules) -> Result<Self, RulesError> {
+        if rules.name.is_empty() || rules.name.contains(char::is_whitespace) {
+            return Err(invalid("name", "must be a non-empty word"));
+        }
+        for (idx, prefix) in rules.comment_prefixes.iter().enumerate() {
+            if prefix.trim().is_empty() {
+                return Err(invalid(
+                    format!("comment_prefixes[{idx}]"),
+                    "must not be empty",
+                ));
+            }
+        }
+        let mut quote_chars = Vec::new();
+        for (idx, quote) in rules.quote_chars.iter().enumerate() {
+            let mut chars = quote.chars();
+            match (chars.next(), chars.next()) {
+                (Some(ch), None) if !ch.is_whitespace() => quote_chars.push(ch),
+                _ => {
+                    return Err(invalid(
+                        format!("quote_chars[{idx}]"),
+                        format!("`{quote}` must be a single non-whitespace character"),
+                    ));
+                }
+            }
+        }
+        for (idx, separator) in rules.separators.iter().enumerate() {
+            if separator.trim().is_empty() {
+                return Err(invalid(format!("separators[{idx}]"), "must not be empty"));
+            }
+        }
+        for (idx, terminator) in rules.block_terminators.iter().enumerate() {
+            if terminator.trim().is_empty() {
+                return Err(invalid(
+                    format!("block_terminators[{idx}]"),
+                    "must not be empty",
+                ));
+            }
+        }
+        let key_hints = rules
+            .key_hints
+            .iter()
+            .enumerate()
+            .map(|(idx, rule)| compile_key_hint(idx, rule))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            rules,
+            quote_chars,
+            key_hints,
+        })
+    }
+
+    /// Load and validate a rule file (see [`DialectRules::load`]).
+    pub fn load(path: &Path) -> Result<Self, RulesError> {
+        Self::new(DialectRules::load(path)?)
+    }
+
+    /// Rules this dialect was built from.
+    pub fn rules(&self) -> &DialectRules {
+        &self.rules
+    }
+
+    /// Parse text using this dialect.
+    pub fn parse(&self, input: &str) -> Document {
+        parse_with_dialect(input, self)
+    }
+}
+
+impl Dialect for RuleDialect {
+    fn dialect_hint(&self) -> DialectHint {
+        DialectHint::Named(self.rules.name.clone())
+    }
+
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        if raw.trim().is_empty() {
+            return TriviaKind::Blank;
+        }
+
+        let trimmed = raw.trim_start();
+        if self
+            .rules
+            .comment_prefixes
+            .iter()
+            .any(|prefix| trimmed.starts_with(prefix.as_str()))
+        {
+            return TriviaKind::Comment;
+        }
+
+        TriviaKind::Content
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        let tokens = self.tokenize(raw);
+        let head = tokens.first()?.clone();
+        let args = tokens.into_iter().skip(1).collect::<Vec<_>>();
+        Some(ParsedLineParts { head, args })
+    }
+
+    fn key_hint(
+        &self,
+        _raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Option<String> {
+        if trivia != TriviaKind::Content {
+            return None;
+        }
+        let parsed = parsed?;
+        let tokens = std::iter::once(&parsed.head)
+            .chain(parsed.args.iter())
+            .map(|token| self.unquote(token))
+            .collect::<Vec<_>>();
+        self.key_hints.iter().find_map(|rule| rule.render(&tokens))
+    }
+
+    fn block_role(
+        &self,
+        raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        _trivia: TriviaKind,
+    ) -> Option<BlockRole> {
+        // Separators may also be comment lines (`#`, `!`), so roles ignore trivia.
+        let line = raw.trim_end();
+        if self.rules.separators.iter().any(|sep| sep == line) {
+            return Some(BlockRole::Exit);
+        }
+        let trimmed = raw.trim();
+        if self
+            .rules
+            .block_terminators
+            .iter()
+            .any(|term| term == trimmed)
+        {
+            return Some(BlockRole::Terminator);
+        }
+        None
+    }
+}
+
+impl RuleDialect {
+    fn tokenize(&self, raw: &str) -> Vec<String> {
+        let mut tokens = Vec::new();
+        let mut current = String::new();
+        let mut in_quote: Option<char> = None;
+        let mut escape = false;
+
+        for ch in raw.chars() {
+            if let Some(q) = in_quote {
+                if escape {
+                    current.push(ch);
+                    escape = false;
+                    continue;
+                }
+
+                if ch == '\\' {
+                    current.push(ch);
+                    escape = true;
+                    continue;
+                }
+
+                current.push(ch);
+                if ch == q {
+                    in_quote = None;
+                }
+                continue;
+            }
+
+            match ch {
+                c if self.quote_chars.contains(&c) => {
+                    current.push(c);
+                    in_quote = Some(c);
+                }
+                c if c.is_whitespace() => {
+                    if !current.is_empty() {
+                        tokens.push(std::mem::take(&mut current));
+                    }
+                }
+                _ => current.push(ch),
+            }
+        }
+
+        if !current.is_empty() {
+            tokens.push(current);
+        }
+
+        tokens
+    }
+
+    fn unquote<'a>(&self, token: &'a str) -> &'a str {
+        self.quote_chars
+            .iter()
+            .find_map(|q| {
+                token
+                    .strip_prefix(*q)
+                    .and_then(|rest| rest.strip_suffix(*q))
+            })
+            .unwrap_or(token)
+    }
+}
+
+impl CompiledKeyHint {
+    fn render(&self, tokens: &[&str]) -> Option<String> {
+        if tokens.len() < self.pattern.len() {
+            return None;
+        }
+        let mut captures = Vec::new();
+        for (pattern, token) in self.pattern.iter().zip(tokens) {
+            match pattern {
+                PatternToken::Literal(text) if text != token => return None,
+                PatternToken::Capture(idx) => captures.push((*idx, *token)),
+                _ => {}
+            }
+        }
+
+        let mut out = String::new();
+        for part in &self.template {
+            match part {
+                TemplatePart::Text(text) => out.push_str(text),
+                TemplatePart::Capture(idx) => {
+                    let (_, token) = captures.iter().find(|(capture, _)| capture == idx)?;
+                    out.push_str(token);
+                }
+            }
+        }
+        Some(out)
+    }
+}
+
+fn compile_key_hint(idx: usize, rule: &KeyHintRule) -> Result<CompiledKeyHint, RulesError> {
+    let pattern_field = format!("key_hints[{idx}].pattern");
+    let mut pattern = Vec::new();
+    for token in rule.pattern.split_whitespace() {
+        let compiled = if token == "*" {
+            PatternToken::Any
+        } else if let Some(capture) = placeholder(token) {
+            if pattern.contains(&PatternToken::Capture(capture)) {
+                return Err(invalid(
+                    pattern_field,
+                    format!("captures `{{{capture}}}` more than once"),
+                ));
+            }
+            PatternToken::Capture(capture)
+        } else if token.contains(['{', '}']) {
+            return Err(invalid(
+                pattern_field,
+                format!(
+                    "`{token}` mixes text and braces; captures must be whole tokens like `{{0}}`"
+                ),
+            ));
+        } else {
+            PatternToken::Literal(token.to_string())
+        };
+        pattern.push(compiled);
+    }
+    if !matches!(pattern.first(), Some(PatternToken::Literal(_))) {
+        return Err(invalid(
+            pattern_field,
+            "must start with a literal token (the line's head)",
+        ));
+    }
+
+    let template_field = format!("key_hints[{idx}].template");
+    if rule.template.trim().is_empty() {
+        return Err(invalid(template_field, "must not be empty"));
+    }
+    let mut template = Vec::new();
+    let mut rest = rule.template.as_str();
+    while !rest.is_empty() {
+        let Some(open) = rest.find(['{', '}']) else {
+            template.push(TemplatePart::Text(rest.to_string()));
+            break;
+        };
+        if open > 0 {
+            template.push(TemplatePart::Text(rest[..open].to_string()));
+        }
+        let close = rest[open..].find('}').map(|pos| open + pos);
+        let capture = close.and_then(|close| placeholder(&rest[open..=close]));
+        let (Some(close), Some(capture)) = (close, capture) else {
+            return Err(invalid(
+                template_field,
+                format!(
+                    "`{}` has an unmatched brace; placeholders look like `{{0}}`",
+                    rule.template
+                ),
+            ));
+        };
+        if !pattern.contains(&PatternToken::Capture(capture)) {
+            return Err(invalid(
+                template_field,
+                format!(
+                    "`{{{capture}}}` is not captured by pattern `{}`",
+                    rule.pattern
+                ),
+            ));
+        }
+        template.push(TemplatePart::Capture(capture));
+        rest = &rest[close + 1..];
+    }
+
+    Ok(CompiledKeyHint { pattern, template })
+}
+
+/// Capture index of a whole `{N}` token.
+fn placeholder(token: &str) -> Option<usize> {
+    token.strip_prefix('{')?.strip_suffix('}')?.parse().ok()
+}
+
+fn invalid(field: impl Into<String>, message: impl Into<String>) -> RulesError {
+    RulesError::Invalid {
+        field: field.into(),
+        message: message.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use netform_ir::Node;
+
+    const ACME_TOML: &str = r##"
+name = "acme"
+comment_prefixes = ["!", "//"]
+quote_chars = ["\"", "'"]
+separators = ["#"]
+block_terminators = ["exit"]
+
+[[key_hints]]
+pattern = "interface {0}"
+template = "interface:{0}"
+
+[[key_hints]]
+pattern = "zone {0} * {1}"
+template = "zone:{0}:{1}"
+"##;
+
+    fn acme() -> RuleDialect {
+        RuleDialect::new(DialectRules::from_toml_str(ACME_TOML).expect("valid toml"))
+            .expect("valid rules")
+    }
+
+    fn invalid_field(rules: &str) -> String {
+        match RuleDialect::new(DialectRules::from_toml_str(rules).expect("valid toml")) {
+            Err(RulesError::Invalid { field, .. }) => field,
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rule_dialect_applies_comments_terminators_and_separators() {
+        let cfg = "// acme appliance\ninterface \"uplink 1\"\n mtu 9000\n zone trust\n  priority 10\n exit\n#\nzone dmz id 7\n allow icmp\nexit\n";
+        let doc = acme().parse(cfg);
+        assert_eq!(doc.render(), cfg);
+        assert!(doc.metadata.parse_findings.is_empty());
+        assert_eq!(doc.metadata.dialect_hint, DialectHint::Named("acme".into()));
+        assert_eq!(doc.roots.len(), 3);
+
+        let Some(Node::Block(interface)) = doc.node(doc.roots[1]) else {
+            panic!("expected interface block");
+        };
+        assert_eq!(
+            interface.header.key_hint.as_deref(),
+            Some("interface:uplink 1")
+        );
+        assert_eq!(interface.footer.as_ref().map(|f| f.raw.as_str()), Some("#"));
+        assert!(matches!(
+            doc.node(interface.children[1]),
+            Some(Node::Block(zone)) if zone.footer.as_ref().is_some_and(|f| f.raw == " exit")
+        ));
+
+        let Some(Node::Block(zone)) = doc.node(doc.roots[2]) else {
+            panic!("expected zone block");
+        };
+        assert_eq!(zone.header.key_hint.as_deref(), Some("zone:dmz:7"));
+        assert_eq!(zone.footer.as_ref().map(|f| f.raw.as_str()), Some("exit"));
+    }
+
+    #[test]
+    fn yaml_rules_match_toml_rules() {
+        let yaml = r##"
+name: acme
+comment_prefixes: ["!", "//"]
+quote_chars: ['"', "'"]
+separators: ["#"]
+block_terminators: [exit]
+key_hints:
+  - pattern: "interface {0}"
+    template: "interface:{0}"
+  - pattern: "zone {0} * {1}"
+    template: "zone:{0}:{1}"
+"##;
+        assert_eq!(
+            DialectRules::from_yaml_str(yaml).expect("valid yaml"),
+            DialectRules::from_toml_str(ACME_TOML).expect("valid toml")
+        );
+    }
+
+    #[test]
+    fn quote_chars_default_to_double_quotes() {
+        let rules = DialectRules::from_toml_str("name = \"acme\"\n").expect("valid toml");
+        assert_eq!(rules.quote_chars, vec!["\""]);
+        assert!(rules.key_hints.is_empty());
+    }
+
+    #[test]
+    fn rule_validation_names_the_offending_field() {
+        assert_eq!(invalid_field("name = \"two words\"\n"), "name");
+        assert_eq!(
+            invalid_field("name = \"acme\"\nquote_chars = [\"''\"]\n"),
+            "quote_chars[0]"
+        );
+        assert_eq!(
+            invalid_field("name = \"acme\"\nseparators = [\" \"]\n"),
+            "separators[0]"
+        );
+        assert_eq!(
+            invalid_field(
+                "name = \"acme\"\n[[key_hints]]\npattern = \"interface {0}\"\ntemplate = \"interface:{1}\"\n"
+            ),
+            "key_hints[0].template"
+        );
+        assert_eq!(
+            invalid_field(
+                "name = \"acme\"\n[[key_hints]]\npattern = \"interface {0\"\ntemplate = \"interface\"\n"
+            ),
+            "key_hints[0].pattern"
+        );
+        assert_eq!(
+            invalid_field(
+                "name = \"acme\"\n[[key_hints]]\npattern = \"{0}\"\ntemplate = \"any:{0}\"\n"
+            ),
+            "key_hints[0].pattern"
+        );
+
+        let err = RuleDialect::new(
+            DialectRules::from_toml_str(
+                "name = \"acme\"\n[[key_hints]]\npattern = \"vlan {0}\"\ntemplate = \"vlan:{0\"\n",
+            )
+            .expect("valid toml"),
+        )
+        .expect_err("unmatched brace");
+        assert_eq!(
+            err.to_string(),
+            "invalid `key_hints[0].template`: `vlan:{0` has an unmatched brace; placeholders look like `{0}`"
+        );
+    }
+
+    #[test]
+    fn rule_files_reject_unknown_fields_and_extensions() {
+        let err = DialectRules::from_toml_str("name = \"acme\"\ncomment = [\"!\"]\n")
+            .expect_err("unknown field");
+        assert!(matches!(err, RulesError::Syntax { format: "toml", .. }));
+        assert!(err.to_string().contains("unknown field `comment`"));
+
+        assert!(matches!(
+            DialectRules::load(Path::new("acme.json")),
+            Err(RulesError::UnsupportedFormat(_))
+        ));
+        assert!(matches!(
+            DialectRules::load(Path::new("/nonexistent/acme.toml")),
+            Err(RulesError::Io { .. })
+        ));
+    }
+}
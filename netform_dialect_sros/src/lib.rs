@@ -0,0 +1,414 @@
+//! Nokia SR OS-oriented dialect profile for `netform_ir`.
+//!
+//! This crate provides a conservative SR OS profile that customizes:
+//! - comment classification (`#`)
+//! - tokenization for braces and quoted strings
+//! - classic CLI structure: `exit` and `exit all` close the same-indent block as its footer
+//! - MD-CLI structure: `{`/`}` drive blocks, independent of indentation
+//! - key hints for `router`, services, `port`, `interface`, and bgp `group`/`neighbor`
+//! - `password`, `authentication-key`, and `secret` values as secrets, plus `community` names
+//!   inside `snmp`
+//!
+//! [`parse_sros`] selects the style from content via [`SrosStyle::detect`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use netform_dialect_sros::parse_sros;
+//!
+//! let cfg = "configure\n    port 1/1/1\n        no shutdown\n    exit\nexit all\n";
+//! let doc = parse_sros(cfg);
+//! assert_eq!(doc.render(), cfg);
+//! ```
+
+use netform_ir::{
+    BlockRole, Dialect, DialectHint, DialectRegistry, Document, ParsedLineParts, SecretKeyword,
+    SecretSpan, TriviaKind, parse_with_dialect, secret_values_after,
+};
+
+/// SR OS configuration syntax flavor.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SrosStyle {
+    /// Classic CLI with indentation and `exit` terminators.
+    #[default]
+    Classic,
+    /// Model-driven CLI with brace-delimited blocks.
+    MdCli,
+    /// Pick [`SrosStyle::Classic`] or [`SrosStyle::MdCli`] per document with
+    /// [`SrosStyle::detect`] in [`Dialect::parse_document`]; line-level calls
+    /// treat it as classic.
+    Detect,
+}
+
+impl SrosStyle {
+    /// Pick the style from content: any content line opening a `{` block means MD-CLI.
+    pub fn detect(input: &str) -> Self {
+        let md_cli = input.lines().any(|line| {
+            classify_sros_trivia(line) == TriviaKind::Content
+                && tokenize_sros(line).last().is_some_and(|token| token == "{")
+        });
+        if md_cli { Self::MdCli } else { Self::Classic }
+    }
+}
+
+/// Keywords whose following value is a secret.
+const SECRET_KEYWORDS: &[SecretKeyword] = &[
+    SecretKeyword::plain("password"),
+    SecretKeyword::plain("authentication-key"),
+    SecretKeyword::plain("secret"),
+];
+
+/// Secret keywords that apply inside `snmp` only; elsewhere `community` names
+/// a routing policy community.
+const SNMP_SECRET_KEYWORDS: &[SecretKeyword] = &[SecretKeyword::plain("community")];
+
+/// Dialect implementation for SR OS-like configuration text.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SrosDialect {
+    /// Syntax flavor used for block roles.
+    pub style: SrosStyle,
+}
+
+/// Parse text using [`SrosDialect`] with the style detected from `input`.
+pub fn parse_sros(input: &str) -> Document {
+    SrosDialect {
+        style: SrosStyle::Detect,
+    }
+    .parse_document(input)
+}
+
+/// Register [`SrosDialect`] as `sros` in `registry`, detecting the style per input.
+pub fn register(registry: &mut DialectRegistry) {
+    registry.register(
+        "sros",
+        Box::new(SrosDialect {
+            style: SrosStyle::Detect,
+        }),
+    );
+}
+
+impl Dialect for SrosDialect {
+    fn dialect_hint(&self) -> DialectHint {
+        DialectHint::Named("sros".to_string())
+    }
+
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        classify_sros_trivia(raw)
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        parse_sros_parts(raw)
+    }
+
+    fn key_hint(
+        &self,
+        _raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Option<String> {
+        if trivia != TriviaKind::Content {
+            return None;
+        }
+        sros_key_hint(parsed)
+    }
+
+    fn secret_spans(
+        &self,
+        raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        parents: &[&ParsedLineParts],
+        trivia: TriviaKind,
+    ) -> Vec<SecretSpan> {
+        if trivia != TriviaKind::Content {
+            return Vec::new();
+        }
+        if parents.iter().any(|parent| parent.head == "snmp") {
+            secret_values_after(raw, &[SECRET_KEYWORDS, SNMP_SECRET_KEYWORDS].concat())
+        } else {
+            secret_values_after(raw, SECRET_KEYWORDS)
+        }
+    }
+
+    fn block_role(
+        &self,
+        _raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Option<BlockRole> {
+        if trivia != TriviaKind::Content {
+            return None;
+        }
+        let parsed = parsed?;
+        match self.style {
+            SrosStyle::Classic | SrosStyle::Detect => classic_block_role(parsed),
+            SrosStyle::MdCli => md_cli_block_role(parsed),
+        }
+    }
+
+    fn parse_document(&self, input: &str) -> Document {
+        match self.style {
+            SrosStyle::Detect => parse_with_dialect(
+                input,
+                &SrosDialect {
+                    style: SrosStyle::detect(input),
+                },
+            ),
+            _ => parse_with_dialect(input, self),
+        }
+    }
+}
+
+fn classify_sros_trivia(raw: &str) -> TriviaKind {
+    if raw.trim().is_empty() {
+        return TriviaKind::Blank;
+    }
+
+    if raw.trim_start().starts_with('#') {
+        return TriviaKind::Comment;
+    }
+
+    TriviaKind::Content
+}
+
+fn parse_sros_parts(raw: &str) -> Option<ParsedLineParts> {
+    let tokens = tokenize_sros(raw);
+    let head = tokens.first()?.clone();
+    let args = tokens.into_iter().skip(1).collect::<Vec<_>>();
+    Some(ParsedLineParts { head, args })
+}
+
+fn tokenize_sros(raw: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quote: Option<char> = None;
+    let mut escape = false;
+
+    for ch in raw.chars() {
+        if let Some(q) = in_quote {
+            if escape {
+                current.push(ch);
+                escape = false;
+                continue;
+            }
+
+            if ch == '\\' {
+                current.push(ch);
+                escape = true;
+                continue;
+            }
+
+            current.push(ch);
+            if ch == q {
+                in_quote = None;
+            }
+            continue;
+        }
+
+        match ch {
+            '"' | '\'' => {
+                current.push(ch);
+                in_quote = Some(ch);
+            }
+            '{' | '}' => {
+                if !current.trim().is_empty() {
+                    tokens.push(current.trim().to_string());
+                }
+                current.clear();
+                tokens.push(ch.to_string());
+            }
+            c if c.is_whitespace() => {
+                if !current.trim().is_empty() {
+                    tokens.push(current.trim().to_string());
+                    current.clear();
+                }
+            }
+            _ => current.push(ch),
+        }
+    }
+
+    if !current.trim().is_empty() {
+        tokens.push(current.trim().to_string());
+    }
+
+    tokens
+}
+
+fn classic_block_role(parsed: &ParsedLineParts) -> Option<BlockRole> {
+    match (parsed.head.as_str(), parsed.args.as_slice()) {
+        ("exit", []) => Some(BlockRole::Terminator),
+        ("exit", [scope]) if scope == "all" => Some(BlockRole::Terminator),
+        _ => None,
+    }
+}
+
+fn md_cli_block_role(parsed: &ParsedLineParts) -> Option<BlockRole> {
+    let tokens = std::iter::once(&parsed.head).chain(parsed.args.iter());
+    let mut depth = 0isize;
+    for token in tokens {
+        match token.as_str() {
+            "{" => depth += 1,
+            "}" => depth -= 1,
+            _ => {}
+        }
+    }
+
+    match depth {
+        d if d > 0 => Some(BlockRole::Open),
+        d if d < 0 => Some(BlockRole::Close),
+        // MD-CLI leaves never carry children; structure comes from braces only.
+        _ => Some(BlockRole::Leaf),
+    }
+}
+
+fn sros_key_hint(parsed: Option<&ParsedLineParts>) -> Option<String> {
+    let parsed = parsed?;
+    let head = parsed.head.as_str();
+    let args = parsed
+        .args
+        .iter()
+        .map(|arg| unquote(arg))
+        .filter(|arg| *arg != "{")
+        .collect::<Vec<_>>();
+
+    match (head, args.as_slice()) {
+        // Classic `router` without a name configures the Base instance.
+        ("router", []) => Some("router:Base".to_string()),
+        ("router", [name, ..]) => Some(format!("router:{name}")),
+        ("service", [kind, id, ..]) => Some(format!("service:{kind}:{id}")),
+        // MD-CLI nests services by kind (`service { vprn "CUST-A" { ... } }`).
+        ("vprn" | "vpls" | "ies" | "epipe", [id, ..]) => Some(format!("service:{head}:{id}")),
+        ("port", [id, ..]) => Some(format!("port:{id}")),
+        ("interface", [name, ..]) => Some(format!("interface:{name}")),
+        ("group", [name, ..]) => Some(format!("bgp-group:{name}")),
+        ("neighbor", [addr, ..]) => Some(format!("neighbor:{addr}")),
+        _ => None,
+    }
+}
+
+fn unquote(token: &str) -> &str {
+    token
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .unwrap_or(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use netform_ir::Node;
+
+    #[test]
+    fn sros_style_detection_uses_brace_blocks() {
+        assert_eq!(
+            SrosStyle::detect("configure {\n    port 1/1/1 {\n    }\n}\n"),
+            SrosStyle::MdCli
+        );
+        assert_eq!(
+            SrosStyle::detect(
+                "# TiMOS-B-23.10.R1\nconfigure\n    system\n        name \"{pe}\"\n    exit\nexit all\n"
+            ),
+            SrosStyle::Classic
+        );
+    }
+
+    #[test]
+    fn sros_classic_exit_lines_become_footers() {
+        let cfg = "# TiMOS-B-23.10.R1\nconfigure\n    port 1/1/1\n        ethernet\n        exit\n        no shutdown\n    exit\n    router Base\n        interface \"system\"\n            address 10.0.0.1/32\n        exit\n        bgp\n            group \"ibgp\"\n                neighbor 10.0.0.2\n                exit\n            exit\n        exit\n    exit\n    service\n        vprn 100 customer 1 create\n            no shutdown\n        exit\n    exit\nexit all\n";
+        let doc = parse_sros(cfg);
+        assert_eq!(doc.render(), cfg);
+        assert!(doc.metadata.parse_findings.is_empty());
+        assert_eq!(doc.roots.len(), 2);
+
+        let Some(Node::Block(configure)) = doc.node(doc.roots[1]) else {
+            panic!("expected configure block");
+        };
+        assert_eq!(
+            configure.footer.as_ref().map(|f| f.raw.as_str()),
+            Some("exit all")
+        );
+        assert_eq!(configure.children.len(), 3);
+
+        let Some(Node::Block(port)) = doc.node(configure.children[0]) else {
+            panic!("expected port block");
+        };
+        assert_eq!(port.header.key_hint.as_deref(), Some("port:1/1/1"));
+        assert_eq!(
+            port.footer.as_ref().map(|f| f.raw.as_str()),
+            Some("    exit")
+        );
+        assert!(matches!(
+            doc.node(port.children[0]),
+            Some(Node::Block(ethernet)) if ethernet.children.is_empty() && ethernet.footer.is_some()
+        ));
+
+        let Some(Node::Block(router)) = doc.node(configure.children[1]) else {
+            panic!("expected router block");
+        };
+        assert_eq!(router.header.key_hint.as_deref(), Some("router:Base"));
+        let Some(Node::Block(bgp)) = doc.node(router.children[1]) else {
+            panic!("expected bgp block");
+        };
+        let Some(Node::Block(group)) = doc.node(bgp.children[0]) else {
+            panic!("expected bgp group block");
+        };
+        assert_eq!(group.header.key_hint.as_deref(), Some("bgp-group:ibgp"));
+        assert!(matches!(
+            doc.node(group.children[0]),
+            Some(Node::Block(neighbor)) if neighbor.header.key_hint.as_deref() == Some("neighbor:10.0.0.2")
+        ));
+
+        let Some(Node::Block(service)) = doc.node(configure.children[2]) else {
+            panic!("expected service block");
+        };
+        assert!(matches!(
+            doc.node(service.children[0]),
+            Some(Node::Block(vprn)) if vprn.header.key_hint.as_deref() == Some("service:vprn:100")
+        ));
+    }
+
+    #[test]
+    fn sros_md_cli_braces_drive_structure() {
+        let cfg = "configure {\n    router \"Base\" {\n        interface \"system\" {\n            ipv4 {\n                primary {\n                    address 10.0.0.1\n                    prefix-length 32\n                }\n            }\n        }\n        bgp {\n            group \"ibgp\" {\n                peer-as 65000\n            }\n            neighbor \"10.0.0.2\" {\n                group \"ibgp\"\n            }\n        }\n    }\n    service {\n        vprn \"CUST-A\" {\n            admin-state enable\n        }\n    }\n}\n";
+        let doc = parse_sros(cfg);
+        assert_eq!(doc.render(), cfg);
+        assert!(doc.metadata.parse_findings.is_empty());
+        assert_eq!(doc.roots.len(), 1);
+
+        let Some(Node::Block(configure)) = doc.node(doc.roots[0]) else {
+            panic!("expected configure block");
+        };
+        assert_eq!(configure.footer.as_ref().map(|f| f.raw.as_str()), Some("}"));
+        let Some(Node::Block(router)) = doc.node(configure.children[0]) else {
+            panic!("expected router block");
+        };
+        assert_eq!(router.header.key_hint.as_deref(), Some("router:Base"));
+        assert!(matches!(
+            doc.node(router.children[0]),
+            Some(Node::Block(interface)) if interface.header.key_hint.as_deref() == Some("interface:system")
+        ));
+
+        let Some(Node::Block(service)) = doc.node(configure.children[1]) else {
+            panic!("expected service block");
+        };
+        assert!(matches!(
+            doc.node(service.children[0]),
+            Some(Node::Block(vprn)) if vprn.header.key_hint.as_deref() == Some("service:vprn:CUST-A")
+        ));
+    }
+
+    #[test]
+    fn registered_sros_dialect_detects_style_per_document() {
+        let mut registry = DialectRegistry::new();
+        register(&mut registry);
+        let cfg = "configure {\n    port 1/1/1 {\n        admin-state enable\n    }\n}\n";
+        let doc = registry.parse("sros", cfg).expect("sros is registered");
+        assert_eq!(doc, parse_sros(cfg));
+        assert_eq!(doc.roots.len(), 1);
+    }
+
+    #[test]
+    fn parse_sros_sets_named_dialect_hint() {
+        let doc = parse_sros("configure\nexit all\n");
+        assert_eq!(doc.metadata.dialect_hint, DialectHint::Named("sros".into()));
+    }
+}
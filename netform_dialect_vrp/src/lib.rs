@@ -0,0 +1,330 @@
+//! Huawei VRP and H3C Comware-oriented dialect profile for `netform_ir`.
+//!
+//! This crate provides a conservative VRP profile that customizes:
+//! - comment classification (`#`, `!`, including `!Software Version` headers)
+//! - tokenization with quoted-string preservation
+//! - section separators: a column-0 `#` closes the open top-level block as its footer
+//! - `quit` and `return` close the innermost open block as its footer
+//! - key hints for `interface`, `bgp`, `ip vpn-instance`, `acl`, `route-policy`,
+//!   `ip ip-prefix`, and bgp address families, looking through `undo`
+//! - `cipher` and `irreversible-cipher` values as secrets
+//!
+//! Separator lines keep [`TriviaKind::Comment`] so comment normalization still
+//! drops them from comparison, but they no longer lose the block boundary.
+//!
+//! # Example
+//!
+//! ```rust
+//! use netform_dialect_vrp::parse_vrp;
+//!
+//! let cfg = "#\ninterface GigabitEthernet0/0/1\n ip address 10.0.0.1 255.255.255.0\n#\nreturn\n";
+//! let doc = parse_vrp(cfg);
+//! assert_eq!(doc.render(), cfg);
+//! ```
+
+use netform_ir::{
+    BlockRole, Dialect, DialectHint, DialectRegistry, Document, ParsedLineParts, SecretKeyword,
+    SecretSpan, TriviaKind, parse_with_dialect, secret_values_after,
+};
+
+/// Keywords whose following value is a secret.
+const SECRET_KEYWORDS: &[SecretKeyword] = &[
+    SecretKeyword::plain("cipher"),
+    SecretKeyword::plain("irreversible-cipher"),
+];
+
+/// Dialect implementation for VRP/Comware-like configuration text.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct VrpDialect;
+
+/// Parse text using [`VrpDialect`].
+pub fn parse_vrp(input: &str) -> Document {
+    parse_with_dialect(input, &VrpDialect)
+}
+
+/// Register [`VrpDialect`] as `vrp` in `registry`.
+pub fn register(registry: &mut DialectRegistry) {
+    registry.register("vrp", Box::new(VrpDialect));
+}
+
+impl Dialect for VrpDialect {
+    fn dialect_hint(&self) -> DialectHint {
+        DialectHint::Named("vrp".to_string())
+    }
+
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        classify_vrp_trivia(raw)
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        parse_vrp_parts(raw)
+    }
+
+    fn key_hint(
+        &self,
+        _raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Option<String> {
+        if trivia != TriviaKind::Content {
+            return None;
+        }
+        vrp_key_hint(parsed)
+    }
+
+    fn secret_spans(
+        &self,
+        raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        _parents: &[&ParsedLineParts],
+        trivia: TriviaKind,
+    ) -> Vec<SecretSpan> {
+        if trivia != TriviaKind::Content {
+            return Vec::new();
+        }
+        secret_values_after(raw, SECRET_KEYWORDS)
+    }
+
+    fn block_role(
+        &self,
+        raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        _trivia: TriviaKind,
+    ) -> Option<BlockRole> {
+        if raw.trim_end() == "#" {
+            // Only column-0 separators end a top-level view; indented ones split
+            // sub-views (bgp address families) and stay comment lines.
+            return Some(BlockRole::Exit);
+        }
+        let parsed = parsed?;
+        match (parsed.head.as_str(), parsed.args.is_empty()) {
+            ("quit", true) => Some(BlockRole::Exit),
+            ("return", true) if !raw.starts_with(char::is_whitespace) => Some(BlockRole::Exit),
+            _ => None,
+        }
+    }
+}
+
+fn classify_vrp_trivia(raw: &str) -> TriviaKind {
+    if raw.trim().is_empty() {
+        return TriviaKind::Blank;
+    }
+
+    let trimmed = raw.trim_start();
+    if trimmed.starts_with('#') || trimmed.starts_with('!') {
+        return TriviaKind::Comment;
+    }
+
+    TriviaKind::Content
+}
+
+fn parse_vrp_parts(raw: &str) -> Option<ParsedLineParts> {
+    let tokens = tokenize_vrp(raw);
+    let head = tokens.first()?.clone();
+    let args = tokens.into_iter().skip(1).collect::<Vec<_>>();
+    Some(ParsedLineParts { head, args })
+}
+
+fn tokenize_vrp(raw: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quote: Option<char> = None;
+    let mut escape = false;
+
+    for ch in raw.chars() {
+        if let Some(q) = in_quote {
+            if escape {
+                current.push(ch);
+                escape = false;
+                continue;
+            }
+
+            if ch == '\\' {
+                current.push(ch);
+                escape = true;
+                continue;
+            }
+
+            current.push(ch);
+            if ch == q {
+                in_quote = None;
+            }
+            continue;
+        }
+
+        match ch {
+            '"' | '\'' => {
+                current.push(ch);
+                in_quote = Some(ch);
+            }
+            c if c.is_whitespace() => {
+                if !current.trim().is_empty() {
+                    tokens.push(current.trim().to_string());
+                    current.clear();
+                }
+            }
+            _ => current.push(ch),
+        }
+    }
+
+    if !current.trim().is_empty() {
+        tokens.push(current.trim().to_string());
+    }
+
+    tokens
+}
+
+fn vrp_key_hint(parsed: Option<&ParsedLineParts>) -> Option<String> {
+    let parsed = parsed?;
+    // `undo <statement>` negates the statement it names and shares its key.
+    let (head, args) = match (parsed.head.as_str(), parsed.args.split_first()) {
+        ("undo", Some((head, args))) => (head.as_str(), args),
+        (head, _) => (head, parsed.args.as_slice()),
+    };
+
+    match head {
+        // `interface Eth-Trunk 1` and `interface Eth-Trunk1` name the same port.
+        "interface" if !args.is_empty() => Some(format!("interface:{}", args.concat())),
+        "bgp" => args.first().map(|asn| format!("bgp:{asn}")),
+        "ipv4-family" | "ipv6-family" => match args {
+            [kind, name, ..] if kind == "vpn-instance" => {
+                Some(format!("{head}:vpn-instance:{name}"))
+            }
+            [kind, ..] => Some(format!("{head}:{kind}")),
+            [] => Some(head.to_string()),
+        },
+        "acl" => match args {
+            // VRP `acl number 3000`/`acl name X`, Comware `acl advanced 3000`.
+            [kind, id, ..] if kind.parse::<u32>().is_err() => Some(format!("acl:{id}")),
+            [id, ..] => Some(format!("acl:{id}")),
+            [] => None,
+        },
+        "route-policy" => match args {
+            [name, action, node, seq, ..] if node == "node" => {
+                Some(format!("route-policy:{name}:{action}:{seq}"))
+            }
+            [name, ..] => Some(format!("route-policy:{name}")),
+            [] => None,
+        },
+        "ip" => match args {
+            [next, name, ..] if next == "vpn-instance" => Some(format!("vpn-instance:{name}")),
+            [next, name, index, seq, ..] if next == "ip-prefix" && index == "index" => {
+                Some(format!("ip-prefix:{name}:{seq}"))
+            }
+            [next, name, ..] if next == "ip-prefix" => Some(format!("ip-prefix:{name}")),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use netform_ir::Node;
+
+    fn hint(raw: &str) -> Option<String> {
+        vrp_key_hint(parse_vrp_parts(raw).as_ref())
+    }
+
+    #[test]
+    fn vrp_key_hints_cover_common_views() {
+        assert_eq!(
+            hint("interface GigabitEthernet0/0/1").as_deref(),
+            Some("interface:GigabitEthernet0/0/1")
+        );
+        assert_eq!(
+            hint("interface Eth-Trunk 1").as_deref(),
+            Some("interface:Eth-Trunk1")
+        );
+        assert_eq!(hint("bgp 65000").as_deref(), Some("bgp:65000"));
+        assert_eq!(
+            hint("ipv4-family vpn-instance CUST-A").as_deref(),
+            Some("ipv4-family:vpn-instance:CUST-A")
+        );
+        assert_eq!(
+            hint("ip vpn-instance CUST-A").as_deref(),
+            Some("vpn-instance:CUST-A")
+        );
+        assert_eq!(hint("acl number 3000").as_deref(), Some("acl:3000"));
+        assert_eq!(hint("acl advanced 3001").as_deref(), Some("acl:3001"));
+        assert_eq!(hint("acl name MGMT").as_deref(), Some("acl:MGMT"));
+        assert_eq!(
+            hint("route-policy RP-IN permit node 10").as_deref(),
+            Some("route-policy:RP-IN:permit:10")
+        );
+        assert_eq!(
+            hint("ip ip-prefix PL-LOOP index 10 permit 10.0.0.0 24").as_deref(),
+            Some("ip-prefix:PL-LOOP:10")
+        );
+        assert_eq!(
+            hint("undo ip vpn-instance CUST-B").as_deref(),
+            Some("vpn-instance:CUST-B")
+        );
+        assert_eq!(hint("undo shutdown"), None);
+    }
+
+    #[test]
+    fn vrp_hash_separators_and_return_close_views() {
+        let cfg = "!Software Version V800R021C10SPC100\n#\nsysname PE-1\n#\ninterface GigabitEthernet0/0/1\n undo shutdown\n ip binding vpn-instance CUST-A\n ip address 10.1.1.1 255.255.255.252\n#\nbgp 65000\n peer 10.0.0.2 as-number 65000\n #\n ipv4-family unicast\n  peer 10.0.0.2 enable\n #\n ipv4-family vpn-instance CUST-A\n  import-route direct\n#\nreturn\n";
+        let doc = parse_vrp(cfg);
+        assert_eq!(doc.render(), cfg);
+        assert!(doc.metadata.parse_findings.is_empty());
+        assert_eq!(doc.roots.len(), 7);
+
+        let Some(Node::Block(interface)) = doc.node(doc.roots[4]) else {
+            panic!("expected interface block");
+        };
+        assert_eq!(
+            interface.header.key_hint.as_deref(),
+            Some("interface:GigabitEthernet0/0/1")
+        );
+        assert_eq!(interface.footer.as_ref().map(|f| f.raw.as_str()), Some("#"));
+        assert_eq!(interface.children.len(), 3);
+
+        let Some(Node::Block(bgp)) = doc.node(doc.roots[5]) else {
+            panic!("expected bgp block");
+        };
+        assert_eq!(bgp.footer.as_ref().map(|f| f.raw.as_str()), Some("#"));
+        // Indented separators stay between the address families.
+        assert_eq!(bgp.children.len(), 5);
+        assert!(matches!(
+            doc.node(bgp.children[4]),
+            Some(Node::Block(family))
+                if family.header.key_hint.as_deref() == Some("ipv4-family:vpn-instance:CUST-A")
+        ));
+
+        // `return` after the last separator has nothing left to close.
+        assert!(matches!(doc.node(doc.roots[6]), Some(Node::Line(line)) if line.raw == "return"));
+    }
+
+    #[test]
+    fn vrp_quit_lines_become_footers() {
+        let cfg = "ip vpn-instance CUST-A\n ipv4-family\n  route-distinguisher 65000:1\n  quit\n quit\nacl number 3000\n rule 5 permit ip source 10.0.0.0 0.0.0.255\nquit\n";
+        let doc = parse_vrp(cfg);
+        assert_eq!(doc.render(), cfg);
+        assert!(doc.metadata.parse_findings.is_empty());
+        assert_eq!(doc.roots.len(), 2);
+
+        let Some(Node::Block(vpn)) = doc.node(doc.roots[0]) else {
+            panic!("expected vpn-instance block");
+        };
+        assert_eq!(vpn.header.key_hint.as_deref(), Some("vpn-instance:CUST-A"));
+        assert_eq!(vpn.footer.as_ref().map(|f| f.raw.as_str()), Some(" quit"));
+        assert!(matches!(
+            doc.node(vpn.children[0]),
+            Some(Node::Block(family)) if family.footer.as_ref().is_some_and(|f| f.raw == "  quit")
+        ));
+
+        let Some(Node::Block(acl)) = doc.node(doc.roots[1]) else {
+            panic!("expected acl block");
+        };
+        assert_eq!(acl.footer.as_ref().map(|f| f.raw.as_str()), Some("quit"));
+    }
+
+    #[test]
+    fn parse_vrp_sets_named_dialect_hint() {
+        let doc = parse_vrp("sysname PE-1\n");
+        assert_eq!(doc.metadata.dialect_hint, DialectHint::Named("vrp".into()));
+    }
+}
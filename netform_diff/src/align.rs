@@ -0,0 +1,287 @@
+//! Sequence alignment of comparison keys into edit operations.
+//!
+//! Every algorithm returns a script that turns `a` into `b`; they differ in
+//! which equal keys they prefer to keep aligned when a key repeats.
+
+use std::collections::HashMap;
+
+use crate::model::DiffAlgorithm;
+
+/// Histogram candidates occurring more often than this on the left are skipped.
+const MAX_HISTOGRAM_CHAIN: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Align two key sequences with `algorithm`.
+pub(crate) fn compute_ops(a: &[u64], b: &[u64], algorithm: DiffAlgorithm) -> Vec<Op> {
+    match algorithm {
+        DiffAlgorithm::Myers => myers_ops(a, b),
+        DiffAlgorithm::Patience => {
+            let mut ops = Vec::with_capacity(a.len().max(b.len()));
+            patience_ops(a, b, &mut ops);
+            ops
+        }
+        DiffAlgorithm::Histogram => {
+            let mut ops = Vec::with_capacity(a.len().max(b.len()));
+            histogram_ops(a, b, &mut ops);
+            ops
+        }
+    }
+}
+
+/// Split off the common prefix and suffix of `a` and `b`.
+///
+/// Returns the prefix and suffix lengths.
+fn common_affixes(a: &[u64], b: &[u64]) -> (usize, usize) {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    (prefix, suffix)
+}
+
+/// Patience diff: anchor on keys that occur exactly once on both sides, keep
+/// the longest run of anchors that appear in the same order, and recurse
+/// between them. Ranges without unique keys fall back to Myers.
+fn patience_ops(a: &[u64], b: &[u64], out: &mut Vec<Op>) {
+    let (prefix, suffix) = common_affixes(a, b);
+    out.extend(std::iter::repeat_n(Op::Equal, prefix));
+    let a_mid = &a[prefix..a.len() - suffix];
+    let b_mid = &b[prefix..b.len() - suffix];
+
+    let anchors = unique_anchors(a_mid, b_mid);
+    if anchors.is_empty() {
+        out.extend(myers_ops(a_mid, b_mid));
+    } else {
+        let (mut i, mut j) = (0usize, 0usize);
+        for (ai, bj) in anchors {
+            patience_ops(&a_mid[i..ai], &b_mid[j..bj], out);
+            out.push(Op::Equal);
+            i = ai + 1;
+            j = bj + 1;
+        }
+        patience_ops(&a_mid[i..], &b_mid[j..], out);
+    }
+
+    out.extend(std::iter::repeat_n(Op::Equal, suffix));
+}
+
+/// Positions of keys unique on both sides, reduced to their longest
+/// increasing run on the right.
+fn unique_anchors(a: &[u64], b: &[u64]) -> Vec<(usize, usize)> {
+    // Occurrences of each key as (left, right).
+    let mut counts: HashMap<u64, (usize, usize)> = HashMap::new();
+    for key in a {
+        counts.entry(*key).or_default().0 += 1;
+    }
+    let mut b_positions: HashMap<u64, usize> = HashMap::new();
+    for (idx, key) in b.iter().enumerate() {
+        if let Some(entry) = counts.get_mut(key) {
+            entry.1 += 1;
+            b_positions.insert(*key, idx);
+        }
+    }
+
+    let pairs = a
+        .iter()
+        .enumerate()
+        .filter(|(_, key)| counts.get(*key) == Some(&(1, 1)))
+        .map(|(idx, key)| (idx, b_positions[key]))
+        .collect::<Vec<_>>();
+
+    longest_increasing_run(&pairs)
+}
+
+/// Longest subsequence of `pairs` (ordered by left index) whose right indexes
+/// increase, by patience sorting.
+fn longest_increasing_run(pairs: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    // tails[k] = index into `pairs` ending the best run of length k + 1.
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous: Vec<Option<usize>> = vec![None; pairs.len()];
+    for (idx, &(_, right)) in pairs.iter().enumerate() {
+        let pile = tails.partition_point(|&tail| pairs[tail].1 < right);
+        previous[idx] = pile.checked_sub(1).map(|pile| tails[pile]);
+        if pile == tails.len() {
+            tails.push(idx);
+        } else {
+            tails[pile] = idx;
+        }
+    }
+
+    let mut run = Vec::with_capacity(tails.len());
+    let mut cursor = tails.last().copied();
+    while let Some(idx) = cursor {
+        run.push(pairs[idx]);
+        cursor = previous[idx];
+    }
+    run.reverse();
+    run
+}
+
+/// Histogram diff: split on the longest common run that starts at the key
+/// occurring least often on the left, then recurse on both sides of it.
+/// Keys repeated more than [`MAX_HISTOGRAM_CHAIN`] times are not used as
+/// split points; ranges without a usable split fall back to Myers.
+fn histogram_ops(a: &[u64], b: &[u64], out: &mut Vec<Op>) {
+    let (prefix, suffix) = common_affixes(a, b);
+    out.extend(std::iter::repeat_n(Op::Equal, prefix));
+    let a_mid = &a[prefix..a.len() - suffix];
+    let b_mid = &b[prefix..b.len() - suffix];
+
+    match histogram_split(a_mid, b_mid) {
+        Some((ai, bj, len)) => {
+            histogram_ops(&a_mid[..ai], &b_mid[..bj], out);
+            out.extend(std::iter::repeat_n(Op::Equal, len));
+            histogram_ops(&a_mid[ai + len..], &b_mid[bj + len..], out);
+        }
+        None => out.extend(myers_ops(a_mid, b_mid)),
+    }
+
+    out.extend(std::iter::repeat_n(Op::Equal, suffix));
+}
+
+/// Best split point as `(a_start, b_start, len)`: lowest left occurrence
+/// count first, then longest run, then earliest position.
+fn histogram_split(a: &[u64], b: &[u64]) -> Option<(usize, usize, usize)> {
+    let mut positions: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (idx, key) in a.iter().enumerate() {
+        positions.entry(*key).or_default().push(idx);
+    }
+
+    let mut best: Option<(usize, usize, usize, usize)> = None;
+    for (bj, key) in b.iter().enumerate() {
+        let Some(starts) = positions.get(key) else {
+            continue;
+        };
+        let count = starts.len();
+        if count > MAX_HISTOGRAM_CHAIN || best.is_some_and(|(best_count, ..)| count > best_count) {
+            continue;
+        }
+        for &ai in starts {
+            let len = a[ai..]
+                .iter()
+                .zip(&b[bj..])
+                .take_while(|(x, y)| x == y)
+                .count();
+            let better = match best {
+                None => true,
+                Some((best_count, _, _, best_len)) => {
+                    count < best_count || (count == best_count && len > best_len)
+                }
+            };
+            if better {
+                best = Some((count, ai, bj, len));
+            }
+        }
+    }
+
+    best.map(|(_, ai, bj, len)| (ai, bj, len))
+}
+
+/// Myers shortest edit script.
+fn myers_ops(a: &[u64], b: &[u64]) -> Vec<Op> {
+    if a.is_empty() {
+        return vec![Op::Insert; b.len()];
+    }
+    if b.is_empty() {
+        return vec![Op::Delete; a.len()];
+    }
+
+    let n = a.len() as isize;
+    let m = b.len() as isize;
+    let max = (a.len() + b.len()) as isize;
+    let offset = max + 1;
+    let v_len = (2 * max + 3) as usize;
+
+    // Myers SES trace over diagonals. This avoids the quadratic LCS matrix and
+    // remains deterministic for a fixed input/order.
+    let mut v = vec![0isize; v_len];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    for d in 0..=max {
+        let mut current = v.clone();
+        let mut k = -d;
+        while k <= d {
+            let idx = (k + offset) as usize;
+            let take_down = k == -d || (k != d && v[idx - 1] < v[idx + 1]);
+            let mut x = if take_down {
+                v[idx + 1]
+            } else {
+                v[idx - 1] + 1
+            };
+            let mut y = x - k;
+
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            current[idx] = x;
+
+            if x >= n && y >= m {
+                trace.push(current);
+                return backtrack_ops(a, b, &trace, offset);
+            }
+            k += 2;
+        }
+        trace.push(current.clone());
+        v = current;
+    }
+
+    Vec::new()
+}
+
+fn backtrack_ops(a: &[u64], b: &[u64], trace: &[Vec<isize>], offset: isize) -> Vec<Op> {
+    let mut x = a.len() as isize;
+    let mut y = b.len() as isize;
+    let mut rev_ops = Vec::new();
+
+    for d in (1..trace.len()).rev() {
+        let d = d as isize;
+        let k = x - y;
+        let prev = &trace[(d - 1) as usize];
+        let idx = (k + offset) as usize;
+        let go_down = k == -d || (k != d && prev[idx - 1] < prev[idx + 1]);
+        let prev_k = if go_down { k + 1 } else { k - 1 };
+        let prev_x = prev[(prev_k + offset) as usize];
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            rev_ops.push(Op::Equal);
+            x -= 1;
+            y -= 1;
+        }
+
+        if x == prev_x {
+            rev_ops.push(Op::Insert);
+            y -= 1;
+        } else {
+            rev_ops.push(Op::Delete);
+            x -= 1;
+        }
+    }
+
+    while x > 0 && y > 0 && a[(x - 1) as usize] == b[(y - 1) as usize] {
+        rev_ops.push(Op::Equal);
+        x -= 1;
+        y -= 1;
+    }
+    while x > 0 {
+        rev_ops.push(Op::Delete);
+        x -= 1;
+    }
+    while y > 0 {
+        rev_ops.push(Op::Insert);
+        y -= 1;
+    }
+
+    rev_ops.reverse();
+    rev_ops
+}
@@ -1,28 +1,26 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 
+use netform_ir::{Path, TriviaKind};
+
+use crate::align::{Op, compute_ops};
 use crate::model::{
-    ComparisonLine, ComparisonView, DiffLine, DiffStats, Edit, EditAnchor, NormalizeOptions,
-    OrderPolicy,
+    ComparisonLine, ComparisonView, DiffAlgorithm, DiffLine, DiffStats, Edit, EditAnchor, KeyKind,
+    NormalizeOptions, OrderPolicy, derive_content_key,
 };
+use crate::normalize::{conceal_digests, secret_digests};
+use crate::tokens::{conceal_token_digests, token_diffs};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Op {
-    Equal,
-    Delete,
-    Insert,
-}
-
-#[derive(Debug, Clone)]
-struct Segment {
-    lines: Vec<ComparisonLine>,
+#[derive(Debug, Clone, Copy)]
+struct Segment<'a> {
+    lines: &'a [ComparisonLine],
     segment_key: u64,
-    is_block: bool,
+    is_list: bool,
 }
 
 #[derive(Debug, Default)]
 pub(crate) struct DiffComputation {
     pub edits: Vec<Edit>,
-    pub fallback_contexts: Vec<netform_ir::Path>,
+    pub fallback_contexts: Vec<Path>,
 }
 
 pub(crate) fn diff_views(
@@ -30,9 +28,48 @@ pub(crate) fn diff_views(
     b: &ComparisonView,
     options: &NormalizeOptions,
 ) -> DiffComputation {
-    let a_segments = build_segments(a);
-    let b_segments = build_segments(b);
+    let mut computation = DiffComputation::default();
+    diff_children(
+        &a.lines,
+        &b.lines,
+        &Path(Vec::new()),
+        options,
+        &mut computation,
+    );
+    computation
+}
 
+/// Diff everything below one parent context.
+///
+/// `a` and `b` hold all descendant lines of `parent`. Its direct children are
+/// aligned with the policy resolved for `parent` itself, and each aligned pair
+/// of blocks recurses with the policy of its own path.
+fn diff_children(
+    a: &[ComparisonLine],
+    b: &[ComparisonLine],
+    parent: &Path,
+    options: &NormalizeOptions,
+    out: &mut DiffComputation,
+) {
+    let depth = parent.0.len();
+    let a_segments = build_segments(a, depth);
+    let b_segments = build_segments(b, depth);
+
+    match options.policy_for_path(parent) {
+        OrderPolicy::Ordered => {
+            diff_segments_ordered(&a_segments, &b_segments, depth, options, out)
+        }
+        policy => diff_segments_unordered(&a_segments, &b_segments, policy, depth, options, out),
+    }
+}
+
+fn diff_segments_ordered(
+    a_segments: &[Segment<'_>],
+    b_segments: &[Segment<'_>],
+    depth: usize,
+    options: &NormalizeOptions,
+    out: &mut DiffComputation,
+) {
     let a_keys = a_segments
         .iter()
         .map(|segment| segment.segment_key)
@@ -41,109 +78,227 @@ pub(crate) fn diff_views(
         .iter()
         .map(|segment| segment.segment_key)
         .collect::<Vec<_>>();
+    let ops = compute_ops(&a_keys, &b_keys, options.algorithm);
+    let moves = find_moves(a_segments, b_segments, &ops);
+    let move_targets = moves.values().copied().collect::<HashSet<_>>();
 
-    let ops = compute_ops(&a_keys, &b_keys);
-
-    let mut edits = Vec::new();
-    let mut fallback_contexts = Vec::new();
     let mut i = 0usize;
     let mut j = 0usize;
-    let mut pending_deleted_segments: Vec<Segment> = Vec::new();
-    let mut pending_inserted_segments: Vec<Segment> = Vec::new();
-
-    let mut flush_segment_fallback =
-        |edits: &mut Vec<Edit>, deleted: &mut Vec<Segment>, inserted: &mut Vec<Segment>| {
-            if deleted.is_empty() && inserted.is_empty() {
-                return;
-            }
-
-            let deleted_lines = deleted
-                .iter()
-                .flat_map(|segment| segment.lines.clone())
-                .collect::<Vec<_>>();
-            let inserted_lines = inserted
-                .iter()
-                .flat_map(|segment| segment.lines.clone())
-                .collect::<Vec<_>>();
-            deleted.clear();
-            inserted.clear();
-
-            let mut fallback = line_diff(
-                &deleted_lines,
-                &inserted_lines,
-                options.policy_for_path(
-                    &deleted_lines
-                        .first()
-                        .map(|line| line.path.clone())
-                        .or_else(|| inserted_lines.first().map(|line| line.path.clone()))
-                        .unwrap_or(netform_ir::Path(Vec::new())),
-                ),
-            );
-            if let Some(anchor) = deleted_lines
-                .first()
-                .map(|line| line.path.clone())
-                .or_else(|| inserted_lines.first().map(|line| line.path.clone()))
-            {
-                fallback_contexts.push(anchor);
-            }
-            edits.append(&mut fallback);
-        };
+    let mut pending_deletes: Vec<&ComparisonLine> = Vec::new();
+    let mut pending_inserts: Vec<&ComparisonLine> = Vec::new();
 
     for op in ops {
         match op {
             Op::Equal => {
-                flush_segment_fallback(
-                    &mut edits,
-                    &mut pending_deleted_segments,
-                    &mut pending_inserted_segments,
+                flush_fallback(
+                    &mut pending_deletes,
+                    &mut pending_inserts,
+                    OrderPolicy::Ordered,
+                    depth,
+                    options.algorithm,
+                    out,
                 );
-
-                let left = &a_segments[i];
-                let right = &b_segments[j];
-                if left.is_block && right.is_block {
-                    let left_children = if left.lines.len() > 1 {
-                        &left.lines[1..]
-                    } else {
-                        &[]
-                    };
-                    let right_children = if right.lines.len() > 1 {
-                        &right.lines[1..]
-                    } else {
-                        &[]
-                    };
-
-                    let mut child_edits = line_diff(
-                        left_children,
-                        right_children,
-                        options.policy_for_path(&left.lines[0].path),
-                    );
-                    edits.append(&mut child_edits);
+                diff_aligned(&a_segments[i], &b_segments[j], depth, options, out);
+                i += 1;
+                j += 1;
+            }
+            Op::Delete => {
+                match moves.get(&i) {
+                    Some(&target) => out
+                        .edits
+                        .push(move_edit(a_segments[i].lines, b_segments[target].lines)),
+                    None => pending_deletes.extend(a_segments[i].lines),
+                }
+                i += 1;
+            }
+            Op::Insert => {
+                if !move_targets.contains(&j) {
+                    pending_inserts.extend(b_segments[j].lines);
                 }
+                j += 1;
+            }
+        }
+    }
+
+    flush_fallback(
+        &mut pending_deletes,
+        &mut pending_inserts,
+        OrderPolicy::Ordered,
+        depth,
+        options.algorithm,
+        out,
+    );
+}
 
+/// Pair deleted and inserted segments with identical content as moves.
+///
+/// Segments match when every line has the same `content_key`, in order; equal
+/// candidates pair up in document order. Comment and blank segments never move.
+fn find_moves(
+    a_segments: &[Segment<'_>],
+    b_segments: &[Segment<'_>],
+    ops: &[Op],
+) -> HashMap<usize, usize> {
+    let mut deleted = Vec::new();
+    let mut inserted: HashMap<u64, VecDeque<usize>> = HashMap::new();
+    let (mut i, mut j) = (0usize, 0usize);
+    for op in ops {
+        match op {
+            Op::Equal => {
                 i += 1;
                 j += 1;
             }
             Op::Delete => {
-                pending_deleted_segments.push(a_segments[i].clone());
+                deleted.push(i);
                 i += 1;
             }
             Op::Insert => {
-                pending_inserted_segments.push(b_segments[j].clone());
+                if let Some(signature) = move_signature(&b_segments[j]) {
+                    inserted.entry(signature).or_default().push_back(j);
+                }
                 j += 1;
             }
         }
     }
 
-    flush_segment_fallback(
-        &mut edits,
-        &mut pending_deleted_segments,
-        &mut pending_inserted_segments,
+    let mut moves = HashMap::new();
+    for idx in deleted {
+        let target = move_signature(&a_segments[idx])
+            .and_then(|signature| inserted.get_mut(&signature))
+            .and_then(VecDeque::pop_front);
+        if let Some(target) = target {
+            moves.insert(idx, target);
+        }
+    }
+    moves
+}
+
+fn move_signature(segment: &Segment<'_>) -> Option<u64> {
+    if segment.lines.first()?.trivia != TriviaKind::Content {
+        return None;
+    }
+    let keys = segment
+        .lines
+        .iter()
+        .flat_map(|line| line.content_key.to_le_bytes())
+        .collect::<Vec<_>>();
+    Some(xxhash_rust::xxh3::xxh3_64(&keys))
+}
+
+fn diff_segments_unordered(
+    a_segments: &[Segment<'_>],
+    b_segments: &[Segment<'_>],
+    policy: OrderPolicy,
+    depth: usize,
+    options: &NormalizeOptions,
+    out: &mut DiffComputation,
+) {
+    // Segments with the same key pair up in document order on each side.
+    let mut candidates: HashMap<u64, VecDeque<usize>> = HashMap::new();
+    for (idx, segment) in b_segments.iter().enumerate() {
+        candidates
+            .entry(segment.segment_key)
+            .or_default()
+            .push_back(idx);
+    }
+
+    let mut matched = vec![false; b_segments.len()];
+    let mut deletes: Vec<&ComparisonLine> = Vec::new();
+    for left in a_segments {
+        match candidates
+            .get_mut(&left.segment_key)
+            .and_then(VecDeque::pop_front)
+        {
+            Some(idx) => {
+                matched[idx] = true;
+                diff_aligned(left, &b_segments[idx], depth, options, out);
+            }
+            None => deletes.extend(left.lines),
+        }
+    }
+
+    let mut inserts = b_segments
+        .iter()
+        .zip(&matched)
+        .filter(|(_, matched)| !**matched)
+        .flat_map(|(segment, _)| segment.lines)
+        .collect::<Vec<_>>();
+    flush_fallback(
+        &mut deletes,
+        &mut inserts,
+        policy,
+        depth,
+        options.algorithm,
+        out,
     );
+}
 
-    DiffComputation {
-        edits,
-        fallback_contexts,
+/// Diff one aligned pair of segments.
+fn diff_aligned(
+    left: &Segment<'_>,
+    right: &Segment<'_>,
+    depth: usize,
+    options: &NormalizeOptions,
+    out: &mut DiffComputation,
+) {
+    if left.is_list && right.is_list {
+        // Keyed lists are first-match sequences: entry order matters. Entries
+        // are aligned one by one, a level below the list itself.
+        diff_segments_ordered(
+            &list_entries(left),
+            &list_entries(right),
+            depth + 1,
+            options,
+            out,
+        );
+        return;
     }
+
+    // Segments start at their own line or block header; the rest are descendants.
+    diff_children(
+        &left.lines[1..],
+        &right.lines[1..],
+        &left.lines[0].path,
+        options,
+        out,
+    );
+}
+
+fn list_entries<'a>(list: &Segment<'a>) -> Vec<Segment<'a>> {
+    list.lines
+        .iter()
+        .map(|line| Segment {
+            lines: std::slice::from_ref(line),
+            segment_key: line.content_key,
+            is_list: false,
+        })
+        .collect()
+}
+
+/// Line-diff segments that found no partner.
+///
+/// Unaligned root segments are recorded as fallback contexts.
+fn flush_fallback(
+    deletes: &mut Vec<&ComparisonLine>,
+    inserts: &mut Vec<&ComparisonLine>,
+    policy: OrderPolicy,
+    depth: usize,
+    algorithm: DiffAlgorithm,
+    out: &mut DiffComputation,
+) {
+    if deletes.is_empty() && inserts.is_empty() {
+        return;
+    }
+
+    if depth == 0
+        && let Some(anchor) = deletes.first().or(inserts.first())
+    {
+        out.fallback_contexts.push(anchor.path.clone());
+    }
+    out.edits
+        .append(&mut line_diff(deletes, inserts, policy, algorithm));
+    deletes.clear();
+    inserts.clear();
 }
 
 pub(crate) fn build_stats(edits: &[Edit]) -> DiffStats {
@@ -168,100 +323,126 @@ pub(crate) fn build_stats(edits: &[Edit]) -> DiffStats {
                 stats.replaced_old_lines += old_lines.len();
                 stats.replaced_new_lines += new_lines.len();
             }
+            Edit::Move { lines, .. } => {
+                stats.moves += 1;
+                stats.moved_lines += lines.len();
+            }
         }
     }
 
     stats
 }
 
-fn build_segments(view: &ComparisonView) -> Vec<Segment> {
+/// Split lines below a parent at `depth` into one segment per direct child.
+fn build_segments(lines: &[ComparisonLine], depth: usize) -> Vec<Segment<'_>> {
     let mut segments = Vec::new();
-    let mut current_root: Option<usize> = None;
-    let mut current = Vec::new();
-
-    for line in &view.lines {
-        let root = line.path.0.first().copied().unwrap_or(usize::MAX);
-        if current_root != Some(root) {
-            if !current.is_empty() {
-                segments.push(lines_to_segment(std::mem::take(&mut current)));
-            }
-            current_root = Some(root);
-        }
-
-        current.push(line.clone());
-    }
+    let mut start = 0usize;
 
-    if !current.is_empty() {
-        segments.push(lines_to_segment(current));
+    for idx in 1..=lines.len() {
+        let Some(line) = lines.get(idx) else {
+            segments.push(lines_to_segment(&lines[start..]));
+            break;
+        };
+        let previous = &lines[idx - 1];
+        if line.path.0.get(depth) == previous.path.0.get(depth) {
+            continue;
+        }
+        let continues_list = line.list_hint.is_some() && previous.list_hint == line.list_hint;
+        if !continues_list {
+            segments.push(lines_to_segment(&lines[start..idx]));
+            start = idx;
+        }
     }
 
     segments
 }
 
-fn lines_to_segment(lines: Vec<ComparisonLine>) -> Segment {
-    let is_block = lines.iter().any(|line| line.path.0.len() > 1);
-    let segment_key = lines.first().map(|line| line.content_key).unwrap_or(0);
+fn lines_to_segment(lines: &[ComparisonLine]) -> Segment<'_> {
+    let list_hint = lines.first().and_then(|line| line.list_hint.as_deref());
+    // Lists are matched by their shared hint, so entry edits keep them aligned.
+    let segment_key = match list_hint {
+        Some(hint) => derive_content_key(
+            0,
+            KeyKind::Line,
+            TriviaKind::Content,
+            &format!("list:{hint}"),
+        ),
+        None => lines.first().map(|line| line.content_key).unwrap_or(0),
+    };
     Segment {
         lines,
         segment_key,
-        is_block,
+        is_list: list_hint.is_some(),
     }
 }
 
-fn line_diff(a: &[ComparisonLine], b: &[ComparisonLine], policy: OrderPolicy) -> Vec<Edit> {
+fn line_diff(
+    a: &[&ComparisonLine],
+    b: &[&ComparisonLine],
+    policy: OrderPolicy,
+    algorithm: DiffAlgorithm,
+) -> Vec<Edit> {
     match policy {
-        OrderPolicy::Ordered => line_diff_ordered(a, b),
+        OrderPolicy::Ordered => line_diff_ordered(a, b, algorithm),
         OrderPolicy::Unordered => line_diff_unordered(a, b),
         OrderPolicy::KeyedStable => line_diff_keyed_stable(a, b),
     }
 }
 
-fn line_diff_ordered(a: &[ComparisonLine], b: &[ComparisonLine]) -> Vec<Edit> {
+fn line_diff_ordered(
+    a: &[&ComparisonLine],
+    b: &[&ComparisonLine],
+    algorithm: DiffAlgorithm,
+) -> Vec<Edit> {
     let a_tokens = a.iter().map(|line| line.content_key).collect::<Vec<_>>();
     let b_tokens = b.iter().map(|line| line.content_key).collect::<Vec<_>>();
-    let ops = compute_ops(&a_tokens, &b_tokens);
+    let ops = compute_ops(&a_tokens, &b_tokens, algorithm);
 
     let mut edits = Vec::new();
     let mut i = 0usize;
     let mut j = 0usize;
-    let mut pending_deletes: Vec<DiffLine> = Vec::new();
-    let mut pending_inserts: Vec<DiffLine> = Vec::new();
-
-    let flush =
-        |edits: &mut Vec<Edit>, deletes: &mut Vec<DiffLine>, inserts: &mut Vec<DiffLine>| {
-            if deletes.is_empty() && inserts.is_empty() {
-                return;
-            }
-
-            if !deletes.is_empty() && !inserts.is_empty() {
-                edits.push(Edit::Replace {
-                    old_at_key: deletes.first().map(|line| line.occurrence_key),
-                    new_at_key: inserts.first().map(|line| line.occurrence_key),
-                    left_anchor: deletes.first().map(to_anchor),
-                    right_anchor: inserts.first().map(to_anchor),
-                    old_lines: std::mem::take(deletes),
-                    new_lines: std::mem::take(inserts),
-                });
-                return;
-            }
+    let mut pending_deletes: Vec<&ComparisonLine> = Vec::new();
+    let mut pending_inserts: Vec<&ComparisonLine> = Vec::new();
+
+    let flush = |edits: &mut Vec<Edit>,
+                 deletes: &mut Vec<&ComparisonLine>,
+                 inserts: &mut Vec<&ComparisonLine>| {
+        if deletes.is_empty() && inserts.is_empty() {
+            return;
+        }
 
-            if !deletes.is_empty() {
-                edits.push(Edit::Delete {
-                    at_key: deletes.first().map(|line| line.occurrence_key),
-                    left_anchor: deletes.first().map(to_anchor),
-                    right_anchor: None,
-                    lines: std::mem::take(deletes),
-                });
-                return;
-            }
+        if !deletes.is_empty() && !inserts.is_empty() {
+            edits.push(replace_edit(deletes, inserts));
+            deletes.clear();
+            inserts.clear();
+            return;
+        }
 
-            edits.push(Edit::Insert {
-                at_key: inserts.first().map(|line| line.occurrence_key),
-                left_anchor: None,
-                right_anchor: inserts.first().map(to_anchor),
-                lines: std::mem::take(inserts),
+        let deletes = std::mem::take(deletes)
+            .into_iter()
+            .map(to_diff_line)
+            .collect::<Vec<_>>();
+        if !deletes.is_empty() {
+            edits.push(Edit::Delete {
+                at_key: deletes.first().map(|line| line.occurrence_key),
+                left_anchor: deletes.first().map(to_anchor),
+                right_anchor: None,
+                lines: deletes,
             });
-        };
+            return;
+        }
+
+        let inserts = std::mem::take(inserts)
+            .into_iter()
+            .map(to_diff_line)
+            .collect::<Vec<_>>();
+        edits.push(Edit::Insert {
+            at_key: inserts.first().map(|line| line.occurrence_key),
+            left_anchor: None,
+            right_anchor: inserts.first().map(to_anchor),
+            lines: inserts,
+        });
+    };
 
     for op in ops {
         match op {
@@ -271,11 +452,11 @@ fn line_diff_ordered(a: &[ComparisonLine], b: &[ComparisonLine]) -> Vec<Edit> {
                 j += 1;
             }
             Op::Delete => {
-                pending_deletes.push(to_diff_line(&a[i]));
+                pending_deletes.push(a[i]);
                 i += 1;
             }
             Op::Insert => {
-                pending_inserts.push(to_diff_line(&b[j]));
+                pending_inserts.push(b[j]);
                 j += 1;
             }
         }
@@ -285,27 +466,27 @@ fn line_diff_ordered(a: &[ComparisonLine], b: &[ComparisonLine]) -> Vec<Edit> {
     edits
 }
 
-fn line_diff_unordered(a: &[ComparisonLine], b: &[ComparisonLine]) -> Vec<Edit> {
+fn line_diff_unordered(a: &[&ComparisonLine], b: &[&ComparisonLine]) -> Vec<Edit> {
     line_diff_multiset(a, b, |line| {
         xxhash_rust::xxh3::xxh3_64(line.normalized.as_bytes())
     })
 }
 
-fn line_diff_keyed_stable(a: &[ComparisonLine], b: &[ComparisonLine]) -> Vec<Edit> {
+fn line_diff_keyed_stable(a: &[&ComparisonLine], b: &[&ComparisonLine]) -> Vec<Edit> {
     line_diff_multiset(a, b, |line| line.content_key)
 }
 
-fn line_diff_multiset<F>(a: &[ComparisonLine], b: &[ComparisonLine], key_fn: F) -> Vec<Edit>
+fn line_diff_multiset<F>(a: &[&ComparisonLine], b: &[&ComparisonLine], key_fn: F) -> Vec<Edit>
 where
     F: Fn(&ComparisonLine) -> u64,
 {
     let mut a_buckets: HashMap<u64, Vec<&ComparisonLine>> = HashMap::new();
     let mut b_buckets: HashMap<u64, Vec<&ComparisonLine>> = HashMap::new();
 
-    for line in a {
+    for &line in a {
         a_buckets.entry(key_fn(line)).or_default().push(line);
     }
-    for line in b {
+    for &line in b {
         b_buckets.entry(key_fn(line)).or_default().push(line);
     }
 
@@ -327,20 +508,19 @@ where
         right.sort_by_key(|line| (line.occurrence_key, line.path.0.clone()));
 
         if left.len() > right.len() {
-            for line in left.into_iter().skip(right.len()) {
-                deletes.push(to_diff_line(line));
-            }
+            deletes.extend(left.into_iter().skip(right.len()));
         } else if right.len() > left.len() {
-            for line in right.into_iter().skip(left.len()) {
-                inserts.push(to_diff_line(line));
-            }
+            inserts.extend(right.into_iter().skip(left.len()));
         }
     }
 
     finalize_chunked_edits(deletes, inserts)
 }
 
-fn finalize_chunked_edits(mut deletes: Vec<DiffLine>, mut inserts: Vec<DiffLine>) -> Vec<Edit> {
+fn finalize_chunked_edits(
+    mut deletes: Vec<&ComparisonLine>,
+    mut inserts: Vec<&ComparisonLine>,
+) -> Vec<Edit> {
     if deletes.is_empty() && inserts.is_empty() {
         return Vec::new();
     }
@@ -349,16 +529,11 @@ fn finalize_chunked_edits(mut deletes: Vec<DiffLine>, mut inserts: Vec<DiffLine>
     inserts.sort_by_key(|line| (line.content_key, line.occurrence_key, line.path.0.clone()));
 
     if !deletes.is_empty() && !inserts.is_empty() {
-        return vec![Edit::Replace {
-            old_at_key: deletes.first().map(|line| line.occurrence_key),
-            new_at_key: inserts.first().map(|line| line.occurrence_key),
-            left_anchor: deletes.first().map(to_anchor),
-            right_anchor: inserts.first().map(to_anchor),
-            old_lines: deletes,
-            new_lines: inserts,
-        }];
+        return vec![replace_edit(&deletes, &inserts)];
     }
 
+    let deletes = deletes.into_iter().map(to_diff_line).collect::<Vec<_>>();
+    let inserts = inserts.into_iter().map(to_diff_line).collect::<Vec<_>>();
     if !deletes.is_empty() {
         return vec![Edit::Delete {
             at_key: deletes.first().map(|line| line.occurrence_key),
@@ -376,11 +551,58 @@ fn finalize_chunked_edits(mut deletes: Vec<DiffLine>, mut inserts: Vec<DiffLine>
     }]
 }
 
+fn replace_edit(old: &[&ComparisonLine], new: &[&ComparisonLine]) -> Edit {
+    // Secrets missing from the other side read as `<secret:changed>`.
+    let (old_digests, new_digests) = (side_digests(old), side_digests(new));
+    let old_lines = old
+        .iter()
+        .map(|line| diff_line_against(line, Some(&new_digests)))
+        .collect::<Vec<_>>();
+    let new_lines = new
+        .iter()
+        .map(|line| diff_line_against(line, Some(&old_digests)))
+        .collect::<Vec<_>>();
+    Edit::Replace {
+        old_at_key: old_lines.first().map(|line| line.occurrence_key),
+        new_at_key: new_lines.first().map(|line| line.occurrence_key),
+        left_anchor: old_lines.first().map(to_anchor),
+        right_anchor: new_lines.first().map(to_anchor),
+        token_diffs: conceal_token_digests(token_diffs(old, new), &old_digests, &new_digests),
+        old_lines,
+        new_lines,
+    }
+}
+
+fn move_edit(old: &[ComparisonLine], new: &[ComparisonLine]) -> Edit {
+    let lines = old.iter().map(to_diff_line).collect::<Vec<_>>();
+    let destination = new.iter().map(to_diff_line).collect::<Vec<_>>();
+    Edit::Move {
+        old_at_key: lines.first().map(|line| line.occurrence_key),
+        new_at_key: destination.first().map(|line| line.occurrence_key),
+        left_anchor: lines.first().map(to_anchor),
+        right_anchor: destination.first().map(to_anchor),
+        lines,
+    }
+}
+
+fn side_digests<'a>(lines: &[&'a ComparisonLine]) -> HashSet<&'a str> {
+    lines
+        .iter()
+        .flat_map(|line| secret_digests(&line.original))
+        .collect()
+}
+
 fn to_diff_line(line: &ComparisonLine) -> DiffLine {
+    diff_line_against(line, None)
+}
+
+/// Diff line for `line`, with secret digests checked against `other` (the
+/// digests on the opposite side of a replace).
+fn diff_line_against(line: &ComparisonLine, other: Option<&HashSet<&str>>) -> DiffLine {
     DiffLine {
         content_key: line.content_key,
         occurrence_key: line.occurrence_key,
-        text: line.original.clone(),
+        text: conceal_digests(&line.original, other),
         path: line.path.clone(),
         span: line.span.clone(),
     }
@@ -392,102 +614,3 @@ fn to_anchor(line: &DiffLine) -> EditAnchor {
         span: line.span.clone(),
     }
 }
-
-fn compute_ops(a: &[u64], b: &[u64]) -> Vec<Op> {
-    if a.is_empty() {
-        return vec![Op::Insert; b.len()];
-    }
-    if b.is_empty() {
-        return vec![Op::Delete; a.len()];
-    }
-
-    let n = a.len() as isize;
-    let m = b.len() as isize;
-    let max = (a.len() + b.len()) as isize;
-    let offset = max + 1;
-    let v_len = (2 * max + 3) as usize;
-
-    // Myers SES trace over diagonals. This avoids the quadratic LCS matrix and
-    // remains deterministic for a fixed input/order.
-    let mut v = vec![0isize; v_len];
-    let mut trace: Vec<Vec<isize>> = Vec::new();
-
-    for d in 0..=max {
-        let mut current = v.clone();
-        let mut k = -d;
-        while k <= d {
-            let idx = (k + offset) as usize;
-            let take_down = k == -d || (k != d && v[idx - 1] < v[idx + 1]);
-            let mut x = if take_down {
-                v[idx + 1]
-            } else {
-                v[idx - 1] + 1
-            };
-            let mut y = x - k;
-
-            while x < n && y < m && a[x as usize] == b[y as usize] {
-                x += 1;
-                y += 1;
-            }
-            current[idx] = x;
-
-            if x >= n && y >= m {
-                trace.push(current);
-                return backtrack_ops(a, b, &trace, offset);
-            }
-            k += 2;
-        }
-        trace.push(current.clone());
-        v = current;
-    }
-
-    Vec::new()
-}
-
-fn backtrack_ops(a: &[u64], b: &[u64], trace: &[Vec<isize>], offset: isize) -> Vec<Op> {
-    let mut x = a.len() as isize;
-    let mut y = b.len() as isize;
-    let mut rev_ops = Vec::new();
-
-    for d in (1..trace.len()).rev() {
-        let d = d as isize;
-        let k = x - y;
-        let prev = &trace[(d - 1) as usize];
-        let idx = (k + offset) as usize;
-        let go_down = k == -d || (k != d && prev[idx - 1] < prev[idx + 1]);
-        let prev_k = if go_down { k + 1 } else { k - 1 };
-        let prev_x = prev[(prev_k + offset) as usize];
-        let prev_y = prev_x - prev_k;
-
-        while x > prev_x && y > prev_y {
-            rev_ops.push(Op::Equal);
-            x -= 1;
-            y -= 1;
-        }
-
-        if x == prev_x {
-            rev_ops.push(Op::Insert);
-            y -= 1;
-        } else {
-            rev_ops.push(Op::Delete);
-            x -= 1;
-        }
-    }
-
-    while x > 0 && y > 0 && a[(x - 1) as usize] == b[(y - 1) as usize] {
-        rev_ops.push(Op::Equal);
-        x -= 1;
-        y -= 1;
-    }
-    while x > 0 {
-        rev_ops.push(Op::Delete);
-        x -= 1;
-    }
-    while y > 0 {
-        rev_ops.push(Op::Insert);
-        y -= 1;
-    }
-
-    rev_ops.reverse();
-    rev_ops
-}
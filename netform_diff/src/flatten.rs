@@ -1,12 +1,12 @@
 use std::collections::HashMap;
 
-use netform_ir::{Document, Node, NodeId, Path, TriviaKind};
+use netform_ir::{Document, LineNode, Node, NodeId, ParsedLineParts, Path, TriviaKind};
 
 use crate::model::{
     ComparisonLine, ComparisonView, KeyKind, NormalizeOptions, derive_content_key,
     derive_occurrence_key,
 };
-use crate::normalize::normalize_for_compare;
+use crate::normalize::{LineScope, SecretMode, normalize_for_compare, redact_parts, rewrite_raw};
 
 #[derive(Debug, Default)]
 struct KeyAllocator {
@@ -39,7 +39,11 @@ pub fn build_comparison_view(doc: &Document, options: &NormalizeOptions) -> Comp
     let mut keys = KeyAllocator::default();
 
     for (idx, root) in doc.roots.iter().copied().enumerate() {
-        flatten_node(doc, root, 0, vec![idx], &mut out, &mut keys, options);
+        let scope = LineScope {
+            path: vec![idx],
+            enclosing_hints: Vec::new(),
+        };
+        flatten_node(doc, root, 0, scope, &mut out, &mut keys, options);
     }
 
     ComparisonView { lines: out }
@@ -49,7 +53,7 @@ fn flatten_node(
     doc: &Document,
     node_id: NodeId,
     parent_signature: u64,
-    path: Vec<usize>,
+    scope: LineScope,
     out: &mut Vec<ComparisonLine>,
     keys: &mut KeyAllocator,
     options: &NormalizeOptions,
@@ -60,7 +64,7 @@ fn flatten_node(
 
     match node {
         Node::Line(line) => {
-            if let Some(normalized) = normalize_for_compare(&line.raw, line.trivia, options) {
+            if let Some(normalized) = normalize_for_compare(line, &scope, options) {
                 let key_material = key_material_for_line(
                     KeyKind::Line,
                     line.trivia,
@@ -74,22 +78,31 @@ fn flatten_node(
                     key_material.for_hash.as_str(),
                 );
 
+                let list_hint = line
+                    .list_hint
+                    .clone()
+                    .filter(|_| scope.path.len() == 1 && line.trivia == TriviaKind::Content);
                 out.push(ComparisonLine {
                     content_key,
                     occurrence_key,
                     key_hint: key_material.hint,
+                    list_hint,
+                    parts: content_parts(line, options),
                     normalized,
-                    original: line.raw.clone(),
-                    path: Path(path),
+                    original: display_text(line, options),
+                    path: Path(scope.path),
                     span: line.span.clone(),
                     trivia: line.trivia,
                 });
             }
         }
         Node::Block(block) => {
-            if let Some(normalized) =
-                normalize_for_compare(&block.header.raw, block.header.trivia, options)
-            {
+            // A dropped header with descendants stays as an empty anchor so the
+            // block's children and footer are still compared.
+            let has_descendants = !block.children.is_empty() || block.footer.is_some();
+            let normalized = normalize_for_compare(&block.header, &scope, options)
+                .or_else(|| has_descendants.then(String::new));
+            if let Some(normalized) = normalized {
                 let key_material = key_material_for_line(
                     KeyKind::BlockHeader,
                     block.header.trivia,
@@ -107,21 +120,32 @@ fn flatten_node(
                     content_key: header_content_key,
                     occurrence_key: header_occurrence_key,
                     key_hint: key_material.hint,
+                    list_hint: None,
+                    parts: content_parts(&block.header, options),
                     normalized,
-                    original: block.header.raw.clone(),
-                    path: Path(path.clone()),
+                    original: display_text(&block.header, options),
+                    path: Path(scope.path.clone()),
                     span: block.header.span.clone(),
                     trivia: block.header.trivia,
                 });
 
+                let mut enclosing_hints = scope.enclosing_hints;
+                enclosing_hints.extend(block.header.key_hint.clone());
+                let child_scope = |idx: usize| {
+                    let mut path = scope.path.clone();
+                    path.push(idx);
+                    LineScope {
+                        path,
+                        enclosing_hints: enclosing_hints.clone(),
+                    }
+                };
+
                 for (child_idx, child_id) in block.children.iter().copied().enumerate() {
-                    let mut child_path = path.clone();
-                    child_path.push(child_idx);
                     flatten_node(
                         doc,
                         child_id,
                         header_content_key,
-                        child_path,
+                        child_scope(child_idx),
                         out,
                         keys,
                         options,
@@ -129,11 +153,9 @@ fn flatten_node(
                 }
 
                 if let Some(footer) = &block.footer {
-                    let mut footer_path = path;
-                    footer_path.push(block.children.len());
-
+                    let footer_scope = child_scope(block.children.len());
                     if let Some(footer_normalized) =
-                        normalize_for_compare(&footer.raw, footer.trivia, options)
+                        normalize_for_compare(footer, &footer_scope, options)
                     {
                         let key_material = key_material_for_line(
                             KeyKind::BlockFooter,
@@ -152,9 +174,11 @@ fn flatten_node(
                             content_key: footer_content_key,
                             occurrence_key: footer_occurrence_key,
                             key_hint: key_material.hint,
+                            list_hint: None,
+                            parts: content_parts(footer, options),
                             normalized: footer_normalized,
-                            original: footer.raw.clone(),
-                            path: Path(footer_path),
+                            original: display_text(footer, options),
+                            path: Path(footer_scope.path),
                             span: footer.span.clone(),
                             trivia: footer.trivia,
                         });
@@ -165,6 +189,27 @@ fn flatten_node(
     }
 }
 
+fn content_parts(line: &LineNode, options: &NormalizeOptions) -> Option<ParsedLineParts> {
+    let parts = line
+        .parsed
+        .clone()
+        .filter(|_| line.trivia == TriviaKind::Content)?;
+    match SecretMode::from_options(options) {
+        Some(mode) => Some(redact_parts(line, parts, mode)),
+        None => Some(parts),
+    }
+}
+
+/// Text shown for `line` in diffs; secret values read as in comparison text
+/// whenever a secret step is active, and hash-mode digests are concealed when
+/// edits are built.
+fn display_text(line: &LineNode, options: &NormalizeOptions) -> String {
+    match SecretMode::from_options(options) {
+        Some(mode) => rewrite_raw(line, Some(mode), false),
+        None => line.raw.clone(),
+    }
+}
+
 #[derive(Debug)]
 struct KeyMaterial {
     for_hash: String,
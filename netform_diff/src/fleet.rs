@@ -0,0 +1,152 @@
+//! Diffing one reference document against many devices.
+
+use std::collections::HashMap;
+
+use netform_ir::Document;
+
+use crate::flatten::build_comparison_view;
+use crate::model::{
+    ComparisonView, Diff, DiffLine, Edit, FleetDevice, FleetDiff, FleetEdit, FleetEditKind,
+    FleetOutlier, FleetSummary, NormalizeOptions,
+};
+
+/// A reference document flattened once for diffing against many others.
+#[derive(Debug, Clone)]
+pub struct FleetReference<'a> {
+    document: &'a Document,
+    view: ComparisonView,
+    options: NormalizeOptions,
+}
+
+impl<'a> FleetReference<'a> {
+    /// Build the reference comparison view with `options`.
+    pub fn new(document: &'a Document, options: NormalizeOptions) -> Self {
+        let view = build_comparison_view(document, &options);
+        Self {
+            document,
+            view,
+            options,
+        }
+    }
+
+    /// Diff the reference (left) against `device` (right).
+    ///
+    /// Equivalent to `diff_documents(reference, device, options)` without
+    /// re-flattening the reference.
+    pub fn diff(&self, device: &Document) -> Diff {
+        crate::diff_against_view(self.document, &self.view, device, self.options.clone())
+    }
+}
+
+/// Diff `reference` against each named device and aggregate the results.
+pub fn diff_fleet<'d, I>(reference: &Document, devices: I, options: NormalizeOptions) -> FleetDiff
+where
+    I: IntoIterator<Item = (String, &'d Document)>,
+{
+    let reference = FleetReference::new(reference, options);
+    let devices = devices
+        .into_iter()
+        .map(|(name, document)| FleetDevice {
+            name,
+            diff: reference.diff(document),
+        })
+        .collect::<Vec<_>>();
+    let summary = summarize_fleet(&devices);
+    FleetDiff { devices, summary }
+}
+
+/// Group the edits of `devices` by identity and flag outlier devices.
+///
+/// Edits are ordered by how many devices share them, then by first
+/// appearance. A device is an outlier when the fleet has more than one device
+/// and some of its edits appear on no other device. Devices are told apart by
+/// position, so names should be unique for the report to be readable.
+pub fn summarize_fleet(devices: &[FleetDevice]) -> FleetSummary {
+    let mut edits: Vec<FleetEdit> = Vec::new();
+    // Indices into `devices` of each edit's devices, parallel to `edits`.
+    let mut members: Vec<Vec<usize>> = Vec::new();
+    let mut by_signature: HashMap<u64, usize> = HashMap::new();
+
+    for (device_idx, device) in devices.iter().enumerate() {
+        for edit in &device.diff.edits {
+            let (kind, old, new) = edit_identity(edit);
+            let signature = edit_signature(kind, old, new);
+            let idx = *by_signature.entry(signature).or_insert_with(|| {
+                edits.push(FleetEdit {
+                    signature,
+                    kind,
+                    old_lines: old.iter().map(|line| line.text.clone()).collect(),
+                    new_lines: new.iter().map(|line| line.text.clone()).collect(),
+                    devices: Vec::new(),
+                });
+                members.push(Vec::new());
+                edits.len() - 1
+            });
+            if members[idx].last() != Some(&device_idx) {
+                members[idx].push(device_idx);
+                edits[idx].devices.push(device.name.clone());
+            }
+        }
+    }
+
+    let mut outliers = Vec::new();
+    if devices.len() > 1 {
+        for (device_idx, device) in devices.iter().enumerate() {
+            let mut unique = 0usize;
+            let mut total = 0usize;
+            for seen in &members {
+                if seen.contains(&device_idx) {
+                    total += 1;
+                    if seen.len() == 1 {
+                        unique += 1;
+                    }
+                }
+            }
+            if unique > 0 {
+                outliers.push(FleetOutlier {
+                    device: device.name.clone(),
+                    unique_edits: unique,
+                    total_edits: total,
+                });
+            }
+        }
+        outliers.sort_by_key(|outlier| std::cmp::Reverse(outlier.unique_edits));
+    }
+
+    edits.sort_by_key(|edit| std::cmp::Reverse(edit.devices.len()));
+
+    FleetSummary {
+        device_count: devices.len(),
+        changed_devices: devices
+            .iter()
+            .filter(|device| device.diff.has_changes)
+            .count(),
+        edits,
+        outliers,
+    }
+}
+
+/// Kind plus old/new lines of an edit; a move's lines count as old lines.
+fn edit_identity(edit: &Edit) -> (FleetEditKind, &[DiffLine], &[DiffLine]) {
+    match edit {
+        Edit::Insert { lines, .. } => (FleetEditKind::Insert, &[], lines),
+        Edit::Delete { lines, .. } => (FleetEditKind::Delete, lines, &[]),
+        Edit::Replace {
+            old_lines,
+            new_lines,
+            ..
+        } => (FleetEditKind::Replace, old_lines, new_lines),
+        Edit::Move { lines, .. } => (FleetEditKind::Move, lines, &[]),
+    }
+}
+
+fn edit_signature(kind: FleetEditKind, old: &[DiffLine], new: &[DiffLine]) -> u64 {
+    let mut material = vec![kind as u8];
+    for lines in [old, new] {
+        material.extend((lines.len() as u64).to_le_bytes());
+        for line in lines {
+            material.extend(line.content_key.to_le_bytes());
+        }
+    }
+    xxhash_rust::xxh3::xxh3_64(&material)
+}
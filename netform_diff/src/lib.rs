@@ -7,6 +7,8 @@
 //! - [`diff_documents`]
 //! - [`format_markdown_report`]
 //! - [`build_plan`]
+//! - [`merge3`]
+//! - [`diff_fleet`]
 //!
 //! # Example
 //!
@@ -20,41 +22,60 @@
 //! assert!(diff.has_changes);
 //! ```
 
+mod align;
 mod engine;
 mod findings;
 mod flatten;
+mod fleet;
+mod merge;
 mod model;
 mod normalize;
 mod plan;
 mod report;
+mod tokens;
 mod util;
 
 pub use flatten::build_comparison_view;
+pub use fleet::{FleetReference, diff_fleet, summarize_fleet};
+pub use merge::merge3;
 pub use model::{
-    ComparisonLine, ComparisonView, Diff, DiffLine, DiffStats, Edit, EditAnchor, Finding,
-    FindingLevel, KeyKind, NormalizationStep, NormalizeOptions, OrderPolicy, OrderPolicyConfig,
-    OrderPolicyOverride, Plan, PlanAction, PlanFinding, PlanLineEdit, PlanLineEditKind,
-    derive_content_key, derive_occurrence_key,
+    ComparisonLine, ComparisonView, Diff, DiffAlgorithm, DiffLine, DiffStats, Edit, EditAnchor,
+    Finding, FindingLevel, FleetDevice, FleetDiff, FleetEdit, FleetEditKind, FleetOutlier,
+    FleetSummary, InvalidPatternError, KeyKind, LinePattern, LineTokenDiff, MergeConflict,
+    MergeResult, NormalizationStep, NormalizeOptions, OrderPolicy, OrderPolicyConfig,
+    OrderPolicyOverride, Plan, PlanAction, PlanFinding, PlanLineEdit, PlanLineEditKind, StepScope,
+    TokenChange, derive_content_key, derive_occurrence_key,
 };
 pub use plan::build_plan;
-pub use report::format_markdown_report;
+pub use report::{format_fleet_report, format_markdown_report};
 
 use netform_ir::Document;
 
 /// Compute a deterministic diff between two parsed documents.
 pub fn diff_documents(a: &Document, b: &Document, options: NormalizeOptions) -> Diff {
     let a_view = build_comparison_view(a, &options);
+    diff_against_view(a, &a_view, b, options)
+}
+
+/// Diff `a`, already flattened into `a_view` with `options`, against `b`.
+fn diff_against_view(
+    a: &Document,
+    a_view: &ComparisonView,
+    b: &Document,
+    options: NormalizeOptions,
+) -> Diff {
     let b_view = build_comparison_view(b, &options);
-    let ctx = findings::DiffContext::from_views(&a_view, &b_view);
-    let computation = engine::diff_views(&a_view, &b_view, &options);
+    let ctx = findings::DiffContext::from_views(a_view, &b_view);
+    let computation = engine::diff_views(a_view, &b_view, &options);
     let stats = engine::build_stats(&computation.edits);
     let findings =
-        findings::collect_findings(a, b, &a_view, &b_view, &ctx, &computation.fallback_contexts);
+        findings::collect_findings(a, b, a_view, &b_view, &ctx, &computation.fallback_contexts);
     let has_changes = !computation.edits.is_empty();
 
     Diff {
         normalization_steps: options.steps,
         order_policy: options.order_policy,
+        algorithm: options.algorithm,
         has_changes,
         edits: computation.edits,
         stats,
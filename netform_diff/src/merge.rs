@@ -0,0 +1,498 @@
+//! Three-way merge of two documents derived from a common base.
+
+use std::collections::HashMap;
+
+use netform_ir::{BlockNode, Document, LineNode, Node, NodeId, Path};
+
+use crate::align::{Op, compute_ops};
+use crate::flatten::build_comparison_view;
+use crate::model::{DiffAlgorithm, EditAnchor, MergeConflict, MergeResult, NormalizeOptions};
+
+const OURS_MARKER: &str = "<<<<<<< ours";
+const BASE_MARKER: &str = "||||||| base";
+const SPLIT_MARKER: &str = "=======";
+const THEIRS_MARKER: &str = ">>>>>>> theirs";
+
+/// Merge `ours` and `theirs`, which both derive from `base`.
+///
+/// Children are aligned per parent by their comparison keys, so stanzas with a
+/// dialect key hint match even when their header text differs, and blocks kept
+/// on all three sides merge recursively. A region changed on one side takes
+/// that side's version; a region changed differently on both sides keeps
+/// `ours` in the merged document and is recorded as a [`MergeConflict`]. A
+/// kept node whose own header or footer text changed differently on both
+/// sides conflicts as a whole subtree.
+/// Lines dropped by normalization merge by their exact text.
+pub fn merge3(
+    base: &Document,
+    ours: &Document,
+    theirs: &Document,
+    options: NormalizeOptions,
+) -> MergeResult {
+    let sides = [
+        Side::new(base, &options),
+        Side::new(ours, &options),
+        Side::new(theirs, &options),
+    ];
+
+    let mut metadata = ours.metadata.clone();
+    metadata.parse_findings.clear();
+    let mut merger = Merger {
+        sides: &sides,
+        algorithm: options.algorithm,
+        merged: Document::new(metadata),
+        conflicts: Vec::new(),
+    };
+    merger.merge_children(
+        [&base.roots, &ours.roots, &theirs.roots],
+        None,
+        &Path(Vec::new()),
+    );
+
+    let Merger {
+        mut merged,
+        conflicts,
+        ..
+    } = merger;
+    merged.recompute_spans();
+    MergeResult { merged, conflicts }
+}
+
+impl MergeResult {
+    /// Whether any region was changed differently on both sides.
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+
+    /// Render the merged document with git-style conflict markers.
+    ///
+    /// Each conflict shows the `ours` lines kept in the merged document, then
+    /// the `base` and `theirs` versions of the same region.
+    pub fn render_with_markers(&self) -> String {
+        let mut out = String::new();
+        self.render_siblings(&self.merged.roots, &mut Vec::new(), &mut out);
+        out
+    }
+
+    fn render_siblings(&self, ids: &[NodeId], path: &mut Vec<usize>, out: &mut String) {
+        let mut conflicts = self
+            .conflicts
+            .iter()
+            .filter(|conflict| conflict.context_path.0 == *path)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .peekable();
+
+        let mut idx = 0usize;
+        loop {
+            if let Some(conflict) = conflicts.next_if(|conflict| conflict.merged_index == idx) {
+                push_marker(out, OURS_MARKER);
+                for offset in 0..conflict.merged_len {
+                    path.push(idx + offset);
+                    self.render_node(ids[idx + offset], path, out);
+                    path.pop();
+                }
+                idx += conflict.merged_len;
+                push_marker(out, BASE_MARKER);
+                push_lines(out, &conflict.base_lines);
+                push_marker(out, SPLIT_MARKER);
+                push_lines(out, &conflict.theirs_lines);
+                push_marker(out, THEIRS_MARKER);
+                continue;
+            }
+
+            let Some(id) = ids.get(idx) else {
+                break;
+            };
+            path.push(idx);
+            self.render_node(*id, path, out);
+            path.pop();
+            idx += 1;
+        }
+    }
+
+    fn render_node(&self, id: NodeId, path: &mut Vec<usize>, out: &mut String) {
+        match self.merged.node(id) {
+            Some(Node::Line(line)) => push_line(out, line),
+            Some(Node::Block(block)) => {
+                push_line(out, &block.header);
+                self.render_siblings(&block.children, path, out);
+                if let Some(footer) = &block.footer {
+                    push_line(out, footer);
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+/// One merge input with comparison keys per node.
+struct Side<'a> {
+    doc: &'a Document,
+    /// Comparison key of each node's own line (the header for blocks).
+    keys: HashMap<NodeId, u64>,
+    /// Key of each node's whole subtree.
+    signatures: HashMap<NodeId, u64>,
+    paths: HashMap<NodeId, Path>,
+}
+
+impl<'a> Side<'a> {
+    fn new(doc: &'a Document, options: &NormalizeOptions) -> Self {
+        let view_keys = build_comparison_view(doc, options)
+            .lines
+            .into_iter()
+            .map(|line| (line.path.0, line.content_key))
+            .collect::<HashMap<_, _>>();
+
+        let mut side = Self {
+            doc,
+            keys: HashMap::new(),
+            signatures: HashMap::new(),
+            paths: HashMap::new(),
+        };
+        for (idx, root) in doc.roots.iter().copied().enumerate() {
+            side.index(root, vec![idx], &view_keys);
+        }
+        side
+    }
+
+    fn index(&mut self, id: NodeId, path: Vec<usize>, view_keys: &HashMap<Vec<usize>, u64>) -> u64 {
+        let Some(node) = self.doc.node(id) else {
+            return 0;
+        };
+        let key_at = |path: &Vec<usize>, line: &LineNode| {
+            view_keys
+                .get(path)
+                .copied()
+                .unwrap_or_else(|| raw_key(line))
+        };
+
+        let key = match node {
+            Node::Line(line) => key_at(&path, line),
+            Node::Block(block) => key_at(&path, &block.header),
+        };
+        let mut material = key.to_le_bytes().to_vec();
+        if let Node::Block(block) = node {
+            for (idx, child) in block.children.iter().copied().enumerate() {
+                let mut child_path = path.clone();
+                child_path.push(idx);
+                material.extend(self.index(child, child_path, view_keys).to_le_bytes());
+            }
+            if let Some(footer) = &block.footer {
+                let mut footer_path = path.clone();
+                footer_path.push(block.children.len());
+                material.extend(key_at(&footer_path, footer).to_le_bytes());
+            }
+        }
+
+        let signature = xxhash_rust::xxh3::xxh3_64(&material);
+        self.keys.insert(id, key);
+        self.signatures.insert(id, signature);
+        self.paths.insert(id, Path(path));
+        signature
+    }
+
+    fn anchor(&self, id: NodeId) -> Option<EditAnchor> {
+        let span = match self.doc.node(id)? {
+            Node::Line(line) => line.span.clone(),
+            Node::Block(block) => block.header.span.clone(),
+        };
+        Some(EditAnchor {
+            path: self.paths.get(&id)?.clone(),
+            span,
+        })
+    }
+
+    fn lines(&self, ids: &[NodeId]) -> Vec<String> {
+        let mut out = Vec::new();
+        for id in ids {
+            collect_lines(self.doc, *id, &mut out);
+        }
+        out
+    }
+}
+
+struct Merger<'s, 'a> {
+    /// `base`, `ours`, `theirs`, in that order.
+    sides: &'s [Side<'a>; 3],
+    algorithm: DiffAlgorithm,
+    merged: Document,
+    conflicts: Vec<MergeConflict>,
+}
+
+const BASE: usize = 0;
+const OURS: usize = 1;
+const THEIRS: usize = 2;
+
+impl Merger<'_, '_> {
+    /// Merge the children of one context, given as `[base, ours, theirs]` ids.
+    fn merge_children(&mut self, ids: [&[NodeId]; 3], parent: Option<NodeId>, context: &Path) {
+        let keys = |side: usize| {
+            ids[side]
+                .iter()
+                .map(|id| self.sides[side].keys[id])
+                .collect::<Vec<_>>()
+        };
+        let base_keys = keys(BASE);
+        let ours_match = matched_positions(&base_keys, &keys(OURS), self.algorithm);
+        let theirs_match = matched_positions(&base_keys, &keys(THEIRS), self.algorithm);
+
+        // Base children kept on both sides split the context into stable
+        // anchors and the changed chunks between them.
+        let (mut b, mut o, mut t) = (0usize, 0usize, 0usize);
+        loop {
+            let stable =
+                (b..base_keys.len()).find_map(|k| Some((k, ours_match[k]?, theirs_match[k]?)));
+            let (kb, ko, kt) =
+                stable.unwrap_or((ids[BASE].len(), ids[OURS].len(), ids[THEIRS].len()));
+            self.merge_chunk(
+                [&ids[BASE][b..kb], &ids[OURS][o..ko], &ids[THEIRS][t..kt]],
+                parent,
+                context,
+            );
+            if stable.is_none() {
+                break;
+            }
+            self.merge_stable(
+                [ids[BASE][kb], ids[OURS][ko], ids[THEIRS][kt]],
+                parent,
+                context,
+            );
+            (b, o, t) = (kb + 1, ko + 1, kt + 1);
+        }
+    }
+
+    fn merge_chunk(&mut self, ids: [&[NodeId]; 3], parent: Option<NodeId>, context: &Path) {
+        if ids.iter().all(|chunk| chunk.is_empty()) {
+            return;
+        }
+
+        let signatures = |side: usize| {
+            ids[side]
+                .iter()
+                .map(|id| self.sides[side].signatures[id])
+                .collect::<Vec<_>>()
+        };
+        let (base, ours, theirs) = (signatures(BASE), signatures(OURS), signatures(THEIRS));
+        let winner = if ours == base {
+            Some(THEIRS)
+        } else if theirs == base || ours == theirs {
+            Some(OURS)
+        } else {
+            None
+        };
+
+        if let Some(side) = winner {
+            for id in ids[side] {
+                self.copy_subtree(side, *id, parent);
+            }
+            return;
+        }
+
+        self.record_conflict(ids, parent, context);
+    }
+
+    /// Keep the `ours` chunk and record the region as a conflict.
+    fn record_conflict(&mut self, ids: [&[NodeId]; 3], parent: Option<NodeId>, context: &Path) {
+        let merged_index = self.sibling_count(parent);
+        for id in ids[OURS] {
+            self.copy_subtree(OURS, *id, parent);
+        }
+        let anchor = |side: usize| {
+            ids[side]
+                .first()
+                .and_then(|id| self.sides[side].anchor(*id))
+        };
+        self.conflicts.push(MergeConflict {
+            context_path: context.clone(),
+            merged_index,
+            merged_len: ids[OURS].len(),
+            base_anchor: anchor(BASE),
+            ours_anchor: anchor(OURS),
+            theirs_anchor: anchor(THEIRS),
+            base_lines: self.sides[BASE].lines(ids[BASE]),
+            ours_lines: self.sides[OURS].lines(ids[OURS]),
+            theirs_lines: self.sides[THEIRS].lines(ids[THEIRS]),
+        });
+    }
+
+    /// Merge one node kept on all three sides.
+    fn merge_stable(&mut self, ids: [NodeId; 3], parent: Option<NodeId>, context: &Path) {
+        let [Some(base), Some(ours), Some(theirs)] =
+            [0, 1, 2].map(|side| self.sides[side].doc.node(ids[side]))
+        else {
+            return;
+        };
+
+        let nodes = [base, ours, theirs];
+        let blocks = nodes.map(|node| match node {
+            Node::Block(block) => Some(block),
+            Node::Line(_) => None,
+        });
+        let footers = blocks.map(|block| block.and_then(|block| block.footer.as_ref()));
+        let own_text = |side: usize| {
+            (
+                &header_line(nodes[side]).raw,
+                footers[side].map(|footer| &footer.raw),
+            )
+        };
+        if own_text(OURS) != own_text(BASE)
+            && own_text(THEIRS) != own_text(BASE)
+            && own_text(OURS) != own_text(THEIRS)
+        {
+            self.record_conflict(ids.each_ref().map(std::slice::from_ref), parent, context);
+            return;
+        }
+
+        let line = pick(header_line(base), header_line(ours), header_line(theirs));
+        if blocks.iter().all(Option::is_none) {
+            self.attach(parent, Node::Line(terminated(line)));
+            return;
+        }
+
+        let footer = if footers[OURS].map(|f| &f.raw) == footers[BASE].map(|f| &f.raw) {
+            footers[THEIRS]
+        } else {
+            footers[OURS]
+        };
+        let kind_label = [OURS, THEIRS, BASE]
+            .iter()
+            .find_map(|side| blocks[*side].and_then(|block| block.kind_label.clone()));
+        let (id, index) = self.attach(
+            parent,
+            Node::Block(BlockNode {
+                header: terminated(line),
+                children: Vec::new(),
+                footer: footer.map(terminated),
+                kind_label,
+            }),
+        );
+
+        let children = blocks.map(|block| block.map_or(&[][..], |block| &block.children[..]));
+        let mut child_context = context.clone();
+        child_context.0.push(index);
+        self.merge_children(children, Some(id), &child_context);
+    }
+
+    fn copy_subtree(&mut self, side: usize, id: NodeId, parent: Option<NodeId>) {
+        let doc = self.sides[side].doc;
+        match doc.node(id) {
+            Some(Node::Line(line)) => {
+                self.attach(parent, Node::Line(terminated(line)));
+            }
+            Some(Node::Block(block)) => {
+                let (copy, _) = self.attach(
+                    parent,
+                    Node::Block(BlockNode {
+                        header: terminated(&block.header),
+                        children: Vec::new(),
+                        footer: block.footer.as_ref().map(terminated),
+                        kind_label: block.kind_label.clone(),
+                    }),
+                );
+                for child in &block.children {
+                    self.copy_subtree(side, *child, Some(copy));
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Append `node` under `parent` (roots when `None`); returns its id and index.
+    fn attach(&mut self, parent: Option<NodeId>, node: Node) -> (NodeId, usize) {
+        let index = self.sibling_count(parent);
+        let id = match parent {
+            Some(parent) => {
+                let id = self.merged.insert_node(node);
+                self.merged.add_child(parent, id);
+                id
+            }
+            None => self.merged.insert_root(node),
+        };
+        (id, index)
+    }
+
+    fn sibling_count(&self, parent: Option<NodeId>) -> usize {
+        match parent.and_then(|parent| self.merged.node(parent)) {
+            Some(Node::Block(block)) => block.children.len(),
+            _ => self.merged.roots.len(),
+        }
+    }
+}
+
+/// For each left position, the right position it is aligned with.
+fn matched_positions(a: &[u64], b: &[u64], algorithm: DiffAlgorithm) -> Vec<Option<usize>> {
+    let mut out = vec![None; a.len()];
+    let (mut i, mut j) = (0usize, 0usize);
+    for op in compute_ops(a, b, algorithm) {
+        match op {
+            Op::Equal => {
+                out[i] = Some(j);
+                i += 1;
+                j += 1;
+            }
+            Op::Delete => i += 1,
+            Op::Insert => j += 1,
+        }
+    }
+    out
+}
+
+fn header_line(node: &Node) -> &LineNode {
+    match node {
+        Node::Line(line) => line,
+        Node::Block(block) => &block.header,
+    }
+}
+
+/// `theirs` when `ours` kept the base text, `ours` otherwise.
+fn pick<'n>(base: &'n LineNode, ours: &'n LineNode, theirs: &'n LineNode) -> &'n LineNode {
+    if ours.raw == base.raw { theirs } else { ours }
+}
+
+/// Clone `line`, giving a final unterminated line a newline so it can be
+/// followed by lines from another document.
+fn terminated(line: &LineNode) -> LineNode {
+    let mut line = line.clone();
+    if line.line_ending.is_empty() {
+        line.line_ending = "\n".to_string();
+    }
+    line
+}
+
+fn raw_key(line: &LineNode) -> u64 {
+    xxhash_rust::xxh3::xxh3_64(format!("raw:{}", line.raw).as_bytes())
+}
+
+fn collect_lines(doc: &Document, id: NodeId, out: &mut Vec<String>) {
+    match doc.node(id) {
+        Some(Node::Line(line)) => out.push(line.raw.clone()),
+        Some(Node::Block(block)) => {
+            out.push(block.header.raw.clone());
+            for child in &block.children {
+                collect_lines(doc, *child, out);
+            }
+            if let Some(footer) = &block.footer {
+                out.push(footer.raw.clone());
+            }
+        }
+        None => {}
+    }
+}
+
+fn push_line(out: &mut String, line: &LineNode) {
+    out.push_str(&line.raw);
+    out.push_str(&line.line_ending);
+}
+
+fn push_lines(out: &mut String, lines: &[String]) {
+    for line in lines {
+        out.push_str(line);
+        out.push('\n');
+    }
+}
+
+fn push_marker(out: &mut String, marker: &str) {
+    out.push_str(marker);
+    out.push('\n');
+}
@@ -1,9 +1,12 @@
-use serde::{Deserialize, Serialize};
+use std::fmt;
 
-use netform_ir::{Path, Span, TriviaKind};
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use netform_ir::{Document, ParsedLineParts, Path, Span, TriviaKind};
 
 /// One ordered normalization step in the comparison pipeline.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum NormalizationStep {
     IgnoreComments,
@@ -11,8 +14,105 @@ pub enum NormalizationStep {
     TrimTrailingWhitespace,
     NormalizeLeadingWhitespace,
     CollapseInternalWhitespace,
+    /// Drop lines in `scope` whose text matches `pattern`.
+    IgnoreMatching {
+        pattern: LinePattern,
+        #[serde(default)]
+        scope: StepScope,
+    },
+    /// Replace every match of `pattern` in lines in `scope`; `$1` and
+    /// `${name}` in `replacement` expand capture groups.
+    Rewrite {
+        pattern: LinePattern,
+        replacement: String,
+        #[serde(default)]
+        scope: StepScope,
+    },
+    /// Compare dialect-reported secret values only by presence: every value
+    /// reads as `<secret>`.
+    MaskSecrets,
+    /// Compare dialect-reported secret values by an xxh3 digest, so a changed
+    /// value still shows up as an edit.
+    HashSecrets,
+    /// Compare dialect-reported abbreviated interface names (`Gi0/1`) in their
+    /// canonical spelling (`GigabitEthernet0/1`).
+    CanonicalizeInterfaces,
 }
 
+/// Lines a pattern-based [`NormalizationStep`] applies to.
+///
+/// A line is in scope when its path starts with `path_prefix` and, if
+/// `key_hint` is set, the line or one of its enclosing block headers carries
+/// that key hint. The default scope covers every line.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StepScope {
+    #[serde(default)]
+    pub path_prefix: Vec<usize>,
+    #[serde(default)]
+    pub key_hint: Option<String>,
+}
+
+/// Regular expression used by a [`NormalizationStep`], serialized as its source.
+#[derive(Debug, Clone)]
+pub struct LinePattern(Regex);
+
+impl LinePattern {
+    /// Compile `pattern` with the `regex` crate syntax.
+    pub fn new(pattern: &str) -> Result<Self, InvalidPatternError> {
+        Regex::new(pattern)
+            .map(Self)
+            .map_err(|err| InvalidPatternError {
+                pattern: pattern.to_string(),
+                message: err.to_string(),
+            })
+    }
+
+    /// Source text of the pattern.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    pub(crate) fn regex(&self) -> &Regex {
+        &self.0
+    }
+}
+
+impl PartialEq for LinePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for LinePattern {}
+
+impl Serialize for LinePattern {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for LinePattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Self::new(&pattern).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Error returned by [`LinePattern::new`] for a pattern that does not compile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidPatternError {
+    pub pattern: String,
+    pub message: String,
+}
+
+impl fmt::Display for InvalidPatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid pattern `{}`: {}", self.pattern, self.message)
+    }
+}
+
+impl std::error::Error for InvalidPatternError {}
+
 /// Ordering behavior used when comparing sibling lines in a context.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -60,11 +160,25 @@ impl OrderPolicyConfig {
     }
 }
 
+/// Sequence alignment used wherever children are compared in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DiffAlgorithm {
+    /// Shortest edit script.
+    #[default]
+    Myers,
+    /// Anchor on lines unique to both sides, then recurse between anchors.
+    Patience,
+    /// Split on the rarest common lines first.
+    Histogram,
+}
+
 /// Options controlling normalization and ordering semantics for diffing.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct NormalizeOptions {
     pub steps: Vec<NormalizationStep>,
     pub order_policy: OrderPolicyConfig,
+    pub algorithm: DiffAlgorithm,
 }
 
 impl NormalizeOptions {
@@ -73,6 +187,7 @@ impl NormalizeOptions {
         Self {
             steps,
             order_policy: OrderPolicyConfig::default(),
+            algorithm: DiffAlgorithm::default(),
         }
     }
 
@@ -82,6 +197,12 @@ impl NormalizeOptions {
         self
     }
 
+    /// Select the alignment algorithm for ordered comparisons.
+    pub fn with_algorithm(mut self, algorithm: DiffAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
     pub(crate) fn policy_for_path(&self, path: &Path) -> OrderPolicy {
         self.order_policy.policy_for_path(path)
     }
@@ -93,6 +214,11 @@ pub struct ComparisonLine {
     pub content_key: u64,
     pub occurrence_key: u64,
     pub key_hint: Option<String>,
+    /// Dialect list hint of a root line; consecutive root lines sharing it form
+    /// one keyed list (for example ASA `access-list NAME ...` entries).
+    pub list_hint: Option<String>,
+    /// Dialect tokens of the line, used for token-level diffs of replaced lines.
+    pub parts: Option<ParsedLineParts>,
     pub normalized: String,
     pub original: String,
     pub path: Path,
@@ -146,9 +272,41 @@ pub enum Edit {
         right_anchor: Option<EditAnchor>,
         old_lines: Vec<DiffLine>,
         new_lines: Vec<DiffLine>,
+        token_diffs: Vec<LineTokenDiff>,
+    },
+    /// Identical content that changed position: `left_anchor` is the source,
+    /// `right_anchor` the destination, and `lines` are the moved lines as found
+    /// at the source.
+    Move {
+        old_at_key: Option<u64>,
+        new_at_key: Option<u64>,
+        left_anchor: Option<EditAnchor>,
+        right_anchor: Option<EditAnchor>,
+        lines: Vec<DiffLine>,
     },
 }
 
+/// Token-level difference between one paired old/new line of a replace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LineTokenDiff {
+    /// Index into the replace's `old_lines`.
+    pub old_index: usize,
+    /// Index into the replace's `new_lines`.
+    pub new_index: usize,
+    /// Aligned `head` + `args` tokens, in line order.
+    pub tokens: Vec<TokenChange>,
+}
+
+/// One aligned token in a [`LineTokenDiff`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum TokenChange {
+    Equal { token: String },
+    Changed { old: String, new: String },
+    Added { token: String },
+    Removed { token: String },
+}
+
 /// Aggregate counters for diff output.
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
 pub struct DiffStats {
@@ -159,6 +317,8 @@ pub struct DiffStats {
     pub deleted_lines: usize,
     pub replaced_old_lines: usize,
     pub replaced_new_lines: usize,
+    pub moves: usize,
+    pub moved_lines: usize,
 }
 
 /// Warning/info emitted during parse propagation or diff uncertainty handling.
@@ -184,6 +344,7 @@ pub enum FindingLevel {
 pub struct Diff {
     pub normalization_steps: Vec<NormalizationStep>,
     pub order_policy: OrderPolicyConfig,
+    pub algorithm: DiffAlgorithm,
     pub has_changes: bool,
     pub edits: Vec<Edit>,
     pub stats: DiffStats,
@@ -211,6 +372,13 @@ pub enum PlanAction {
         context_path: Path,
         line_edits: Vec<PlanLineEdit>,
     },
+    MoveLines {
+        source_path: Path,
+        source_span: Span,
+        target_path: Path,
+        target_span: Span,
+        lines: Vec<String>,
+    },
 }
 
 /// One line-oriented edit in `apply_line_edits_under_context`.
@@ -236,6 +404,86 @@ pub struct PlanFinding {
     pub message: String,
 }
 
+/// One device's diff against the fleet reference.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FleetDevice {
+    pub name: String,
+    pub diff: Diff,
+}
+
+/// Output of [`crate::diff_fleet`]: per-device diffs plus their aggregate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FleetDiff {
+    pub devices: Vec<FleetDevice>,
+    pub summary: FleetSummary,
+}
+
+/// Aggregate view of many diffs against one reference.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct FleetSummary {
+    pub device_count: usize,
+    pub changed_devices: usize,
+    /// Distinct edits, most widespread first.
+    pub edits: Vec<FleetEdit>,
+    /// Devices with edits no other device shares, most unique edits first.
+    pub outliers: Vec<FleetOutlier>,
+}
+
+/// One distinct edit and the devices it appears on.
+///
+/// Edits are the same when they have the same kind and the same line content
+/// keys, so one change under the same stanza matches across devices even when
+/// its position differs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FleetEdit {
+    pub signature: u64,
+    pub kind: FleetEditKind,
+    pub old_lines: Vec<String>,
+    pub new_lines: Vec<String>,
+    pub devices: Vec<String>,
+}
+
+/// [`Edit`] variant of a [`FleetEdit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum FleetEditKind {
+    Insert,
+    Delete,
+    Replace,
+    Move,
+}
+
+/// A device whose drift is not shared with the rest of the fleet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FleetOutlier {
+    pub device: String,
+    pub unique_edits: usize,
+    pub total_edits: usize,
+}
+
+/// Output of [`crate::merge3`]: the merged document plus unresolved regions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeResult {
+    pub merged: Document,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// A region changed differently on both sides of a three-way merge.
+///
+/// The merged document holds the `ours` version at `merged_index` among the
+/// children of `context_path`; anchors are `None` for a side with no lines.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MergeConflict {
+    pub context_path: Path,
+    pub merged_index: usize,
+    pub merged_len: usize,
+    pub base_anchor: Option<EditAnchor>,
+    pub ours_anchor: Option<EditAnchor>,
+    pub theirs_anchor: Option<EditAnchor>,
+    pub base_lines: Vec<String>,
+    pub ours_lines: Vec<String>,
+    pub theirs_lines: Vec<String>,
+}
+
 /// Key namespace discriminator used when hashing comparison identities.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -1,13 +1,49 @@
-use netform_ir::TriviaKind;
+use std::collections::HashSet;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::OnceLock;
 
-use crate::model::{NormalizationStep, NormalizeOptions};
+use netform_ir::{LineNode, ParsedLineParts, TriviaKind};
+
+use crate::model::{NormalizationStep, NormalizeOptions, StepScope};
+use crate::util::path_starts_with;
+
+/// Where a line sits in its document, for scoped normalization steps.
+#[derive(Debug, Clone)]
+pub(crate) struct LineScope {
+    pub(crate) path: Vec<usize>,
+    /// Key hints of enclosing block headers, outermost first.
+    pub(crate) enclosing_hints: Vec<String>,
+}
+
+impl LineScope {
+    fn contains(&self, line: &LineNode, scope: &StepScope) -> bool {
+        path_starts_with(&self.path, &scope.path_prefix)
+            && scope.key_hint.as_deref().is_none_or(|hint| {
+                line.key_hint.as_deref() == Some(hint)
+                    || self
+                        .enclosing_hints
+                        .iter()
+                        .any(|enclosing| enclosing == hint)
+            })
+    }
+}
 
 pub(crate) fn normalize_for_compare(
-    raw: &str,
-    trivia: TriviaKind,
+    line: &LineNode,
+    at: &LineScope,
     options: &NormalizeOptions,
 ) -> Option<String> {
-    let mut output = raw.to_string();
+    let trivia = line.trivia;
+    // Secret and interface spans index into `raw`, so those steps apply
+    // before any other.
+    let canonical_interfaces = options
+        .steps
+        .contains(&NormalizationStep::CanonicalizeInterfaces);
+    let mut output = rewrite_raw(
+        line,
+        SecretMode::from_options(options),
+        canonical_interfaces,
+    );
 
     for step in &options.steps {
         match step {
@@ -32,12 +68,208 @@ pub(crate) fn normalize_for_compare(
             NormalizationStep::CollapseInternalWhitespace => {
                 output = output.split_whitespace().collect::<Vec<_>>().join(" ");
             }
+            NormalizationStep::IgnoreMatching { pattern, scope } => {
+                if at.contains(line, scope) && pattern.regex().is_match(&output) {
+                    return None;
+                }
+            }
+            NormalizationStep::Rewrite {
+                pattern,
+                replacement,
+                scope,
+            } => {
+                if at.contains(line, scope) {
+                    output = pattern
+                        .regex()
+                        .replace_all(&output, replacement.as_str())
+                        .into_owned();
+                }
+            }
+            NormalizationStep::MaskSecrets
+            | NormalizationStep::HashSecrets
+            | NormalizationStep::CanonicalizeInterfaces => {}
         }
     }
 
     Some(output)
 }
 
+/// How secret values read in comparison text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SecretMode {
+    Mask,
+    Hash,
+}
+
+impl SecretMode {
+    /// Mode of the last secret step in `options`, if any.
+    pub(crate) fn from_options(options: &NormalizeOptions) -> Option<Self> {
+        options.steps.iter().rev().find_map(|step| match step {
+            NormalizationStep::MaskSecrets => Some(Self::Mask),
+            NormalizationStep::HashSecrets => Some(Self::Hash),
+            _ => None,
+        })
+    }
+}
+
+const SECRET_PLACEHOLDER: &str = "<secret>";
+const SECRET_CHANGED: &str = "<secret:changed>";
+const SECRET_DIGEST_PREFIX: &str = "<secret:";
+const SECRET_DIGEST_LEN: usize = 16;
+
+/// `line.raw` with secret spans replaced by `<secret>` (or `<secret:DIGEST>`
+/// in hash mode) and, when `interfaces` is set, interface names expanded.
+///
+/// Digests are keyed per run and must go through [`conceal_digests`] before
+/// reaching output.
+pub(crate) fn rewrite_raw(
+    line: &LineNode,
+    secrets: Option<SecretMode>,
+    interfaces: bool,
+) -> String {
+    let raw = line.raw.as_str();
+    let mut edits = Vec::new();
+    if let Some(mode) = secrets {
+        for span in &line.secrets {
+            let Some(value) = raw.get(span.start..span.end) else {
+                continue;
+            };
+            edits.push((span.start, span.end, secret_replacement(value, mode)));
+        }
+    }
+    if interfaces {
+        for name in &line.interfaces {
+            edits.push((name.start, name.end, name.canonical.clone()));
+        }
+    }
+    edits.sort_by_key(|(start, _, _)| *start);
+
+    let mut out = String::with_capacity(raw.len());
+    let mut pos = 0usize;
+    for (start, end, replacement) in edits {
+        // Overlapping or out-of-range spans are left as written.
+        if start < pos || raw.get(start..end).is_none() {
+            continue;
+        }
+        out.push_str(&raw[pos..start]);
+        out.push_str(&replacement);
+        pos = end;
+    }
+    out.push_str(&raw[pos..]);
+    out
+}
+
+/// `parts` with the token text covered by secret spans of `line` replaced as
+/// in [`rewrite_raw`].
+///
+/// Tokens are located in `raw` in order, so only the occurrence a span
+/// actually covers is redacted; a username that happens to equal the
+/// password stays readable. A token that cannot be located (the tokenizer
+/// rewrote it) is redacted wherever it contains a secret value.
+pub(crate) fn redact_parts(
+    line: &LineNode,
+    parts: ParsedLineParts,
+    mode: SecretMode,
+) -> ParsedLineParts {
+    let raw = line.raw.as_str();
+    let mut cursor = 0usize;
+    let mut redact = |token: String| {
+        let Some(offset) = raw.get(cursor..).and_then(|rest| rest.find(token.as_str())) else {
+            return line
+                .secrets
+                .iter()
+                .filter_map(|span| raw.get(span.start..span.end))
+                .filter(|value| !value.is_empty())
+                .fold(token, |token, value| {
+                    token.replace(value, &secret_replacement(value, mode))
+                });
+        };
+        let start = cursor + offset;
+        let end = start + token.len();
+        cursor = end;
+
+        let mut out = String::with_capacity(token.len());
+        let mut pos = start;
+        for span in &line.secrets {
+            let (from, to) = (span.start.max(pos), span.end.min(end));
+            let Some(value) = raw.get(span.start..span.end) else {
+                continue;
+            };
+            if from >= to {
+                continue;
+            }
+            out.push_str(&raw[pos..from]);
+            out.push_str(&secret_replacement(value, mode));
+            pos = to;
+        }
+        out.push_str(&raw[pos..end]);
+        out
+    };
+    let head = redact(parts.head);
+    let args = parts.args.into_iter().map(&mut redact).collect();
+    ParsedLineParts { head, args }
+}
+
+fn secret_replacement(value: &str, mode: SecretMode) -> String {
+    match mode {
+        SecretMode::Mask => SECRET_PLACEHOLDER.to_string(),
+        SecretMode::Hash => format!(
+            "{SECRET_DIGEST_PREFIX}{:0width$x}>",
+            secret_key().hash_one(value),
+            width = SECRET_DIGEST_LEN
+        ),
+    }
+}
+
+/// Random SipHash key shared by every comparison in this process.
+///
+/// Digests of low-entropy secrets under a fixed hash could be reversed with a
+/// dictionary, so hash mode keys them per run and never prints them.
+fn secret_key() -> &'static RandomState {
+    static KEY: OnceLock<RandomState> = OnceLock::new();
+    KEY.get_or_init(RandomState::new)
+}
+
+/// Hash-mode digests written into `text`.
+pub(crate) fn secret_digests(text: &str) -> impl Iterator<Item = &str> {
+    digest_markers(text).map(move |(start, end)| &text[start + SECRET_DIGEST_PREFIX.len()..end - 1])
+}
+
+/// `text` with each hash-mode digest shown as `<secret>`, or as
+/// `<secret:changed>` when `other` (the digests on the opposite side of an
+/// edit) lacks it.
+pub(crate) fn conceal_digests(text: &str, other: Option<&HashSet<&str>>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut pos = 0usize;
+    for (start, end) in digest_markers(text) {
+        let digest = &text[start + SECRET_DIGEST_PREFIX.len()..end - 1];
+        out.push_str(&text[pos..start]);
+        out.push_str(if other.is_some_and(|other| !other.contains(digest)) {
+            SECRET_CHANGED
+        } else {
+            SECRET_PLACEHOLDER
+        });
+        pos = end;
+    }
+    out.push_str(&text[pos..]);
+    out
+}
+
+/// Byte ranges of `<secret:DIGEST>` markers in `text`.
+fn digest_markers(text: &str) -> impl Iterator<Item = (usize, usize)> {
+    text.match_indices(SECRET_DIGEST_PREFIX)
+        .filter_map(move |(start, _)| {
+            let digest_start = start + SECRET_DIGEST_PREFIX.len();
+            let end = digest_start + SECRET_DIGEST_LEN + 1;
+            let marker = text.get(digest_start..end)?;
+            (marker.ends_with('>')
+                && marker[..SECRET_DIGEST_LEN]
+                    .bytes()
+                    .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')))
+            .then_some((start, end))
+        })
+}
+
 fn count_indent_columns(raw: &str) -> usize {
     let mut width = 0usize;
     for ch in raw.chars() {
@@ -96,6 +96,28 @@ pub fn build_plan(diff: &Diff) -> Plan {
                     });
                 }
             }
+            Edit::Move {
+                left_anchor,
+                right_anchor,
+                lines,
+                ..
+            } => {
+                if let (Some(source), Some(target)) = (left_anchor, right_anchor) {
+                    actions.push(PlanAction::MoveLines {
+                        source_path: source.path.clone(),
+                        source_span: source.span.clone(),
+                        target_path: target.path.clone(),
+                        target_span: target.span.clone(),
+                        lines: lines.iter().map(|line| line.text.clone()).collect(),
+                    });
+                } else {
+                    findings.push(PlanFinding {
+                        code: "missing_anchor".to_string(),
+                        message: "cannot create plan action for move edit without both anchors"
+                            .to_string(),
+                    });
+                }
+            }
         }
     }
 
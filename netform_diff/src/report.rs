@@ -1,4 +1,4 @@
-use crate::model::{Diff, Edit};
+use crate::model::{Diff, Edit, FleetSummary, LineTokenDiff, TokenChange};
 
 /// Format a markdown-oriented human report from a diff result.
 pub fn format_markdown_report(diff: &Diff, left_label: &str, right_label: &str) -> String {
@@ -17,9 +17,13 @@ pub fn format_markdown_report(diff: &Diff, left_label: &str, right_label: &str)
         diff.stats.deletes, diff.stats.deleted_lines
     ));
     out.push_str(&format!(
-        "- Replaces: {} ({} -> {} lines)\n\n",
+        "- Replaces: {} ({} -> {} lines)\n",
         diff.stats.replaces, diff.stats.replaced_old_lines, diff.stats.replaced_new_lines
     ));
+    out.push_str(&format!(
+        "- Moves: {} ({} lines)\n\n",
+        diff.stats.moves, diff.stats.moved_lines
+    ));
 
     out.push_str("## Edits\n\n");
     if diff.edits.is_empty() {
@@ -27,6 +31,11 @@ pub fn format_markdown_report(diff: &Diff, left_label: &str, right_label: &str)
     } else {
         for (idx, edit) in diff.edits.iter().enumerate() {
             out.push_str(&format!("{}. {}\n", idx + 1, describe_edit(edit)));
+            if let Edit::Replace { token_diffs, .. } = edit {
+                for line in token_diffs {
+                    out.push_str(&format!("   - {}\n", highlight_tokens(line)));
+                }
+            }
         }
     }
 
@@ -43,6 +52,50 @@ pub fn format_markdown_report(diff: &Diff, left_label: &str, right_label: &str)
     out
 }
 
+/// Format a markdown report summarizing a fleet against its reference.
+pub fn format_fleet_report(summary: &FleetSummary, reference_label: &str) -> String {
+    let mut out = String::new();
+    out.push_str("# Fleet Diff Report\n\n");
+    out.push_str(&format!("- Reference: `{reference_label}`\n"));
+    out.push_str(&format!(
+        "- Devices: {} ({} changed)\n\n",
+        summary.device_count, summary.changed_devices
+    ));
+
+    out.push_str("## Edits\n\n");
+    if summary.edits.is_empty() {
+        out.push_str("No changes detected.\n");
+    } else {
+        for (idx, edit) in summary.edits.iter().enumerate() {
+            out.push_str(&format!(
+                "{}. {:?} on {}/{} device(s)\n",
+                idx + 1,
+                edit.kind,
+                edit.devices.len(),
+                summary.device_count
+            ));
+            for line in &edit.old_lines {
+                out.push_str(&format!("   - `- {line}`\n"));
+            }
+            for line in &edit.new_lines {
+                out.push_str(&format!("   - `+ {line}`\n"));
+            }
+        }
+    }
+
+    if !summary.outliers.is_empty() {
+        out.push_str("\n## Outliers\n\n");
+        for outlier in &summary.outliers {
+            out.push_str(&format!(
+                "- `{}`: {} of {} edit(s) seen on no other device\n",
+                outlier.device, outlier.unique_edits, outlier.total_edits
+            ));
+        }
+    }
+
+    out
+}
+
 fn describe_edit(edit: &Edit) -> String {
     match edit {
         Edit::Insert { at_key, lines, .. } => format!(
@@ -68,5 +121,43 @@ fn describe_edit(edit: &Edit) -> String {
             new_lines.len(),
             crate::util::key_label(*new_at_key),
         ),
+        Edit::Move {
+            old_at_key,
+            new_at_key,
+            lines,
+            ..
+        } => format!(
+            "Move {} line(s) from key {} to key {}",
+            lines.len(),
+            crate::util::key_label(*old_at_key),
+            crate::util::key_label(*new_at_key),
+        ),
+    }
+}
+
+/// Render a token diff inline: unchanged runs as code, removed tokens struck
+/// through, added tokens in bold.
+fn highlight_tokens(line: &LineTokenDiff) -> String {
+    let mut parts = Vec::new();
+    let mut equal_run: Vec<&str> = Vec::new();
+    for change in &line.tokens {
+        if let TokenChange::Equal { token } = change {
+            equal_run.push(token);
+            continue;
+        }
+        if !equal_run.is_empty() {
+            parts.push(format!("`{}`", equal_run.join(" ")));
+            equal_run.clear();
+        }
+        match change {
+            TokenChange::Changed { old, new } => parts.push(format!("~~`{old}`~~ **`{new}`**")),
+            TokenChange::Removed { token } => parts.push(format!("~~`{token}`~~")),
+            TokenChange::Added { token } => parts.push(format!("**`{token}`**")),
+            TokenChange::Equal { .. } => {}
+        }
+    }
+    if !equal_run.is_empty() {
+        parts.push(format!("`{}`", equal_run.join(" ")));
     }
+    parts.join(" ")
 }
@@ -1,10 +1,14 @@
+use netform_dialect_asa::parse_asa;
 use netform_dialect_iosxe::parse_iosxe;
-use netform_ir::{Path, Span, parse_generic};
+use netform_dialect_junos::{hierarchical_to_set, parse_junos};
+use netform_ir::{Node, Path, Span, parse_generic};
 
+use super::align::{Op, compute_ops};
 use super::{
-    Diff, DiffLine, Edit, EditAnchor, NormalizationStep, NormalizeOptions, OrderPolicy,
-    OrderPolicyConfig, PlanAction, PlanLineEditKind, build_comparison_view, build_plan,
-    diff_documents,
+    Diff, DiffAlgorithm, DiffLine, Edit, EditAnchor, LinePattern, NormalizationStep,
+    NormalizeOptions, OrderPolicy, OrderPolicyConfig, OrderPolicyOverride, PlanAction,
+    PlanLineEditKind, StepScope, TokenChange, build_comparison_view, build_plan, diff_documents,
+    format_markdown_report, merge3,
 };
 
 #[test]
@@ -17,6 +21,172 @@ fn detects_replace_edit() {
     assert!(matches!(diff.edits[0], Edit::Replace { .. }));
 }
 
+#[test]
+fn replace_edit_carries_token_level_diff() {
+    let a = parse_generic("router bgp 65000\n neighbor 10.0.0.1 route-map RM-IN in\n");
+    let b = parse_generic("router bgp 65000\n neighbor 10.0.0.1 route-map RM-IN-V2 in\n");
+
+    let diff = diff_documents(&a, &b, NormalizeOptions::default());
+    let Edit::Replace { token_diffs, .. } = &diff.edits[0] else {
+        panic!("expected a replace edit");
+    };
+    assert_eq!(token_diffs.len(), 1);
+    assert_eq!((token_diffs[0].old_index, token_diffs[0].new_index), (0, 0));
+
+    let equal = |token: &str| TokenChange::Equal {
+        token: token.to_string(),
+    };
+    assert_eq!(
+        token_diffs[0].tokens,
+        vec![
+            equal("neighbor"),
+            equal("10.0.0.1"),
+            equal("route-map"),
+            TokenChange::Changed {
+                old: "RM-IN".to_string(),
+                new: "RM-IN-V2".to_string(),
+            },
+            equal("in"),
+        ]
+    );
+}
+
+#[test]
+fn token_diff_reports_added_and_removed_args() {
+    let a = parse_generic("interface Ethernet1\n switchport trunk allowed vlan 10\n");
+    let b = parse_generic("interface Ethernet1\n switchport trunk allowed vlan add 10 20\n");
+
+    let diff = diff_documents(&a, &b, NormalizeOptions::default());
+    let Edit::Replace { token_diffs, .. } = &diff.edits[0] else {
+        panic!("expected a replace edit");
+    };
+    let changes = token_diffs[0]
+        .tokens
+        .iter()
+        .filter(|token| !matches!(token, TokenChange::Equal { .. }))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        changes,
+        vec![
+            &TokenChange::Added {
+                token: "add".to_string()
+            },
+            &TokenChange::Added {
+                token: "20".to_string()
+            },
+        ]
+    );
+
+    let removed = super::tokens::align_tokens(&["shutdown", "now"], &["shutdown"]);
+    assert_eq!(
+        removed[1],
+        TokenChange::Removed {
+            token: "now".to_string()
+        }
+    );
+}
+
+#[test]
+fn token_diff_skips_lines_with_different_heads() {
+    let a = parse_generic("interface Ethernet1\n description old\n");
+    let b = parse_generic("interface Ethernet1\n mtu 9000\n");
+
+    let diff = diff_documents(&a, &b, NormalizeOptions::default());
+    let Edit::Replace { token_diffs, .. } = &diff.edits[0] else {
+        panic!("expected a replace edit");
+    };
+    assert!(token_diffs.is_empty());
+}
+
+fn apply_ops(a: &[u64], b: &[u64], ops: &[Op]) -> Vec<u64> {
+    let (mut i, mut j) = (0usize, 0usize);
+    let mut out = Vec::new();
+    for op in ops {
+        match op {
+            Op::Equal => {
+                assert_eq!(a[i], b[j], "equal op on different keys");
+                out.push(a[i]);
+                i += 1;
+                j += 1;
+            }
+            Op::Delete => i += 1,
+            Op::Insert => {
+                out.push(b[j]);
+                j += 1;
+            }
+        }
+    }
+    assert_eq!((i, j), (a.len(), b.len()));
+    out
+}
+
+#[test]
+fn every_algorithm_produces_a_valid_edit_script() {
+    let cases: [(&[u64], &[u64]); 6] = [
+        (&[], &[1, 2]),
+        (&[1, 2], &[]),
+        (&[1, 2, 3], &[1, 2, 3]),
+        (&[1, 9, 9, 2, 9, 3], &[9, 1, 2, 9, 9, 4, 3]),
+        (&[5, 5, 5, 5], &[5, 6, 5]),
+        (&[1, 2, 3, 4, 5, 6], &[6, 5, 4, 3, 2, 1]),
+    ];
+    for algorithm in [
+        DiffAlgorithm::Myers,
+        DiffAlgorithm::Patience,
+        DiffAlgorithm::Histogram,
+    ] {
+        for (a, b) in cases {
+            let ops = compute_ops(a, b, algorithm);
+            assert_eq!(apply_ops(a, b, &ops), b, "{algorithm:?} on {a:?} -> {b:?}");
+        }
+    }
+}
+
+#[test]
+fn patience_and_histogram_anchor_on_unique_lines() {
+    // `hostname` (1) and `end` (3) are unique; `!` (2) repeats.
+    let a = [1, 2, 2, 2, 3];
+    let b = [2, 2, 2, 1, 3];
+
+    let myers = compute_ops(&a, &b, DiffAlgorithm::Myers);
+    assert_eq!(myers[0], Op::Delete);
+
+    for algorithm in [DiffAlgorithm::Patience, DiffAlgorithm::Histogram] {
+        let ops = compute_ops(&a, &b, algorithm);
+        assert_eq!(
+            ops,
+            vec![
+                Op::Insert,
+                Op::Insert,
+                Op::Insert,
+                Op::Equal,
+                Op::Delete,
+                Op::Delete,
+                Op::Delete,
+                Op::Equal,
+            ],
+            "{algorithm:?}"
+        );
+    }
+}
+
+#[test]
+fn diff_records_selected_algorithm() {
+    let a = parse_generic("hostname a\n");
+    let b = parse_generic("hostname b\n");
+
+    let diff = diff_documents(
+        &a,
+        &b,
+        NormalizeOptions::default().with_algorithm(DiffAlgorithm::Histogram),
+    );
+    assert_eq!(diff.algorithm, DiffAlgorithm::Histogram);
+    assert_eq!(
+        diff_documents(&a, &b, NormalizeOptions::default()).algorithm,
+        DiffAlgorithm::Myers
+    );
+}
+
 #[test]
 fn ignores_comments_when_configured() {
     let a = parse_generic("! generated\ninterface Ethernet1\n");
@@ -142,6 +312,231 @@ fn keyed_stable_policy_ignores_reordered_block_children() {
     assert!(!diff.has_changes);
 }
 
+const JUNOS_BGP: &str = "protocols {\n    bgp {\n        group EBGP {\n            type external;\n            neighbor 10.0.0.1 {\n                peer-as 65001;\n            }\n            neighbor 10.0.0.2 {\n                peer-as 65002;\n            }\n        }\n    }\n    ospf {\n        area 0 {\n            interface ge-0/0/0;\n            interface ge-0/0/1;\n        }\n    }\n}\n";
+
+fn unordered_at(context_prefix: Vec<usize>) -> NormalizeOptions {
+    NormalizeOptions::default().with_order_policy(OrderPolicyConfig {
+        default: OrderPolicy::Ordered,
+        overrides: vec![OrderPolicyOverride {
+            context_prefix,
+            policy: OrderPolicy::Unordered,
+        }],
+    })
+}
+
+#[test]
+fn deep_policy_override_applies_to_nested_context() {
+    let a = parse_junos(JUNOS_BGP);
+    let b = parse_junos(&JUNOS_BGP.replace(
+        "            neighbor 10.0.0.1 {\n                peer-as 65001;\n            }\n            neighbor 10.0.0.2 {\n                peer-as 65002;\n            }\n",
+        "            neighbor 10.0.0.2 {\n                peer-as 65002;\n            }\n            neighbor 10.0.0.1 {\n                peer-as 65001;\n            }\n",
+    ));
+
+    // `protocols bgp group EBGP` sits at [0, 0, 0].
+    let diff = diff_documents(&a, &b, unordered_at(vec![0, 0, 0]));
+    assert!(!diff.has_changes);
+
+    let diff = diff_documents(&a, &b, NormalizeOptions::default());
+    assert!(diff.has_changes);
+}
+
+#[test]
+fn deep_policy_override_does_not_leak_into_sibling_contexts() {
+    let a = parse_junos(JUNOS_BGP);
+    let b = parse_junos(&JUNOS_BGP.replace(
+        "            interface ge-0/0/0;\n            interface ge-0/0/1;\n",
+        "            interface ge-0/0/1;\n            interface ge-0/0/0;\n",
+    ));
+
+    let diff = diff_documents(&a, &b, unordered_at(vec![0, 0, 0]));
+    assert!(diff.has_changes);
+    for edit in &diff.edits {
+        let anchor = match edit {
+            Edit::Insert { right_anchor, .. } => right_anchor,
+            Edit::Delete { left_anchor, .. }
+            | Edit::Replace { left_anchor, .. }
+            | Edit::Move { left_anchor, .. } => left_anchor,
+        };
+        let anchor = anchor.as_ref().expect("edit anchor");
+        assert!(anchor.path.0.starts_with(&[0, 1, 0]));
+    }
+
+    // `protocols ospf area 0` sits at [0, 1, 0].
+    let diff = diff_documents(&a, &b, unordered_at(vec![0, 1, 0]));
+    assert!(!diff.has_changes);
+}
+
+#[test]
+fn nested_edits_do_not_mix_levels() {
+    let a = parse_junos(JUNOS_BGP);
+    let b = parse_junos(
+        &JUNOS_BGP
+            .replace("type external;", "type internal;")
+            .replace("peer-as 65002;", "peer-as 65000;"),
+    );
+
+    let diff = diff_documents(&a, &b, NormalizeOptions::default());
+    assert_eq!(diff.edits.len(), 2);
+    let depths = diff
+        .edits
+        .iter()
+        .map(|edit| match edit {
+            Edit::Replace {
+                old_lines,
+                new_lines,
+                ..
+            } => {
+                assert_eq!((old_lines.len(), new_lines.len()), (1, 1));
+                old_lines[0].path.0.len()
+            }
+            other => panic!("expected replace edits, got {other:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(depths, vec![4, 5]);
+}
+
+const ROUTE_MAPS: &str = "route-map RM-IN permit 10\n match community CL-A\nroute-map RM-IN permit 20\n match community CL-B\nroute-map RM-IN permit 30\n set local-preference 50\n";
+
+#[test]
+fn reordered_block_is_reported_as_move() {
+    let a = parse_iosxe(ROUTE_MAPS);
+    let b = parse_iosxe(
+        "route-map RM-IN permit 20\n match community CL-B\nroute-map RM-IN permit 10\n match community CL-A\nroute-map RM-IN permit 30\n set local-preference 50\n",
+    );
+
+    let diff = diff_documents(&a, &b, NormalizeOptions::default());
+    assert_eq!(diff.edits.len(), 1);
+    let Edit::Move {
+        left_anchor: Some(source),
+        right_anchor: Some(destination),
+        lines,
+        ..
+    } = &diff.edits[0]
+    else {
+        panic!("expected a move edit, got {:?}", diff.edits[0]);
+    };
+    assert_eq!(source.path.0, vec![0]);
+    assert_eq!(destination.path.0, vec![1]);
+    assert_eq!(
+        lines
+            .iter()
+            .map(|line| line.text.as_str())
+            .collect::<Vec<_>>(),
+        vec!["route-map RM-IN permit 10", " match community CL-A"]
+    );
+    assert_eq!((diff.stats.moves, diff.stats.moved_lines), (1, 2));
+    assert!(diff.findings.is_empty());
+}
+
+#[test]
+fn reordered_list_entry_is_reported_as_move() {
+    let a = parse_asa(
+        "access-list OUTSIDE_IN extended permit tcp any host 10.0.0.5 eq https\naccess-list OUTSIDE_IN extended permit icmp any any\naccess-list OUTSIDE_IN extended deny ip any any\n",
+    );
+    let b = parse_asa(
+        "access-list OUTSIDE_IN extended permit icmp any any\naccess-list OUTSIDE_IN extended permit tcp any host 10.0.0.5 eq https\naccess-list OUTSIDE_IN extended deny ip any any\n",
+    );
+
+    let diff = diff_documents(&a, &b, NormalizeOptions::default());
+    assert_eq!(diff.edits.len(), 1);
+    assert!(matches!(&diff.edits[0], Edit::Move { lines, .. } if lines.len() == 1));
+    assert!(diff.findings.is_empty());
+}
+
+#[test]
+fn moved_block_with_changed_content_is_not_a_move() {
+    let a = parse_iosxe(ROUTE_MAPS);
+    let b = parse_iosxe(
+        "route-map RM-IN permit 20\n match community CL-B\nroute-map RM-IN permit 10\n match community CL-C\nroute-map RM-IN permit 30\n set local-preference 50\n",
+    );
+
+    let diff = diff_documents(&a, &b, NormalizeOptions::default());
+    assert_eq!(diff.stats.moves, 0);
+    assert!(diff.has_changes);
+}
+
+#[test]
+fn reordered_comments_are_not_moves() {
+    let a = parse_generic("! one\n! two\n");
+    let b = parse_generic("! two\n! one\n");
+
+    let diff = diff_documents(&a, &b, NormalizeOptions::default());
+    assert_eq!(diff.stats.moves, 0);
+    assert!(diff.has_changes);
+}
+
+#[test]
+fn keyed_lists_keep_entry_order_under_keyed_stable() {
+    let a = parse_asa(
+        "access-list OUTSIDE_IN extended permit tcp any host 10.0.0.5 eq https\naccess-list OUTSIDE_IN extended deny ip any any\n",
+    );
+    let b = parse_asa(
+        "access-list OUTSIDE_IN extended deny ip any any\naccess-list OUTSIDE_IN extended permit tcp any host 10.0.0.5 eq https\n",
+    );
+
+    let diff = diff_documents(
+        &a,
+        &b,
+        NormalizeOptions::default().with_order_policy(OrderPolicyConfig {
+            default: OrderPolicy::KeyedStable,
+            overrides: Vec::new(),
+        }),
+    );
+
+    assert!(diff.has_changes);
+    assert!(diff.findings.is_empty());
+}
+
+#[test]
+fn root_lines_sharing_key_hints_follow_order_policy() {
+    let a = parse_junos(
+        "set interfaces ge-0/0/0 description uplink\nset interfaces ge-0/0/0 mtu 9000\n",
+    );
+    let b = parse_junos(
+        "set interfaces ge-0/0/0 mtu 9000\nset interfaces ge-0/0/0 description uplink\n",
+    );
+
+    let ordered = diff_documents(&a, &b, NormalizeOptions::default());
+    assert!(ordered.has_changes);
+    for policy in [OrderPolicy::Unordered, OrderPolicy::KeyedStable] {
+        let diff = diff_documents(
+            &a,
+            &b,
+            NormalizeOptions::default().with_order_policy(OrderPolicyConfig {
+                default: policy,
+                overrides: Vec::new(),
+            }),
+        );
+        assert!(!diff.has_changes, "{policy:?}: {:?}", diff.edits);
+    }
+}
+
+#[test]
+fn keyed_lists_align_by_name_when_entries_change() {
+    let a = parse_asa(
+        "access-list MGMT extended permit tcp any any eq ssh\naccess-list OUTSIDE_IN extended permit tcp any host 10.0.0.5 eq https\naccess-list OUTSIDE_IN extended deny ip any any\n",
+    );
+    let b = parse_asa(
+        "access-list MGMT extended permit tcp any any eq ssh\naccess-list OUTSIDE_IN extended permit tcp any host 10.0.0.6 eq https\naccess-list OUTSIDE_IN extended deny ip any any\n",
+    );
+
+    let diff = diff_documents(&a, &b, NormalizeOptions::default());
+    assert_eq!(diff.edits.len(), 1);
+    match &diff.edits[0] {
+        Edit::Replace {
+            old_lines,
+            new_lines,
+            ..
+        } => {
+            assert_eq!(old_lines.len(), 1);
+            assert!(new_lines[0].text.contains("10.0.0.6"));
+        }
+        other => panic!("expected replace edit, got {other:?}"),
+    }
+    // The list segment matched by name, so no fallback alignment was needed.
+    assert!(diff.findings.is_empty());
+}
+
 #[test]
 fn fallback_alignment_emits_finding() {
     let a = parse_generic("interface Ethernet1\n  description one\n");
@@ -301,3 +696,490 @@ fn emits_finding_for_ambiguous_extracted_stanza_keys() {
         f.code == "ambiguous_key_match" && f.message.contains("ambiguous extracted key")
     }));
 }
+
+#[test]
+fn edit_anchor_paths_resolve_to_source_nodes() {
+    let a = parse_iosxe("interface Ethernet1\n  description old\n  mtu 9000\n");
+    let b = parse_iosxe("interface Ethernet1\n  description new\n  mtu 9000\n");
+
+    let diff = diff_documents(&a, &b, NormalizeOptions::default());
+    let Edit::Replace {
+        left_anchor: Some(left),
+        right_anchor: Some(right),
+        ..
+    } = &diff.edits[0]
+    else {
+        panic!("expected anchored replace edit");
+    };
+
+    let left_id = a.resolve(&left.path).expect("left anchor resolves");
+    let right_id = b.resolve(&right.path).expect("right anchor resolves");
+    assert!(matches!(a.node(left_id), Some(Node::Line(l)) if l.raw == "  description old"));
+    assert!(matches!(b.node(right_id), Some(Node::Line(l)) if l.raw == "  description new"));
+    assert_eq!(
+        a.ancestors(left_id),
+        a.find_by_key_hint("interface:Ethernet1")
+    );
+}
+
+#[test]
+fn intended_set_config_matches_converted_hierarchical_actual() {
+    let intended = parse_junos(
+        "set system host-name edge-1\nset interfaces ge-0/0/0 unit 0 family inet address 10.0.0.1/31\nset interfaces ge-0/0/1 disable\n",
+    );
+    let actual = parse_junos(
+        "system {\n    host-name edge-1;\n}\ninterfaces {\n    ge-0/0/0 {\n        unit 0 {\n            family inet {\n                address 10.0.0.1/31;\n            }\n        }\n    }\n    ge-0/0/1 {\n        disable;\n    }\n}\n",
+    );
+
+    let diff = diff_documents(
+        &intended,
+        &hierarchical_to_set(&actual),
+        NormalizeOptions::default(),
+    );
+    assert!(!diff.has_changes);
+    assert!(diff.edits.is_empty());
+}
+
+#[test]
+fn merge3_combines_changes_to_different_stanzas() {
+    let base = parse_iosxe(
+        "hostname edge-01\ninterface Gi1\n description uplink\n no ip redirects\n mtu 1500\ninterface Gi2\n shutdown\n",
+    );
+    let ours = parse_iosxe(
+        "hostname edge-01\ninterface Gi1\n description core uplink\n no ip redirects\n mtu 1500\ninterface Gi2\n shutdown\n",
+    );
+    let theirs = parse_iosxe(
+        "hostname edge-02\ninterface Gi1\n description uplink\n no ip redirects\n mtu 9000\ninterface Gi2\n no shutdown\nntp server 10.0.0.1\n",
+    );
+
+    let result = merge3(&base, &ours, &theirs, NormalizeOptions::default());
+    assert!(!result.has_conflicts());
+    assert_eq!(
+        result.merged.render(),
+        "hostname edge-02\ninterface Gi1\n description core uplink\n no ip redirects\n mtu 9000\ninterface Gi2\n no shutdown\nntp server 10.0.0.1\n"
+    );
+    assert_eq!(result.render_with_markers(), result.merged.render());
+}
+
+#[test]
+fn merge3_takes_identical_changes_and_one_sided_deletes_cleanly() {
+    let base = parse_generic(
+        "ntp server 10.0.0.1\nlogging host 10.0.0.9\nsnmp-server community public RO\n",
+    );
+    let ours = parse_generic("ntp server 10.0.0.2\nlogging host 10.0.0.9\n");
+    let theirs = parse_generic(
+        "ntp server 10.0.0.2\nlogging host 10.0.0.9\nsnmp-server community public RO\n",
+    );
+
+    let result = merge3(&base, &ours, &theirs, NormalizeOptions::default());
+    assert!(!result.has_conflicts());
+    assert_eq!(
+        result.merged.render(),
+        "ntp server 10.0.0.2\nlogging host 10.0.0.9\n"
+    );
+}
+
+#[test]
+fn merge3_records_conflict_inside_nested_block() {
+    let base = parse_iosxe(
+        "router bgp 65000\n neighbor 10.0.0.1 remote-as 65001\n bgp log-neighbor-changes\n",
+    );
+    let ours = parse_iosxe(
+        "router bgp 65000\n neighbor 10.0.0.1 remote-as 65002\n bgp log-neighbor-changes\n",
+    );
+    let theirs = parse_iosxe(
+        "router bgp 65000\n neighbor 10.0.0.1 remote-as 65003\n bgp log-neighbor-changes\n",
+    );
+
+    let result = merge3(&base, &ours, &theirs, NormalizeOptions::default());
+    assert_eq!(result.conflicts.len(), 1);
+    let conflict = &result.conflicts[0];
+    assert_eq!(conflict.context_path, Path(vec![0]));
+    assert_eq!((conflict.merged_index, conflict.merged_len), (0, 1));
+    assert_eq!(
+        conflict.base_lines,
+        vec![" neighbor 10.0.0.1 remote-as 65001"]
+    );
+    assert_eq!(
+        conflict.theirs_lines,
+        vec![" neighbor 10.0.0.1 remote-as 65003"]
+    );
+    for anchor in [
+        &conflict.base_anchor,
+        &conflict.ours_anchor,
+        &conflict.theirs_anchor,
+    ] {
+        assert_eq!(
+            anchor.as_ref().map(|anchor| anchor.path.clone()),
+            Some(Path(vec![0, 0]))
+        );
+    }
+
+    // The merged document keeps `ours` at the conflict.
+    assert_eq!(result.merged.render(), ours.render());
+    assert_eq!(
+        result.render_with_markers(),
+        "router bgp 65000\n<<<<<<< ours\n neighbor 10.0.0.1 remote-as 65002\n||||||| base\n neighbor 10.0.0.1 remote-as 65001\n=======\n neighbor 10.0.0.1 remote-as 65003\n>>>>>>> theirs\n bgp log-neighbor-changes\n"
+    );
+}
+
+#[test]
+fn merge3_conflicts_when_one_side_deletes_a_stanza_the_other_changed() {
+    let base = parse_iosxe("interface Gi1\n mtu 1500\nhostname edge-01\n");
+    let ours = parse_iosxe("hostname edge-01\n");
+    let theirs = parse_iosxe("interface Gi1\n mtu 9000\nhostname edge-01\n");
+
+    let result = merge3(&base, &ours, &theirs, NormalizeOptions::default());
+    assert_eq!(result.conflicts.len(), 1);
+    let conflict = &result.conflicts[0];
+    assert_eq!(conflict.merged_len, 0);
+    assert!(conflict.ours_anchor.is_none());
+    assert_eq!(conflict.theirs_lines, vec!["interface Gi1", " mtu 9000"]);
+    assert_eq!(result.merged.render(), "hostname edge-01\n");
+}
+
+#[test]
+fn merge3_conflicts_when_both_sides_rewrite_a_kept_header() {
+    let base = parse_iosxe("interface Gi1\n mtu 1500\n");
+    let ours = parse_iosxe("interface GigabitEthernet1\n mtu 1500\n");
+    let theirs = parse_iosxe("interface gi1\n mtu 1500\n");
+
+    // All headers carry the `interface:GigabitEthernet1` key hint, so the block
+    // is kept on every side while its header text changed two different ways.
+    let result = merge3(&base, &ours, &theirs, NormalizeOptions::default());
+    assert_eq!(result.conflicts.len(), 1);
+    let conflict = &result.conflicts[0];
+    assert_eq!((conflict.merged_index, conflict.merged_len), (0, 1));
+    assert_eq!(conflict.base_lines, vec!["interface Gi1", " mtu 1500"]);
+    assert_eq!(conflict.theirs_lines, vec!["interface gi1", " mtu 1500"]);
+    assert_eq!(
+        result.merged.render(),
+        "interface GigabitEthernet1\n mtu 1500\n"
+    );
+}
+
+fn pattern(source: &str) -> LinePattern {
+    LinePattern::new(source).expect("valid pattern")
+}
+
+#[test]
+fn ignore_matching_drops_volatile_lines() {
+    let a = parse_iosxe("Building configuration...\nntp clock-period 17208\nhostname edge-01\n");
+    let b = parse_iosxe("Building configuration...\nntp clock-period 36029\nhostname edge-01\n");
+
+    assert!(diff_documents(&a, &b, NormalizeOptions::default()).has_changes);
+
+    let options = NormalizeOptions::new(vec![
+        NormalizationStep::IgnoreMatching {
+            pattern: pattern(r"^Building configuration"),
+            scope: StepScope::default(),
+        },
+        NormalizationStep::IgnoreMatching {
+            pattern: pattern(r"^ntp clock-period \d+$"),
+            scope: StepScope::default(),
+        },
+    ]);
+    assert_eq!(build_comparison_view(&a, &options).lines.len(), 1);
+    assert!(!diff_documents(&a, &b, options).has_changes);
+}
+
+#[test]
+fn rewrite_expands_capture_groups_before_comparison() {
+    let a = parse_generic("username admin secret 5 $1$abcd$xyz\n");
+    let b = parse_generic("username admin secret 5 $1$efgh$uvw\n");
+
+    let options = NormalizeOptions::new(vec![NormalizationStep::Rewrite {
+        pattern: pattern(r"^(username \S+ secret \d+) \S+$"),
+        replacement: "${1} <hash>".to_string(),
+        scope: StepScope::default(),
+    }]);
+    let view = build_comparison_view(&a, &options);
+    assert_eq!(view.lines[0].normalized, "username admin secret 5 <hash>");
+    assert_eq!(
+        view.lines[0].original,
+        "username admin secret 5 $1$abcd$xyz"
+    );
+    assert!(!diff_documents(&a, &b, options).has_changes);
+}
+
+#[test]
+fn ignored_block_header_keeps_children_compared() {
+    let options = NormalizeOptions::new(vec![NormalizationStep::IgnoreMatching {
+        pattern: pattern(r"^section"),
+        scope: StepScope::default(),
+    }]);
+
+    let a = parse_generic(
+        "section built 2024-01-01
+  mtu 1500
+",
+    );
+    let b = parse_generic(
+        "section built 2025-02-02
+  mtu 1500
+",
+    );
+    assert!(!diff_documents(&a, &b, options.clone()).has_changes);
+
+    let c = parse_generic(
+        "section built 2025-02-02
+  mtu 9000
+",
+    );
+    let diff = diff_documents(&a, &c, options);
+    let Edit::Replace {
+        old_lines,
+        new_lines,
+        ..
+    } = &diff.edits[0]
+    else {
+        panic!("expected replace edit");
+    };
+    assert_eq!(diff.edits.len(), 1);
+    assert_eq!(old_lines[0].text, "  mtu 1500");
+    assert_eq!(new_lines[0].text, "  mtu 9000");
+}
+
+#[test]
+fn pattern_steps_respect_key_hint_and_path_scopes() {
+    let doc = parse_iosxe(
+        "interface Gi1\n description uplink\ninterface Gi2\n description spare\ndescription top\n",
+    );
+    let ignore_descriptions = |scope: StepScope| {
+        let options = NormalizeOptions::new(vec![NormalizationStep::IgnoreMatching {
+            pattern: pattern(r"description"),
+            scope,
+        }]);
+        build_comparison_view(&doc, &options)
+            .lines
+            .into_iter()
+            .map(|line| line.original)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        ignore_descriptions(StepScope {
+            path_prefix: Vec::new(),
+            key_hint: Some("interface:GigabitEthernet2".to_string()),
+        }),
+        vec![
+            "interface Gi1",
+            " description uplink",
+            "interface Gi2",
+            "description top"
+        ]
+    );
+    assert_eq!(
+        ignore_descriptions(StepScope {
+            path_prefix: vec![0],
+            key_hint: None,
+        }),
+        vec![
+            "interface Gi1",
+            "interface Gi2",
+            " description spare",
+            "description top"
+        ]
+    );
+}
+
+#[test]
+fn pattern_steps_serialize_in_diff_output() {
+    let a = parse_generic("hostname a\n");
+    let options = NormalizeOptions::new(vec![
+        NormalizationStep::IgnoreComments,
+        NormalizationStep::Rewrite {
+            pattern: pattern(r"^hostname (\S+)$"),
+            replacement: "hostname <$1>".to_string(),
+            scope: StepScope {
+                path_prefix: vec![0],
+                key_hint: None,
+            },
+        },
+    ]);
+    let diff = diff_documents(&a, &a, options.clone());
+    let json = serde_json::to_value(&diff.normalization_steps).expect("serialize steps");
+    assert_eq!(
+        json,
+        serde_json::json!([
+            "ignore_comments",
+            {
+                "rewrite": {
+                    "pattern": "^hostname (\\S+)$",
+                    "replacement": "hostname <$1>",
+                    "scope": { "path_prefix": [0], "key_hint": null }
+                }
+            }
+        ])
+    );
+
+    let parsed: Vec<NormalizationStep> = serde_json::from_value(json).expect("deserialize steps");
+    assert_eq!(parsed, options.steps);
+
+    let invalid = serde_json::from_value::<NormalizationStep>(serde_json::json!({
+        "ignore_matching": { "pattern": "(unclosed" }
+    }));
+    assert!(invalid.is_err());
+    assert!(
+        LinePattern::new("(unclosed")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid pattern `(unclosed`")
+    );
+}
+
+const SECRET_LEFT: &str =
+    "username admin secret 9 $9$oldhash\nsnmp-server community RO-left RO\nhostname a\n";
+
+#[test]
+fn mask_secrets_compares_only_presence() {
+    let a = parse_iosxe(SECRET_LEFT);
+    let b = parse_iosxe(
+        "username admin secret 9 $9$newhash\nsnmp-server community RO-right RO\nhostname a\n",
+    );
+
+    assert!(diff_documents(&a, &b, NormalizeOptions::default()).has_changes);
+    let masked = NormalizeOptions::new(vec![NormalizationStep::MaskSecrets]);
+    assert!(!diff_documents(&a, &b, masked.clone()).has_changes);
+
+    let removed = parse_iosxe("username admin\nsnmp-server community RO-left RO\nhostname a\n");
+    let diff = diff_documents(&a, &removed, masked);
+    let Edit::Replace {
+        old_lines,
+        new_lines,
+        token_diffs,
+        ..
+    } = &diff.edits[0]
+    else {
+        panic!("expected replace edit");
+    };
+    assert_eq!(old_lines[0].text, "username admin secret 9 <secret>");
+    assert_eq!(new_lines[0].text, "username admin");
+    assert!(
+        token_diffs
+            .iter()
+            .flat_map(|diff| &diff.tokens)
+            .all(|change| !format!("{change:?}").contains("$9$"))
+    );
+}
+
+#[test]
+fn hash_secrets_detects_changes_without_leaking_values() {
+    let a = parse_iosxe(SECRET_LEFT);
+    let b = parse_iosxe(
+        "username admin secret 9 $9$newhash\nsnmp-server community RO-left RO\nhostname a\n",
+    );
+    let options = NormalizeOptions::new(vec![NormalizationStep::HashSecrets]);
+
+    assert!(!diff_documents(&a, &a, options.clone()).has_changes);
+    let diff = diff_documents(&a, &b, options);
+    assert_eq!(diff.edits.len(), 1);
+    let Edit::Replace {
+        old_lines,
+        new_lines,
+        ..
+    } = &diff.edits[0]
+    else {
+        panic!("expected replace edit");
+    };
+    assert_eq!(
+        old_lines[0].text,
+        "username admin secret 9 <secret:changed>"
+    );
+    assert_eq!(
+        new_lines[0].text,
+        "username admin secret 9 <secret:changed>"
+    );
+
+    // Digests are keyed per run and never printed, so outputs hold no value
+    // that could be matched against a dictionary.
+    let report = format_markdown_report(&diff, "left.cfg", "right.cfg");
+    let plan = serde_json::to_string(&build_plan(&diff)).expect("serialize plan");
+    let json = serde_json::to_string(&diff).expect("serialize diff");
+    for output in [report, plan, json] {
+        assert!(!output.contains("$9$"), "{output}");
+        assert!(output.contains("<secret:changed>"));
+        assert_eq!(
+            output.matches("<secret:").count(),
+            output.matches("<secret:changed>").count(),
+            "{output}"
+        );
+    }
+}
+
+#[test]
+fn secret_tokens_are_redacted_by_span_not_by_value() {
+    let a = parse_iosxe("username admin password 0 admin\n");
+    let b = parse_iosxe("username admin password 0 admin2\n");
+
+    let masked = NormalizeOptions::new(vec![NormalizationStep::MaskSecrets]);
+    assert!(!diff_documents(&a, &b, masked).has_changes);
+
+    let hashed = NormalizeOptions::new(vec![NormalizationStep::HashSecrets]);
+    let diff = diff_documents(&a, &b, hashed);
+    let Edit::Replace { token_diffs, .. } = &diff.edits[0] else {
+        panic!("expected replace edit");
+    };
+    let changes = token_diffs
+        .iter()
+        .flat_map(|diff| &diff.tokens)
+        .collect::<Vec<_>>();
+    let rendered = format!("{changes:?}");
+    assert!(!rendered.contains("admin2"), "{rendered}");
+    // Only the password token changes; the matching username stays readable.
+    let edited = changes
+        .iter()
+        .filter(|change| !matches!(change, TokenChange::Equal { .. }))
+        .collect::<Vec<_>>();
+    assert_eq!(edited.len(), 1, "{rendered}");
+    assert_eq!(
+        *edited[0],
+        &TokenChange::Changed {
+            old: "<secret:changed>".to_string(),
+            new: "<secret:changed>".to_string(),
+        }
+    );
+    assert!(
+        changes
+            .iter()
+            .any(|change| matches!(change, TokenChange::Equal { token } if token == "admin")),
+        "{rendered}"
+    );
+}
+
+#[test]
+fn canonicalize_interfaces_matches_abbreviated_names() {
+    let intended = parse_iosxe("interface Gi0/1\n shutdown\nip route 0.0.0.0 0.0.0.0 Gi0/1\n");
+    let actual = parse_iosxe(
+        "interface GigabitEthernet0/1\n shutdown\nip route 0.0.0.0 0.0.0.0 GigabitEthernet0/1\n",
+    );
+
+    // Key hints are canonical already, so only the route line differs.
+    let diff = diff_documents(&intended, &actual, NormalizeOptions::default());
+    let Edit::Replace {
+        old_lines,
+        new_lines,
+        ..
+    } = &diff.edits[0]
+    else {
+        panic!("expected replace edit");
+    };
+    assert_eq!(diff.edits.len(), 1);
+    assert_eq!(old_lines[0].text, "ip route 0.0.0.0 0.0.0.0 Gi0/1");
+    assert_eq!(
+        new_lines[0].text,
+        "ip route 0.0.0.0 0.0.0.0 GigabitEthernet0/1"
+    );
+
+    let options = NormalizeOptions::new(vec![NormalizationStep::CanonicalizeInterfaces]);
+    assert!(!diff_documents(&intended, &actual, options.clone()).has_changes);
+
+    let moved = parse_iosxe(
+        "interface GigabitEthernet0/1\n shutdown\nip route 0.0.0.0 0.0.0.0 GigabitEthernet0/2\n",
+    );
+    let diff = diff_documents(&intended, &moved, options);
+    let Edit::Replace { old_lines, .. } = &diff.edits[0] else {
+        panic!("expected replace edit");
+    };
+    assert_eq!(old_lines[0].text, "ip route 0.0.0.0 0.0.0.0 Gi0/1");
+}
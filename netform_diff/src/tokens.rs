@@ -0,0 +1,154 @@
+//! Token-level diffs for the line pairs of a replace edit.
+
+use std::collections::HashSet;
+
+use crate::model::{ComparisonLine, LineTokenDiff, TokenChange};
+use crate::normalize::conceal_digests;
+
+/// Pair old and new lines of a replace and align their tokens.
+///
+/// Each old line pairs with the first unpaired new line sharing its `head`
+/// (and its key hint, when both carry one). Lines without dialect tokens, such
+/// as comments, stay unpaired.
+pub(crate) fn token_diffs(old: &[&ComparisonLine], new: &[&ComparisonLine]) -> Vec<LineTokenDiff> {
+    let mut paired = vec![false; new.len()];
+    let mut out = Vec::new();
+
+    for (old_index, old_line) in old.iter().enumerate() {
+        let Some(old_parts) = &old_line.parts else {
+            continue;
+        };
+        let candidate = new.iter().zip(&paired).position(|(new_line, &taken)| {
+            !taken
+                && new_line
+                    .parts
+                    .as_ref()
+                    .is_some_and(|parts| parts.head == old_parts.head)
+                && (old_line.key_hint.is_none()
+                    || new_line.key_hint.is_none()
+                    || old_line.key_hint == new_line.key_hint)
+        });
+        let Some(new_index) = candidate else {
+            continue;
+        };
+        paired[new_index] = true;
+
+        let Some(new_parts) = &new[new_index].parts else {
+            continue;
+        };
+        let old_tokens = std::iter::once(&old_parts.head)
+            .chain(&old_parts.args)
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        let new_tokens = std::iter::once(&new_parts.head)
+            .chain(&new_parts.args)
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        out.push(LineTokenDiff {
+            old_index,
+            new_index,
+            tokens: align_tokens(&old_tokens, &new_tokens),
+        });
+    }
+
+    out
+}
+
+/// Show secret digests in `diffs` as `<secret>`, or as `<secret:changed>` when
+/// the opposite side of the replace lacks them.
+pub(crate) fn conceal_token_digests(
+    diffs: Vec<LineTokenDiff>,
+    old_digests: &HashSet<&str>,
+    new_digests: &HashSet<&str>,
+) -> Vec<LineTokenDiff> {
+    let (old_side, new_side) = (Some(new_digests), Some(old_digests));
+    diffs
+        .into_iter()
+        .map(|diff| LineTokenDiff {
+            tokens: diff
+                .tokens
+                .into_iter()
+                .map(|change| match change {
+                    TokenChange::Equal { token } => TokenChange::Equal {
+                        token: conceal_digests(&token, None),
+                    },
+                    TokenChange::Changed { old, new } => TokenChange::Changed {
+                        old: conceal_digests(&old, old_side),
+                        new: conceal_digests(&new, new_side),
+                    },
+                    TokenChange::Removed { token } => TokenChange::Removed {
+                        token: conceal_digests(&token, old_side),
+                    },
+                    TokenChange::Added { token } => TokenChange::Added {
+                        token: conceal_digests(&token, new_side),
+                    },
+                })
+                .collect(),
+            ..diff
+        })
+        .collect()
+}
+
+/// Align two token lists by longest common subsequence.
+///
+/// Removed and added tokens between the same pair of equal tokens are reported
+/// as changed, position by position; the surplus stays removed or added.
+pub(crate) fn align_tokens(old: &[&str], new: &[&str]) -> Vec<TokenChange> {
+    // lcs[i][j] = common subsequence length of old[i..] and new[j..].
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    let (mut i, mut j) = (0usize, 0usize);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            flush_run(&mut out, &mut removed, &mut added);
+            out.push(TokenChange::Equal {
+                token: old[i].to_string(),
+            });
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            removed.push(old[i]);
+            i += 1;
+        } else {
+            added.push(new[j]);
+            j += 1;
+        }
+    }
+    flush_run(&mut out, &mut removed, &mut added);
+
+    out
+}
+
+fn flush_run(out: &mut Vec<TokenChange>, removed: &mut Vec<&str>, added: &mut Vec<&str>) {
+    let changed = removed.len().min(added.len());
+    for (old, new) in removed.iter().zip(added.iter()) {
+        out.push(TokenChange::Changed {
+            old: old.to_string(),
+            new: new.to_string(),
+        });
+    }
+    for token in &removed[changed..] {
+        out.push(TokenChange::Removed {
+            token: token.to_string(),
+        });
+    }
+    for token in &added[changed..] {
+        out.push(TokenChange::Added {
+            token: token.to_string(),
+        });
+    }
+    removed.clear();
+    added.clear();
+}
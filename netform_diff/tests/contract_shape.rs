@@ -33,6 +33,7 @@ fn diff_json_shape_contract() {
     let obj = value.as_object().expect("diff should be object");
     assert!(obj.contains_key("normalization_steps"));
     assert!(obj.contains_key("order_policy"));
+    assert!(obj.contains_key("algorithm"));
     assert!(obj.contains_key("has_changes"));
     assert!(obj.contains_key("edits"));
     assert!(obj.contains_key("stats"));
@@ -53,38 +54,64 @@ fn diff_json_shape_contract() {
     assert!(!edits.is_empty());
 
     for edit in edits {
-        let edit_obj = edit.as_object().expect("edit should be object");
-        assert!(edit_obj.contains_key("type"));
-        assert!(!edit_obj.contains_key("confidence"));
-
-        match edit_obj
-            .get("type")
-            .and_then(Value::as_str)
-            .expect("type string")
-        {
-            "Insert" => {
-                assert!(edit_obj.contains_key("at_key"));
-                assert!(edit_obj.contains_key("left_anchor"));
-                assert!(edit_obj.contains_key("right_anchor"));
-                assert!(edit_obj.contains_key("lines"));
-            }
-            "Delete" => {
-                assert!(edit_obj.contains_key("at_key"));
-                assert!(edit_obj.contains_key("left_anchor"));
-                assert!(edit_obj.contains_key("right_anchor"));
-                assert!(edit_obj.contains_key("lines"));
-            }
-            "Replace" => {
-                assert!(edit_obj.contains_key("old_at_key"));
-                assert!(edit_obj.contains_key("new_at_key"));
-                assert!(edit_obj.contains_key("left_anchor"));
-                assert!(edit_obj.contains_key("right_anchor"));
-                assert!(edit_obj.contains_key("old_lines"));
-                assert!(edit_obj.contains_key("new_lines"));
-            }
-            other => panic!("unexpected edit type: {other}"),
+        assert_edit_shape(edit);
+    }
+}
+
+#[test]
+fn move_edit_json_shape_contract() {
+    let intended = parse_generic("interface Ethernet1\n  description uplink\n  mtu 9000\n");
+    let actual = parse_generic("interface Ethernet1\n  mtu 9000\n  description uplink\n");
+
+    let diff = diff_documents(&intended, &actual, NormalizeOptions::default());
+    let value = serde_json::to_value(&diff).expect("serialize diff");
+    let edits = value
+        .get("edits")
+        .and_then(Value::as_array)
+        .expect("edits should be array");
+
+    let types = edits.iter().map(assert_edit_shape).collect::<Vec<_>>();
+    assert!(types.contains(&"Move"), "{types:?}");
+}
+
+/// Check the keys of one serialized edit and return its type.
+fn assert_edit_shape(edit: &Value) -> &str {
+    let edit_obj = edit.as_object().expect("edit should be object");
+    assert!(edit_obj.contains_key("type"));
+    assert!(!edit_obj.contains_key("confidence"));
+
+    let kind = edit_obj
+        .get("type")
+        .and_then(Value::as_str)
+        .expect("type string");
+    match kind {
+        "Insert" | "Delete" => {
+            assert!(edit_obj.contains_key("at_key"));
+            assert!(edit_obj.contains_key("left_anchor"));
+            assert!(edit_obj.contains_key("right_anchor"));
+            assert!(edit_obj.contains_key("lines"));
+        }
+        "Replace" => {
+            assert!(edit_obj.contains_key("old_at_key"));
+            assert!(edit_obj.contains_key("new_at_key"));
+            assert!(edit_obj.contains_key("left_anchor"));
+            assert!(edit_obj.contains_key("right_anchor"));
+            assert!(edit_obj.contains_key("old_lines"));
+            assert!(edit_obj.contains_key("new_lines"));
+            assert!(edit_obj.contains_key("token_diffs"));
+        }
+        "Move" => {
+            assert!(edit_obj.contains_key("old_at_key"));
+            assert!(edit_obj.contains_key("new_at_key"));
+            assert!(edit_obj.contains_key("left_anchor"));
+            assert!(edit_obj.contains_key("right_anchor"));
+            assert!(edit_obj.contains_key("lines"));
+            assert!(!edit_obj.contains_key("old_lines"));
+            assert!(!edit_obj.contains_key("token_diffs"));
         }
+        other => panic!("unexpected edit type: {other}"),
     }
+    kind
 }
 
 #[test]
@@ -1,22 +1,28 @@
 use std::fs;
 use std::path::Path;
 
-use netform_diff::{NormalizeOptions, diff_documents};
+use netform_diff::{DiffAlgorithm, NormalizeOptions, diff_documents};
 use netform_ir::parse_generic;
 
-#[test]
-fn diff_is_deterministic_for_embedded_corpus_pairs() {
+fn corpus_samples() -> Vec<String> {
     let repo_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
     let testdata = repo_root.join("netform_ir").join("testdata");
 
-    let mut samples = Vec::new();
-    for entry in fs::read_dir(&testdata).expect("read testdata") {
-        let entry = entry.expect("entry");
-        let path = entry.path();
-        if path.is_file() {
-            samples.push(fs::read_to_string(path).expect("read sample"));
-        }
-    }
+    let mut paths = fs::read_dir(&testdata)
+        .expect("read testdata")
+        .map(|entry| entry.expect("entry").path())
+        .filter(|path| path.is_file())
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| fs::read_to_string(path).expect("read sample"))
+        .collect()
+}
+
+#[test]
+fn diff_is_deterministic_for_embedded_corpus_pairs() {
+    let samples = corpus_samples();
 
     for (i, a_text) in samples.iter().enumerate() {
         for (j, b_text) in samples.iter().enumerate() {
@@ -36,3 +42,35 @@ fn diff_is_deterministic_for_embedded_corpus_pairs() {
         }
     }
 }
+
+#[test]
+fn every_algorithm_is_deterministic_for_embedded_corpus_pairs() {
+    let samples = corpus_samples();
+
+    for algorithm in [
+        DiffAlgorithm::Myers,
+        DiffAlgorithm::Patience,
+        DiffAlgorithm::Histogram,
+    ] {
+        for (i, a_text) in samples.iter().enumerate() {
+            for (j, b_text) in samples.iter().enumerate() {
+                let a = parse_generic(a_text);
+                let b = parse_generic(b_text);
+                let options = NormalizeOptions::default().with_algorithm(algorithm);
+
+                let one = diff_documents(&a, &b, options.clone());
+                let two = diff_documents(&a, &b, options);
+                assert_eq!(one.algorithm, algorithm);
+
+                let one_json = serde_json::to_string_pretty(&one).expect("serialize first");
+                let two_json = serde_json::to_string_pretty(&two).expect("serialize second");
+
+                assert_eq!(
+                    one_json, two_json,
+                    "flapping {algorithm:?} output for corpus pair ({i}, {j})"
+                );
+                assert_eq!(one.has_changes, i != j || a_text != b_text);
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use netform_dialect_iosxe::parse_iosxe;
+use netform_diff::{
+    FleetEditKind, FleetReference, NormalizeOptions, diff_documents, diff_fleet,
+    format_fleet_report,
+};
+
+const GOLDEN: &str =
+    "hostname golden\nntp server 10.0.0.1\ninterface Gi1\n description uplink\n mtu 9000\n";
+
+#[test]
+fn fleet_reference_matches_pairwise_diff() {
+    let golden = parse_iosxe(GOLDEN);
+    let device = parse_iosxe("hostname golden\ninterface Gi1\n description uplink\n mtu 1500\n");
+
+    let reference = FleetReference::new(&golden, NormalizeOptions::default());
+    assert_eq!(
+        reference.diff(&device),
+        diff_documents(&golden, &device, NormalizeOptions::default())
+    );
+}
+
+#[test]
+fn fleet_summary_counts_shared_edits_and_flags_outliers() {
+    let golden = parse_iosxe(GOLDEN);
+    let clean = parse_iosxe(GOLDEN);
+    let small_mtu =
+        "hostname golden\nntp server 10.0.0.1\ninterface Gi1\n description uplink\n mtu 1500\n";
+    let edge_1 = parse_iosxe(small_mtu);
+    let edge_2 = parse_iosxe(small_mtu);
+    let edge_3 = parse_iosxe(
+        "hostname golden\nntp server 10.9.9.9\ninterface Gi1\n description uplink\n mtu 1500\n",
+    );
+
+    let fleet = diff_fleet(
+        &golden,
+        [
+            ("core-1".to_string(), &clean),
+            ("edge-1".to_string(), &edge_1),
+            ("edge-2".to_string(), &edge_2),
+            ("edge-3".to_string(), &edge_3),
+        ],
+        NormalizeOptions::default(),
+    );
+
+    assert_eq!(fleet.devices.len(), 4);
+    assert!(!fleet.devices[0].diff.has_changes);
+    let summary = &fleet.summary;
+    assert_eq!((summary.device_count, summary.changed_devices), (4, 3));
+
+    assert_eq!(summary.edits.len(), 2);
+    let mtu = &summary.edits[0];
+    assert_eq!(mtu.kind, FleetEditKind::Replace);
+    assert_eq!(mtu.old_lines, vec![" mtu 9000"]);
+    assert_eq!(mtu.new_lines, vec![" mtu 1500"]);
+    assert_eq!(mtu.devices, vec!["edge-1", "edge-2", "edge-3"]);
+    assert_eq!(summary.edits[1].devices, vec!["edge-3"]);
+
+    assert_eq!(summary.outliers.len(), 1);
+    assert_eq!(summary.outliers[0].device, "edge-3");
+    assert_eq!(
+        (
+            summary.outliers[0].unique_edits,
+            summary.outliers[0].total_edits
+        ),
+        (1, 2)
+    );
+
+    let report = format_fleet_report(summary, "golden.cfg");
+    assert!(report.contains("# Fleet Diff Report"));
+    assert!(report.contains("- Devices: 4 (3 changed)"));
+    assert!(report.contains("1. Replace on 3/4 device(s)"));
+    assert!(report.contains("   - `+  mtu 1500`"));
+    assert!(report.contains("- `edge-3`: 1 of 2 edit(s) seen on no other device"));
+}
+
+#[test]
+fn fleet_summary_counts_devices_by_position_not_name() {
+    let golden = parse_iosxe(GOLDEN);
+    let first = parse_iosxe(&GOLDEN.replace("mtu 9000", "mtu 1500"));
+    let second = parse_iosxe(&GOLDEN.replace("10.0.0.1", "10.0.0.2"));
+
+    let fleet = diff_fleet(
+        &golden,
+        [("r1".to_string(), &first), ("r1".to_string(), &second)],
+        NormalizeOptions::default(),
+    );
+
+    let summary = &fleet.summary;
+    assert_eq!(summary.edits.len(), 2);
+    assert!(summary.edits.iter().all(|edit| edit.devices == vec!["r1"]));
+    assert_eq!(summary.outliers.len(), 2);
+    assert!(
+        summary
+            .outliers
+            .iter()
+            .all(|outlier| (outlier.unique_edits, outlier.total_edits) == (1, 1))
+    );
+}
@@ -30,6 +30,31 @@ fn generates_line_edit_plan_for_single_line_replace() {
     }
 }
 
+#[test]
+fn generates_move_plan_for_reordered_block() {
+    let a = parse_generic("route-map RM permit 10\n  set metric 10\nroute-map RM permit 20\n");
+    let b = parse_generic("route-map RM permit 20\nroute-map RM permit 10\n  set metric 10\n");
+
+    let diff = diff_documents(&a, &b, NormalizeOptions::default());
+    let plan = build_plan(&diff);
+
+    assert_eq!(plan.findings.len(), 0);
+    assert_eq!(plan.actions.len(), 1);
+    match &plan.actions[0] {
+        PlanAction::MoveLines {
+            source_path,
+            target_path,
+            lines,
+            ..
+        } => {
+            assert_eq!(source_path.0, vec![0]);
+            assert_eq!(target_path.0, vec![1]);
+            assert_eq!(lines, &vec!["route-map RM permit 10", "  set metric 10"]);
+        }
+        other => panic!("expected move action, got {other:?}"),
+    }
+}
+
 #[test]
 fn generates_replace_block_plan_for_multi_line_replace() {
     let a = parse_generic("interface Ethernet1\n  description old\n  mtu 9000\n");
@@ -90,6 +115,7 @@ fn groups_multiple_line_edits_under_same_context() {
                     path: Path(vec![0, 1]),
                     span: anchor_a.span.clone(),
                 }],
+                token_diffs: Vec::new(),
             },
             Edit::Replace {
                 old_at_key: Some(21),
@@ -110,6 +136,7 @@ fn groups_multiple_line_edits_under_same_context() {
                     path: Path(vec![0, 2]),
                     span: anchor_b.span.clone(),
                 }],
+                token_diffs: Vec::new(),
             },
         ],
         ..Diff::default()
@@ -172,6 +199,7 @@ fn preserves_first_seen_action_order_when_grouping_line_actions() {
                     path: Path(vec![0, 1]),
                     span: line_anchor.span.clone(),
                 }],
+                token_diffs: Vec::new(),
             },
             Edit::Replace {
                 old_at_key: Some(3),
@@ -226,6 +254,7 @@ fn preserves_first_seen_action_order_when_grouping_line_actions() {
                         },
                     },
                 ],
+                token_diffs: Vec::new(),
             },
         ],
         ..Diff::default()
@@ -30,3 +30,26 @@ fn json_output_is_stable_shape() {
     assert!(json.contains("\"old_at_key\""));
     assert!(json.contains("\"occurrence_key\""));
 }
+
+#[test]
+fn markdown_report_highlights_changed_tokens() {
+    let a = parse_generic("router bgp 65000\n neighbor 10.0.0.1 route-map RM-IN in\n");
+    let b = parse_generic("router bgp 65000\n neighbor 10.0.0.1 route-map RM-IN-V2 in\n");
+
+    let diff = diff_documents(&a, &b, NormalizeOptions::default());
+    let report = format_markdown_report(&diff, "left.cfg", "right.cfg");
+
+    assert!(report.contains("`neighbor 10.0.0.1 route-map` ~~`RM-IN`~~ **`RM-IN-V2`** `in`"));
+}
+
+#[test]
+fn markdown_report_lists_moves() {
+    let a = parse_generic("ntp server 10.0.0.1\nntp server 10.0.0.2\n");
+    let b = parse_generic("ntp server 10.0.0.2\nntp server 10.0.0.1\n");
+
+    let diff = diff_documents(&a, &b, NormalizeOptions::default());
+    let report = format_markdown_report(&diff, "left.cfg", "right.cfg");
+
+    assert!(report.contains("Moves: 1 (1 lines)"));
+    assert!(report.contains("Move 1 line(s) from key 0x"));
+}
@@ -0,0 +1,204 @@
+//! Heuristic dialect detection from configuration text.
+//!
+//! Detection scores the first lines of a config against vendor signals
+//! (`Building configuration...`, `#config-version=`, `set ` prefixes, braces,
+//! `/interface` paths, ...) and ranks dialect names by their share of the
+//! evidence. Names match the dialect crates (`iosxe`, `junos`, ...); input
+//! without any signal ranks `generic` with zero confidence.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+/// Number of leading lines [`detect_dialect`] scores.
+pub const DETECT_SAMPLE_LINES: usize = 200;
+
+/// Confidence below which a detection should be treated as a guess.
+pub const LOW_CONFIDENCE: f64 = 0.5;
+
+/// Score at which a dialect has enough evidence on its own.
+const STRONG_EVIDENCE: u32 = 8;
+
+/// One ranked dialect guess.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DialectGuess {
+    /// Dialect name, as used by `config-diff --dialect`.
+    pub name: String,
+    /// Confidence in `0.0..=1.0`.
+    pub confidence: f64,
+}
+
+/// Ranked result of [`detect_dialect`], best guess first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DialectDetection {
+    /// Guesses with any evidence, by descending confidence; `generic` alone
+    /// when nothing matched.
+    pub ranked: Vec<DialectGuess>,
+}
+
+impl DialectDetection {
+    /// Best guess, if any.
+    pub fn best(&self) -> Option<&DialectGuess> {
+        self.ranked.first()
+    }
+
+    /// Whether the best guess reaches [`LOW_CONFIDENCE`].
+    pub fn is_confident(&self) -> bool {
+        self.best()
+            .is_some_and(|guess| guess.confidence >= LOW_CONFIDENCE)
+    }
+}
+
+/// Guess the dialect of `input` from its first [`DETECT_SAMPLE_LINES`] lines.
+pub fn detect_dialect(input: &str) -> DialectDetection {
+    detect_dialect_in(input, DETECT_SAMPLE_LINES)
+}
+
+/// Guess the dialect of `input` from its first `max_lines` lines.
+///
+/// Identical lines score once. Confidence is the best dialect's share of all
+/// evidence, scaled down while the best score is below a fixed evidence
+/// threshold. Ties rank by name.
+pub fn detect_dialect_in(input: &str, max_lines: usize) -> DialectDetection {
+    let mut scores: BTreeMap<&'static str, u32> = BTreeMap::new();
+    let mut seen = BTreeSet::new();
+    for raw in input.lines().take(max_lines) {
+        // Repeated separators (`!`, `}`, `next`) say no more than the first one.
+        if !seen.insert(raw.trim()) {
+            continue;
+        }
+        for (name, weight) in line_signals(raw) {
+            *scores.entry(name).or_default() += weight;
+        }
+    }
+
+    let total = scores.values().sum::<u32>();
+    let best = scores.values().copied().max().unwrap_or(0);
+    if total == 0 {
+        return DialectDetection {
+            ranked: vec![DialectGuess {
+                name: "generic".to_string(),
+                confidence: 0.0,
+            }],
+        };
+    }
+
+    let evidence = f64::from(best.min(STRONG_EVIDENCE)) / f64::from(STRONG_EVIDENCE);
+    let mut ranked = scores.into_iter().collect::<Vec<_>>();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    DialectDetection {
+        ranked: ranked
+            .into_iter()
+            .map(|(name, score)| DialectGuess {
+                name: name.to_string(),
+                confidence: f64::from(score) / f64::from(total) * evidence,
+            })
+            .collect(),
+    }
+}
+
+/// Weighted dialect signals carried by one line.
+fn line_signals(raw: &str) -> Vec<(&'static str, u32)> {
+    let trimmed = raw.trim();
+    let indented = raw.starts_with(char::is_whitespace);
+    let mut out = Vec::new();
+
+    // Headers written by the device itself are the strongest signals.
+    if trimmed.starts_with("Building configuration") || trimmed.starts_with("Current configuration")
+    {
+        out.push(("iosxe", 6));
+    }
+    if trimmed.starts_with("!Command: show running-config") || trimmed.starts_with("!Time:") {
+        out.push(("nxos", 6));
+    }
+    if trimmed.starts_with("! Command: show running-config") || trimmed.starts_with("! device:") {
+        out.push(("eos", 6));
+    }
+    if trimmed.starts_with("!! IOS XR Configuration") {
+        out.push(("iosxr", 8));
+    }
+    if trimmed.starts_with("#config-version=") {
+        out.push(("fortios", 8));
+    }
+    if trimmed.starts_with('#') && trimmed.contains("by RouterOS") {
+        out.push(("routeros", 8));
+    }
+    if trimmed.starts_with("# TiMOS-") {
+        out.push(("sros", 8));
+    }
+    if trimmed.starts_with("ASA Version") {
+        out.push(("asa", 8));
+    }
+    if trimmed.starts_with("!Software Version V") {
+        out.push(("vrp", 6));
+    }
+    if trimmed.starts_with("## Last commit:") || trimmed.starts_with("## Last changed:") {
+        out.push(("junos", 6));
+    }
+
+    // Structural and keyword signals.
+    match trimmed {
+        "!" => out.extend([
+            ("iosxe", 2),
+            ("eos", 1),
+            ("iosxr", 1),
+            ("nxos", 1),
+            ("asa", 1),
+        ]),
+        "#" if !indented => out.push(("vrp", 1)),
+        "}" => out.extend([("junos", 1), ("sros", 1)]),
+        "exit all" => out.push(("sros", 4)),
+        "configure {" => out.push(("sros", 4)),
+        "next" => out.push(("fortios", 1)),
+        "end-policy" | "end-set" => out.push(("iosxr", 3)),
+        "return" => out.push(("vrp", 2)),
+        ": Saved" | ":end" => out.push(("asa", 3)),
+        _ => {}
+    }
+
+    if !indented {
+        // RouterOS entries also start with `set`/`add` but carry `key=value` args.
+        if (trimmed.starts_with("set ") || trimmed.starts_with("add ")) && trimmed.contains('=') {
+            out.push(("routeros", 1));
+        } else if trimmed.starts_with("set ") || trimmed.starts_with("deactivate ") {
+            out.push(("junos", 2));
+        }
+        if trimmed.starts_with("/interface") || trimmed.starts_with("/ip ") {
+            out.push(("routeros", 3));
+        }
+        if trimmed.starts_with("config system ") || trimmed.starts_with("config firewall ") {
+            out.push(("fortios", 3));
+        }
+        if trimmed.starts_with("feature ") || trimmed.starts_with("vrf context ") {
+            out.push(("nxos", 2));
+        }
+        if trimmed.starts_with("sysname ") {
+            out.push(("vrp", 3));
+        }
+        if trimmed.starts_with("object network ") || trimmed.starts_with("object-group ") {
+            out.push(("asa", 2));
+        }
+        if trimmed.starts_with("management api ") {
+            out.push(("eos", 3));
+        }
+    }
+
+    if trimmed.starts_with("undo ") {
+        out.push(("vrp", 1));
+    }
+    if trimmed.starts_with("nameif ") {
+        out.push(("asa", 2));
+    }
+    if trimmed.starts_with("edit ") && indented {
+        out.push(("fortios", 1));
+    }
+    if trimmed.ends_with(';') && !trimmed.starts_with('#') {
+        out.push(("junos", 1));
+    }
+    if trimmed.ends_with(" {") && !trimmed.starts_with('#') {
+        out.extend([("junos", 1), ("sros", 1)]);
+    }
+
+    out
+}
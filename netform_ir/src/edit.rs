@@ -0,0 +1,399 @@
+//! Structural editing operations for [`Document`].
+//!
+//! Edits keep `roots` and block `children` consistent and never reuse arena
+//! slots: removed nodes stay in the arena as tombstones so every [`NodeId`]
+//! handed out earlier keeps pointing at the same node (or at nothing).
+//!
+//! Spans are not updated eagerly. Call [`Document::recompute_spans`] after a
+//! batch of edits to renumber lines and byte offsets in render order.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::{Dialect, Document, LineNode, Node, NodeId, Path, Span, TriviaKind};
+
+/// Error returned by [`Document`] editing operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditError {
+    /// The id does not exist in the arena.
+    UnknownNode(NodeId),
+    /// The id refers to a node that was removed.
+    RemovedNode(NodeId),
+    /// The node is already part of the tree and cannot be attached twice.
+    AlreadyAttached(NodeId),
+    /// The target node is not a block and cannot hold children.
+    NotABlock(NodeId),
+    /// The path does not address an existing container or insert position.
+    InvalidPath(Path),
+    /// The operation would place a node below itself.
+    Cycle(NodeId),
+    /// The requested order is not a permutation of the current children.
+    InvalidOrder,
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownNode(id) => write!(f, "node {} does not exist", id.0),
+            Self::RemovedNode(id) => write!(f, "node {} was removed", id.0),
+            Self::AlreadyAttached(id) => write!(f, "node {} is already attached", id.0),
+            Self::NotABlock(id) => write!(f, "node {} is not a block", id.0),
+            Self::InvalidPath(path) => write!(f, "path {:?} does not address a position", path.0),
+            Self::Cycle(id) => write!(f, "node {} cannot be placed below itself", id.0),
+            Self::InvalidOrder => f.write_str("order is not a permutation of the current children"),
+        }
+    }
+}
+
+impl Error for EditError {}
+
+/// Position of an attached node: its parent block (`None` for roots) and sibling index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Location {
+    pub(crate) parent: Option<NodeId>,
+    pub(crate) index: usize,
+}
+
+impl LineNode {
+    /// Build a detached line from raw text using `dialect` for classification,
+    /// tokenization, key hints, secret spans, interface names, and list hints.
+    ///
+    /// The line is classified without enclosing lines, so dialect hooks see
+    /// no parents. The line ending defaults to `"\n"` and the span is zeroed
+    /// until [`Document::recompute_spans`] runs.
+    pub fn from_dialect<D: Dialect + ?Sized>(raw: &str, dialect: &D) -> Self {
+        let trivia = dialect.classify_trivia(raw);
+        let parsed = if trivia == TriviaKind::Content {
+            dialect.parse_parts(raw)
+        } else {
+            None
+        };
+        let key_hint = dialect.key_hint(raw, parsed.as_ref(), trivia);
+        let secrets = dialect.secret_spans(raw, parsed.as_ref(), &[], trivia);
+        let interfaces = dialect.interface_names(raw, parsed.as_ref(), trivia);
+        let list_hint = dialect.list_hint(raw, parsed.as_ref(), trivia);
+        Self {
+            raw: raw.to_string(),
+            line_ending: "\n".to_string(),
+            span: Span {
+                line: 0,
+                start_byte: 0,
+                end_byte: 0,
+            },
+            parsed,
+            key_hint,
+            trivia,
+            secrets,
+            interfaces,
+            list_hint,
+        }
+    }
+}
+
+impl Document {
+    /// Return `true` when `id` was removed by an edit.
+    pub fn is_removed(&self, id: NodeId) -> bool {
+        self.tombstones.contains(&id)
+    }
+
+    /// Detach `id` from the tree and tombstone it together with its descendants.
+    pub fn remove(&mut self, id: NodeId) -> Result<(), EditError> {
+        self.check_live(id)?;
+        if let Some(location) = self.locate(id) {
+            self.siblings_mut(location.parent).remove(location.index);
+        }
+        self.tombstone_subtree(id);
+        Ok(())
+    }
+
+    /// Insert `node` at `path` and return its new id.
+    ///
+    /// All but the last path index address the container (empty prefix means
+    /// roots); the last index is the sibling position, `0..=len`. Children
+    /// listed by a block `node` must be live, detached nodes.
+    pub fn insert_at(&mut self, path: &Path, node: Node) -> Result<NodeId, EditError> {
+        let (parent, index) = self.resolve_position(path)?;
+        self.check_new_children(&node, None, &[])?;
+        let id = self.insert_node(node);
+        self.siblings_mut(parent).insert(index, id);
+        Ok(id)
+    }
+
+    /// Move the attached or detached node `id` to `path`.
+    ///
+    /// `path` is resolved after `id` has been detached from its current position.
+    pub fn move_to(&mut self, id: NodeId, path: &Path) -> Result<(), EditError> {
+        self.check_live(id)?;
+        let previous = self.locate(id);
+        if let Some(location) = previous {
+            self.siblings_mut(location.parent).remove(location.index);
+        }
+
+        // The detached subtree is unreachable from roots, so `path` cannot
+        // resolve below `id` and no cycle check is needed.
+        let target = self.resolve_position(path);
+
+        match target {
+            Ok((parent, index)) => {
+                self.siblings_mut(parent).insert(index, id);
+                Ok(())
+            }
+            Err(err) => {
+                // Restore the original position so a failed move is a no-op.
+                if let Some(location) = previous {
+                    self.siblings_mut(location.parent)
+                        .insert(location.index, id);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Replace the node stored at `id` in place and return the previous node.
+    ///
+    /// The id and tree position are kept. Descendants of the old node that the
+    /// new node does not list as children are tombstoned.
+    pub fn replace(&mut self, id: NodeId, node: Node) -> Result<Node, EditError> {
+        self.check_live(id)?;
+        let old_children = match &self.arena[id.0] {
+            Node::Block(block) => block.children.clone(),
+            Node::Line(_) => Vec::new(),
+        };
+        self.check_new_children(&node, Some(id), &old_children)?;
+
+        let kept = match &node {
+            Node::Block(block) => block.children.clone(),
+            Node::Line(_) => Vec::new(),
+        };
+        let old = std::mem::replace(&mut self.arena[id.0], node);
+        for child in old_children {
+            if !kept.contains(&child) {
+                self.tombstone_subtree(child);
+            }
+        }
+        Ok(old)
+    }
+
+    /// Replace the text line of `id` (the header for blocks) and return the old line.
+    pub fn replace_line(&mut self, id: NodeId, line: LineNode) -> Result<LineNode, EditError> {
+        self.check_live(id)?;
+        let slot = match &mut self.arena[id.0] {
+            Node::Line(existing) => existing,
+            Node::Block(block) => &mut block.header,
+        };
+        Ok(std::mem::replace(slot, line))
+    }
+
+    /// Reorder the children of `parent` (roots when `None`) to exactly `order`.
+    pub fn reorder_children(
+        &mut self,
+        parent: Option<NodeId>,
+        order: &[NodeId],
+    ) -> Result<(), EditError> {
+        if let Some(parent) = parent {
+            self.check_live(parent)?;
+            if !matches!(self.arena[parent.0], Node::Block(_)) {
+                return Err(EditError::NotABlock(parent));
+            }
+        }
+
+        let current = self.siblings_mut(parent);
+        let mut sorted_current = current.clone();
+        let mut sorted_order = order.to_vec();
+        sorted_current.sort_unstable();
+        sorted_order.sort_unstable();
+        if sorted_current != sorted_order {
+            return Err(EditError::InvalidOrder);
+        }
+
+        current.copy_from_slice(order);
+        Ok(())
+    }
+
+    /// Renumber every rendered line's span and refresh line/byte metadata.
+    ///
+    /// Spans are assigned in render order, so they match `render()` output
+    /// after edits. Parse findings keep their original spans.
+    pub fn recompute_spans(&mut self) {
+        let mut order = Vec::new();
+        for root in &self.roots {
+            self.collect_render_order(*root, &mut order);
+        }
+
+        let mut line_no = 1usize;
+        let mut offset = 0usize;
+        for (id, slot) in order {
+            let line = match (&mut self.arena[id.0], slot) {
+                (Node::Line(line), _) => line,
+                (Node::Block(block), LineSlot::Header) => &mut block.header,
+                (Node::Block(block), LineSlot::Footer) => match &mut block.footer {
+                    Some(footer) => footer,
+                    None => continue,
+                },
+            };
+            line.span = Span {
+                line: line_no,
+                start_byte: offset,
+                end_byte: offset + line.raw.len(),
+            };
+            offset += line.raw.len() + line.line_ending.len();
+            // Joined continuation lines keep their inner line endings in `raw`.
+            line_no += 1 + line.raw.matches('\n').count();
+        }
+
+        self.metadata.line_count = line_no - 1;
+        self.metadata.original_bytes = offset;
+    }
+
+    pub(crate) fn locate(&self, id: NodeId) -> Option<Location> {
+        if let Some(index) = self.roots.iter().position(|root| *root == id) {
+            return Some(Location {
+                parent: None,
+                index,
+            });
+        }
+
+        self.arena
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| !self.tombstones.contains(&NodeId(*idx)))
+            .find_map(|(idx, node)| match node {
+                Node::Block(block) => {
+                    block
+                        .children
+                        .iter()
+                        .position(|child| *child == id)
+                        .map(|index| Location {
+                            parent: Some(NodeId(idx)),
+                            index,
+                        })
+                }
+                Node::Line(_) => None,
+            })
+    }
+
+    fn check_live(&self, id: NodeId) -> Result<(), EditError> {
+        if id.0 >= self.arena.len() {
+            return Err(EditError::UnknownNode(id));
+        }
+        if self.tombstones.contains(&id) {
+            return Err(EditError::RemovedNode(id));
+        }
+        Ok(())
+    }
+
+    fn check_new_children(
+        &self,
+        node: &Node,
+        owner: Option<NodeId>,
+        reusable: &[NodeId],
+    ) -> Result<(), EditError> {
+        let Node::Block(block) = node else {
+            return Ok(());
+        };
+
+        for (idx, child) in block.children.iter().copied().enumerate() {
+            self.check_live(child)?;
+            if block.children[..idx].contains(&child) {
+                return Err(EditError::AlreadyAttached(child));
+            }
+            if let Some(owner) = owner
+                && (child == owner || self.is_descendant(owner, child))
+            {
+                return Err(EditError::Cycle(owner));
+            }
+            if !reusable.contains(&child) && self.locate(child).is_some() {
+                return Err(EditError::AlreadyAttached(child));
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve_position(&self, path: &Path) -> Result<(Option<NodeId>, usize), EditError> {
+        let invalid = || EditError::InvalidPath(path.clone());
+        let (index, prefix) = path.0.split_last().ok_or_else(invalid)?;
+
+        let mut parent: Option<NodeId> = None;
+        for step in prefix {
+            let siblings = match parent {
+                None => &self.roots,
+                Some(id) => match &self.arena[id.0] {
+                    Node::Block(block) => &block.children,
+                    Node::Line(_) => return Err(invalid()),
+                },
+            };
+            parent = Some(*siblings.get(*step).ok_or_else(invalid)?);
+        }
+
+        let len = match parent {
+            None => self.roots.len(),
+            Some(id) => match &self.arena[id.0] {
+                Node::Block(block) => block.children.len(),
+                Node::Line(_) => return Err(EditError::NotABlock(id)),
+            },
+        };
+        if *index > len {
+            return Err(invalid());
+        }
+        Ok((parent, *index))
+    }
+
+    fn siblings_mut(&mut self, parent: Option<NodeId>) -> &mut Vec<NodeId> {
+        match parent {
+            Some(id) => match &mut self.arena[id.0] {
+                Node::Block(block) => &mut block.children,
+                Node::Line(_) => unreachable!("locations only point at blocks"),
+            },
+            None => &mut self.roots,
+        }
+    }
+
+    /// Return `true` when `id` lies strictly below `ancestor`.
+    fn is_descendant(&self, id: NodeId, ancestor: NodeId) -> bool {
+        let mut stack = match self.arena.get(ancestor.0) {
+            Some(Node::Block(block)) => block.children.clone(),
+            _ => return false,
+        };
+        while let Some(next) = stack.pop() {
+            if next == id {
+                return true;
+            }
+            if let Some(Node::Block(block)) = self.arena.get(next.0) {
+                stack.extend(block.children.iter().copied());
+            }
+        }
+        false
+    }
+
+    fn tombstone_subtree(&mut self, id: NodeId) {
+        let mut stack = vec![id];
+        while let Some(next) = stack.pop() {
+            if !self.tombstones.insert(next) {
+                continue;
+            }
+            if let Some(Node::Block(block)) = self.arena.get(next.0) {
+                stack.extend(block.children.iter().copied());
+            }
+        }
+    }
+
+    fn collect_render_order(&self, id: NodeId, out: &mut Vec<(NodeId, LineSlot)>) {
+        match self.arena.get(id.0) {
+            Some(Node::Line(_)) => out.push((id, LineSlot::Header)),
+            Some(Node::Block(block)) => {
+                out.push((id, LineSlot::Header));
+                for child in &block.children {
+                    self.collect_render_order(*child, out);
+                }
+                out.push((id, LineSlot::Footer));
+            }
+            None => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum LineSlot {
+    Header,
+    Footer,
+}
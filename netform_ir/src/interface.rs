@@ -0,0 +1,69 @@
+//! Interface name canonicalization shared by dialects.
+
+use serde::{Deserialize, Serialize};
+
+/// An abbreviated interface name in a line's `raw` text and its full form.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterfaceName {
+    pub start: usize,
+    pub end: usize,
+    pub canonical: String,
+}
+
+/// Expand an abbreviated interface name with `table`.
+///
+/// Each table entry is `(full type name, shortest accepted abbreviation)`.
+/// The type prefix of `name` (everything before the first digit) matches an
+/// entry case-insensitively when it is at least the shortest abbreviation and
+/// a prefix of the full type name; the first matching entry wins. Returns
+/// `None` when nothing matches or `name` is already canonical.
+///
+/// ```rust
+/// use netform_ir::canonical_interface_name;
+///
+/// let table = &[("GigabitEthernet", "Gi"), ("Port-channel", "Po")];
+/// assert_eq!(
+///     canonical_interface_name("gi0/1", table).as_deref(),
+///     Some("GigabitEthernet0/1")
+/// );
+/// assert_eq!(canonical_interface_name("GigabitEthernet0/1", table), None);
+/// ```
+pub fn canonical_interface_name(name: &str, table: &[(&str, &str)]) -> Option<String> {
+    let digit = name.find(|c: char| c.is_ascii_digit())?;
+    let (prefix, number) = name.split_at(digit);
+    if prefix.is_empty()
+        || !number
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '/' | '.' | ':'))
+    {
+        return None;
+    }
+    let prefix = prefix.to_ascii_lowercase();
+    let (full, _) = table.iter().find(|(full, shortest)| {
+        prefix.starts_with(&shortest.to_ascii_lowercase())
+            && full.to_ascii_lowercase().starts_with(&prefix)
+    })?;
+    let canonical = format!("{full}{number}");
+    (canonical != name).then_some(canonical)
+}
+
+/// Locate whitespace-separated tokens of `raw` that expand with `table`.
+pub fn interface_names_in(raw: &str, table: &[(&str, &str)]) -> Vec<InterfaceName> {
+    let mut out = Vec::new();
+    let mut pos = 0usize;
+    while let Some(offset) = raw[pos..].find(|c: char| !c.is_whitespace()) {
+        let start = pos + offset;
+        let end = raw[start..]
+            .find(char::is_whitespace)
+            .map_or(raw.len(), |idx| start + idx);
+        if let Some(canonical) = canonical_interface_name(&raw[start..end], table) {
+            out.push(InterfaceName {
+                start,
+                end,
+                canonical,
+            });
+        }
+        pos = end;
+    }
+    out
+}
@@ -4,9 +4,20 @@
 //! - a tree model (`Document`, `Node`, `LineNode`, `BlockNode`)
 //! - a conservative parser (`parse_generic`, `parse_with_dialect`)
 //! - a lossless renderer (`Document::render`)
+//! - an editing API that keeps the tree consistent (`Document::insert_at`,
+//!   `Document::remove`, `Document::move_to`, `Document::replace`, ...)
+//! - lookup by path and key hint plus parent navigation (`Document::resolve`,
+//!   `Document::path_of`, `Document::find_by_key_hint`, `Document::ancestors`)
+//! - a selector language for hierarchical queries (`Selector`, `Document::select`)
+//! - heuristic dialect detection (`detect_dialect`)
+//! - runtime dialect lookup by name (`DialectRegistry`)
 //!
 //! The parser is intentionally conservative for pre-alpha use:
-//! - it only uses indentation as a structural cue
+//! - it uses indentation as the default structural cue
+//! - dialects may drive block open/close from tokens (for example braces)
+//! - dialects may mark delimited literal regions (banners, certificates) kept opaque
+//! - dialects may report secret values, kept in `LineNode::secrets` for masking
+//! - dialects may expand abbreviated interface names, kept in `LineNode::interfaces`
 //! - unknown patterns are preserved as regular lines
 //! - no input lines are dropped
 //!
@@ -21,8 +32,27 @@
 //! ```
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use std::fmt;
 
+mod detect;
+mod edit;
+mod interface;
+mod lookup;
+mod registry;
+mod secret;
+mod select;
+
+pub use detect::{
+    DETECT_SAMPLE_LINES, DialectDetection, DialectGuess, LOW_CONFIDENCE, detect_dialect,
+    detect_dialect_in,
+};
+pub use edit::EditError;
+pub use interface::{InterfaceName, canonical_interface_name, interface_names_in};
+pub use registry::{DialectRegistry, UnknownDialectError};
+pub use secret::{SecretKeyword, SecretSpan, secret_values_after};
+pub use select::{Selector, SelectorError, SelectorMatch, glob_match};
+
 /// Stable arena identifier for a node in a [`Document`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct NodeId(pub usize);
@@ -64,6 +94,17 @@ pub struct LineNode {
     pub parsed: Option<ParsedLineParts>,
     pub key_hint: Option<String>,
     pub trivia: TriviaKind,
+    /// Secret values in `raw` reported by the dialect, in line order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secrets: Vec<SecretSpan>,
+    /// Abbreviated interface names in `raw` with their canonical form, in line
+    /// order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub interfaces: Vec<InterfaceName>,
+    /// Name of the flat list this line is an entry of, from
+    /// [`Dialect::list_hint`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_hint: Option<String>,
 }
 
 /// Structured block node with a header line and nested children.
@@ -75,7 +116,63 @@ pub struct BlockNode {
     pub kind_label: Option<String>,
 }
 
+/// Explicit structural role a dialect can assign to a line from its tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BlockRole {
+    /// Line opens a block and becomes its header.
+    Open,
+    /// Line closes the innermost explicitly opened block and becomes its footer.
+    Close,
+    /// Line never opens a block, regardless of indentation.
+    Leaf,
+    /// Line closes the innermost indentation-opened block whose header has the
+    /// same indentation and becomes its footer; otherwise it is kept as a line.
+    Terminator,
+    /// Line opens a root block holding every following line until the next
+    /// section line, regardless of indentation (for example RouterOS `/ip address`).
+    Section,
+    /// Line closes the innermost indentation-opened block whose header is at or
+    /// left of the line's indentation and becomes its footer (for example VRP
+    /// `quit`); otherwise it is kept as a line.
+    Exit,
+}
+
+/// Delimited literal text region opened by a header line (for example a banner).
+///
+/// Lines inside the region are kept verbatim as children of one block and are
+/// never classified or tokenized by the dialect.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiteralRegion {
+    /// Label stored in [`BlockNode::kind_label`] for the region block.
+    pub kind_label: String,
+    /// Rule that finds the closing line, which becomes the block footer.
+    pub end: LiteralEnd,
+}
+
+/// Closing rule for a [`LiteralRegion`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LiteralEnd {
+    /// The first following line containing this delimiter closes the region.
+    Delimiter(String),
+    /// The first following line whose trimmed text equals this marker closes the region.
+    Marker(String),
+}
+
+impl LiteralEnd {
+    fn closes(&self, raw: &str) -> bool {
+        match self {
+            Self::Delimiter(delimiter) => raw.contains(delimiter.as_str()),
+            Self::Marker(marker) => raw.trim() == marker,
+        }
+    }
+}
+
 /// Arena node variant.
+// Blocks embed header and footer lines; boxing them would add an indirection
+// to every arena lookup.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Node {
     Line(LineNode),
@@ -116,6 +213,9 @@ pub struct Document {
     pub metadata: DocumentMetadata,
     pub roots: Vec<NodeId>,
     pub arena: Vec<Node>,
+    /// Arena ids removed by edits; their slots are kept so ids stay stable.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub tombstones: BTreeSet<NodeId>,
 }
 
 impl Document {
@@ -125,6 +225,7 @@ impl Document {
             metadata,
             roots: Vec::new(),
             arena: Vec::new(),
+            tombstones: BTreeSet::new(),
         }
     }
 
@@ -143,14 +244,22 @@ impl Document {
     }
 
     /// Borrow a node by id.
+    ///
+    /// Returns `None` for unknown ids and for nodes removed by an edit.
     pub fn node(&self, id: NodeId) -> Option<&Node> {
+        if self.tombstones.contains(&id) {
+            return None;
+        }
         self.arena.get(id.0)
     }
 
     /// Append `child` to `parent` if parent is a block.
     ///
-    /// Returns `true` when attached, `false` when `parent` is not a block.
+    /// Returns `true` when attached, `false` when `parent` is not a live block.
     pub fn add_child(&mut self, parent: NodeId, child: NodeId) -> bool {
+        if self.tombstones.contains(&parent) {
+            return false;
+        }
         match self.arena.get_mut(parent.0) {
             Some(Node::Block(block)) => {
                 block.children.push(child);
@@ -219,6 +328,91 @@ pub trait Dialect {
     ) -> Option<String> {
         None
     }
+    /// Optionally assign an explicit block role to this line.
+    ///
+    /// Returning `None` keeps indentation-based structure for the line.
+    fn block_role(
+        &self,
+        _raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        _trivia: TriviaKind,
+    ) -> Option<BlockRole> {
+        None
+    }
+    /// Optionally locate secret values (passwords, keys, hashes, communities)
+    /// in this line.
+    ///
+    /// `parents` are the parsed content lines enclosing this one by
+    /// indentation, outermost first, so a keyword can count as a secret only
+    /// in the right section. Spans index into `raw`, must not overlap, and are
+    /// kept in [`LineNode::secrets`] so diffs can mask and redact them.
+    fn secret_spans(
+        &self,
+        _raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        _parents: &[&ParsedLineParts],
+        _trivia: TriviaKind,
+    ) -> Vec<SecretSpan> {
+        Vec::new()
+    }
+    /// Optionally locate abbreviated interface names (`Gi0/1`, `Po10`) in this
+    /// line together with their canonical spelling.
+    ///
+    /// Spans index into `raw`, must not overlap, and are kept in
+    /// [`LineNode::interfaces`] so diffs can compare canonical names.
+    fn interface_names(
+        &self,
+        _raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        _trivia: TriviaKind,
+    ) -> Vec<InterfaceName> {
+        Vec::new()
+    }
+    /// Optionally name the flat list this line is an entry of (for example ASA
+    /// `access-list NAME ...` lines).
+    ///
+    /// Consecutive root lines sharing a list hint are diffed as one list whose
+    /// entries keep their order under every order policy. Returning `None`
+    /// leaves the line to the requested policy.
+    fn list_hint(
+        &self,
+        _raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        _trivia: TriviaKind,
+    ) -> Option<String> {
+        None
+    }
+    /// Whether this physical line continues onto the next one (for example a
+    /// trailing `\`).
+    ///
+    /// Continued lines are joined into one [`LineNode`] whose `raw` keeps the
+    /// inner line endings, so rendering stays lossless.
+    fn continues_line(&self, _raw: &str) -> bool {
+        false
+    }
+    /// Optionally open a delimited literal region at this content line.
+    ///
+    /// `parents` are the enclosing lines as for [`Dialect::secret_spans`]. The
+    /// line becomes the header of a block labeled `kind_label`; following lines
+    /// are kept opaque until the region's end rule matches.
+    fn literal_region(
+        &self,
+        _raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        _parents: &[&ParsedLineParts],
+        _trivia: TriviaKind,
+    ) -> Option<LiteralRegion> {
+        None
+    }
+    /// Parse a whole document with this dialect.
+    ///
+    /// Defaults to [`parse_with_dialect`]; dialects that configure themselves
+    /// from the input (for example a syntax flavor sniffed from content)
+    /// override it so callers holding a `dyn Dialect` get the same result as
+    /// the dialect crate's own parse function.
+    fn parse_document(&self, input: &str) -> Document {
+        parse_with_dialect(input, self)
+    }
 }
 
 /// Conservative default dialect for vendor-agnostic parsing.
@@ -245,7 +439,21 @@ impl Dialect for GenericDialect {
 /// - open a block when next content line is more indented
 /// - close blocks on non-blank dedent
 /// - preserve all lines even when structure is uncertain
-pub fn parse_with_dialect<D: Dialect>(input: &str, dialect: &D) -> Document {
+///
+/// Lines with an explicit [`BlockRole`] from the dialect override indentation:
+/// - `Open` lines always open a block that dedent cannot close
+/// - `Close` lines close the innermost explicit block and become its footer
+/// - `Leaf` lines never open a block
+/// - `Section` lines open a root block that lasts until the next section line
+/// - `Terminator` lines close a same-indent implicit block (for example IOS XR `!`);
+///   right after a same-indent line inside a block, they turn that line into an
+///   empty block instead
+/// - `Exit` lines close the innermost implicit block at or left of their indentation
+///   (for example VRP `quit`)
+///
+/// Lines opening a [`LiteralRegion`] become a labeled block whose children are
+/// the verbatim region lines and whose footer is the closing line.
+pub fn parse_with_dialect<D: Dialect + ?Sized>(input: &str, dialect: &D) -> Document {
     let mut doc = Document::new(DocumentMetadata {
         source_name: None,
         dialect_hint: dialect.dialect_hint(),
@@ -260,12 +468,33 @@ pub fn parse_with_dialect<D: Dialect>(input: &str, dialect: &D) -> Document {
         &mut doc.metadata.line_count,
         &mut doc.metadata.parse_findings,
     );
-    let mut parent_stack: Vec<(usize, NodeId)> = Vec::new();
+    let mut parent_stack: Vec<OpenBlock> = Vec::new();
+    let mut literal_block: Option<NodeId> = None;
 
     for idx in 0..lines.len() {
         let line = &lines[idx];
 
-        if line.trivia == TriviaKind::Content && line.indent > 0 && parent_stack.is_empty() {
+        match (&line.literal, literal_block) {
+            (Some(LiteralSlot::Body), Some(block_id)) => {
+                let id = doc.insert_node(Node::Line(line.as_line_node()));
+                doc.add_child(block_id, id);
+                continue;
+            }
+            (Some(LiteralSlot::Close), Some(block_id)) => {
+                if let Some(Node::Block(block)) = doc.arena.get_mut(block_id.0) {
+                    block.footer = Some(line.as_line_node());
+                }
+                literal_block = None;
+                continue;
+            }
+            _ => {}
+        }
+
+        if line.role.is_none()
+            && line.trivia == TriviaKind::Content
+            && line.indent > 0
+            && parent_stack.is_empty()
+        {
             doc.metadata.parse_findings.push(ParseFinding {
                 code: "orphan-indentation".to_string(),
                 message: "indented content line without an open parent block; line kept as-is"
@@ -274,10 +503,60 @@ pub fn parse_with_dialect<D: Dialect>(input: &str, dialect: &D) -> Document {
             });
         }
 
-        // Non-blank lines can close open blocks when indentation decreases.
+        if line.role == Some(BlockRole::Close) {
+            if parent_stack
+                .iter()
+                .any(|open| open.kind == OpenKind::Explicit)
+            {
+                // Implicit blocks nested in the explicit block end with it.
+                while parent_stack
+                    .last()
+                    .is_some_and(|open| open.kind == OpenKind::Indent)
+                {
+                    parent_stack.pop();
+                }
+                if let Some(open) = parent_stack.pop()
+                    && let Some(Node::Block(block)) = doc.arena.get_mut(open.id.0)
+                {
+                    block.footer = Some(line.as_line_node());
+                }
+                continue;
+            }
+
+            doc.metadata.parse_findings.push(ParseFinding {
+                code: "unmatched-block-close".to_string(),
+                message: "block close without an explicitly opened block; line kept as-is"
+                    .to_string(),
+                span: line.span.clone(),
+            });
+        }
+
+        if matches!(line.role, Some(BlockRole::Terminator | BlockRole::Exit)) {
+            while parent_stack
+                .last()
+                .is_some_and(|open| open.kind == OpenKind::Indent && open.indent > line.indent)
+            {
+                parent_stack.pop();
+            }
+            // After the pops above, an `Exit` closes whichever implicit block is left.
+            let exit = line.role == Some(BlockRole::Exit);
+            if parent_stack.last().is_some_and(|open| {
+                open.kind == OpenKind::Indent && (exit || open.indent == line.indent)
+            }) && let Some(open) = parent_stack.pop()
+                && let Some(Node::Block(block)) = doc.arena.get_mut(open.id.0)
+            {
+                block.footer = Some(line.as_line_node());
+                continue;
+            }
+            if !exit && close_empty_block(&mut doc, &parent_stack, &lines[..idx], line) {
+                continue;
+            }
+        }
+
+        // Non-blank lines can close implicit blocks when indentation decreases.
         if line.trivia != TriviaKind::Blank {
-            while let Some((parent_indent, _)) = parent_stack.last().copied() {
-                if line.indent <= parent_indent {
+            while let Some(open) = parent_stack.last() {
+                if open.kind == OpenKind::Indent && line.indent <= open.indent {
                     parent_stack.pop();
                 } else {
                     break;
@@ -285,8 +564,40 @@ pub fn parse_with_dialect<D: Dialect>(input: &str, dialect: &D) -> Document {
             }
         }
 
-        let opens_block = line.trivia == TriviaKind::Content
-            && next_content_indent(&lines, idx).is_some_and(|next| next > line.indent);
+        if let Some(LiteralSlot::Open(kind_label)) = &line.literal {
+            let block = Node::Block(BlockNode {
+                header: line.as_line_node(),
+                children: Vec::new(),
+                footer: None,
+                kind_label: Some(kind_label.clone()),
+            });
+            let id = doc.insert_node(block);
+            attach_node(&mut doc, &parent_stack, id);
+            literal_block = Some(id);
+            continue;
+        }
+
+        if line.role == Some(BlockRole::Section) {
+            // A new section ends everything opened since the previous one.
+            for open in parent_stack.drain(..) {
+                if open.kind == OpenKind::Explicit {
+                    report_unclosed(&mut doc, open.id);
+                }
+            }
+        }
+
+        let (opens_block, kind) = match line.role {
+            Some(BlockRole::Open) => (true, OpenKind::Explicit),
+            Some(BlockRole::Section) => (true, OpenKind::Section),
+            Some(BlockRole::Close | BlockRole::Leaf | BlockRole::Terminator | BlockRole::Exit) => {
+                (false, OpenKind::Indent)
+            }
+            None => (
+                line.trivia == TriviaKind::Content
+                    && next_content_indent(&lines, idx).is_some_and(|next| next > line.indent),
+                OpenKind::Indent,
+            ),
+        };
 
         if opens_block {
             let block = Node::Block(BlockNode {
@@ -297,16 +608,113 @@ pub fn parse_with_dialect<D: Dialect>(input: &str, dialect: &D) -> Document {
             });
             let id = doc.insert_node(block);
             attach_node(&mut doc, &parent_stack, id);
-            parent_stack.push((line.indent, id));
+            parent_stack.push(OpenBlock {
+                indent: line.indent,
+                id,
+                kind,
+            });
         } else {
             let id = doc.insert_node(Node::Line(line.as_line_node()));
             attach_node(&mut doc, &parent_stack, id);
         }
     }
 
+    if let Some(Node::Block(block)) = literal_block.and_then(|id| doc.node(id)) {
+        doc.metadata.parse_findings.push(ParseFinding {
+            code: "unterminated-literal".to_string(),
+            message: "literal region has no closing line; remaining lines kept inside it"
+                .to_string(),
+            span: block.header.span.clone(),
+        });
+    }
+
+    for open in parent_stack
+        .into_iter()
+        .filter(|open| open.kind == OpenKind::Explicit)
+    {
+        report_unclosed(&mut doc, open.id);
+    }
+
     doc
 }
 
+fn report_unclosed(doc: &mut Document, id: NodeId) {
+    let Some(Node::Block(block)) = doc.node(id) else {
+        return;
+    };
+    let span = block.header.span.clone();
+    doc.metadata.parse_findings.push(ParseFinding {
+        code: "unclosed-block".to_string(),
+        message: "explicitly opened block has no closing line; block kept open".to_string(),
+        span,
+    });
+}
+
+/// Turn the line just before `terminator` into an empty block it closes.
+///
+/// Applies only inside an open block, when the previous content line is a
+/// plain line at the terminator's indentation and the last child attached.
+fn close_empty_block(
+    doc: &mut Document,
+    parent_stack: &[OpenBlock],
+    previous: &[LineCandidate],
+    terminator: &LineCandidate,
+) -> bool {
+    let Some(parent) = parent_stack.last() else {
+        return false;
+    };
+    let Some(prev) = previous
+        .iter()
+        .rev()
+        .find(|line| line.trivia == TriviaKind::Content)
+    else {
+        return false;
+    };
+    if prev.indent != terminator.indent || prev.indent <= parent.indent || prev.role.is_some() {
+        return false;
+    }
+
+    let Some(Node::Block(block)) = doc.arena.get(parent.id.0) else {
+        return false;
+    };
+    let Some(last) = block.children.last().copied() else {
+        return false;
+    };
+    let Some(Node::Line(header)) = doc.arena.get(last.0) else {
+        return false;
+    };
+    if header.span != prev.span {
+        return false;
+    }
+
+    let header = header.clone();
+    doc.arena[last.0] = Node::Block(BlockNode {
+        header,
+        children: Vec::new(),
+        footer: Some(terminator.as_line_node()),
+        kind_label: None,
+    });
+    true
+}
+
+#[derive(Debug, Clone, Copy)]
+struct OpenBlock {
+    indent: usize,
+    id: NodeId,
+    kind: OpenKind,
+}
+
+/// How an open block was started, which decides what can close it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OpenKind {
+    /// Opened by indentation; closed by dedent or a same-indent terminator.
+    Indent,
+    /// Opened by [`BlockRole::Open`]; closed by [`BlockRole::Close`].
+    Explicit,
+    /// Opened by [`BlockRole::Section`]; closed by the next section.
+    Section,
+}
+
 #[derive(Debug, Clone)]
 struct LineCandidate {
     raw: String,
@@ -315,9 +723,21 @@ struct LineCandidate {
     parsed: Option<ParsedLineParts>,
     key_hint: Option<String>,
     trivia: TriviaKind,
+    secrets: Vec<SecretSpan>,
+    interfaces: Vec<InterfaceName>,
+    list_hint: Option<String>,
+    role: Option<BlockRole>,
+    literal: Option<LiteralSlot>,
     indent: usize,
 }
 
+#[derive(Debug, Clone)]
+enum LiteralSlot {
+    Open(String),
+    Body,
+    Close,
+}
+
 impl LineCandidate {
     fn as_line_node(&self) -> LineNode {
         LineNode {
@@ -327,11 +747,14 @@ impl LineCandidate {
             parsed: self.parsed.clone(),
             key_hint: self.key_hint.clone(),
             trivia: self.trivia,
+            secrets: self.secrets.clone(),
+            interfaces: self.interfaces.clone(),
+            list_hint: self.list_hint.clone(),
         }
     }
 }
 
-fn collect_lines<D: Dialect>(
+fn collect_lines<D: Dialect + ?Sized>(
     input: &str,
     dialect: &D,
     line_count: &mut usize,
@@ -340,29 +763,104 @@ fn collect_lines<D: Dialect>(
     let mut out = Vec::new();
     let mut start = 0usize;
     let mut line_no = 1usize;
+    let mut open_literal: Option<LiteralEnd> = None;
+    // Indentation and index of the content lines enclosing the next line.
+    let mut enclosing: Vec<(usize, usize)> = Vec::new();
 
     while start < input.len() {
         let next_lf = input[start..].find('\n').map(|idx| start + idx);
-        let (segment, next_start) = if let Some(lf_idx) = next_lf {
+        let (segment, mut next_start) = if let Some(lf_idx) = next_lf {
             (&input[start..=lf_idx], lf_idx + 1)
         } else {
             (&input[start..], input.len())
         };
 
-        let (raw, line_ending) = split_line_ending(segment);
-        let trivia = dialect.classify_trivia(raw);
+        let (mut raw, mut line_ending) = split_line_ending(segment);
+        // Joined continuation lines keep their inner line endings in `raw`.
+        let mut physical_lines = 1usize;
+        while open_literal.is_none()
+            && next_start < input.len()
+            && dialect.continues_line(raw.rsplit('\n').next().unwrap_or(raw))
+        {
+            let next_end = input[next_start..]
+                .find('\n')
+                .map_or(input.len(), |idx| next_start + idx + 1);
+            (raw, line_ending) = split_line_ending(&input[start..next_end]);
+            next_start = next_end;
+            physical_lines += 1;
+        }
+
         let span = Span {
             line: line_no,
             start_byte: start,
             // Spans currently cover the content bytes only (not trailing newline bytes).
             end_byte: start + raw.len(),
         };
+
+        if let Some(end) = &open_literal {
+            // Literal region lines are opaque: no dialect classification or tokens.
+            let closes = end.closes(raw);
+            out.push(LineCandidate {
+                raw: raw.to_string(),
+                line_ending: line_ending.to_string(),
+                span,
+                parsed: None,
+                key_hint: None,
+                trivia: if raw.trim().is_empty() {
+                    TriviaKind::Blank
+                } else {
+                    TriviaKind::Content
+                },
+                secrets: Vec::new(),
+                interfaces: Vec::new(),
+                list_hint: None,
+                role: None,
+                literal: Some(if closes {
+                    LiteralSlot::Close
+                } else {
+                    LiteralSlot::Body
+                }),
+                indent: count_indent(raw),
+            });
+            if closes {
+                open_literal = None;
+            }
+
+            *line_count += 1;
+            line_no += 1;
+            start = next_start;
+            continue;
+        }
+
+        let trivia = dialect.classify_trivia(raw);
         let parsed = if trivia == TriviaKind::Content {
             dialect.parse_parts(raw)
         } else {
             None
         };
+        let indent = count_indent(raw);
+        if trivia == TriviaKind::Content {
+            enclosing.retain(|&(parent_indent, _)| parent_indent < indent);
+        }
+        let parents = enclosing
+            .iter()
+            .filter_map(|&(_, idx)| out[idx].parsed.as_ref())
+            .collect::<Vec<_>>();
         let key_hint = dialect.key_hint(raw, parsed.as_ref(), trivia);
+        let secrets = dialect.secret_spans(raw, parsed.as_ref(), &parents, trivia);
+        let interfaces = dialect.interface_names(raw, parsed.as_ref(), trivia);
+        let list_hint = dialect.list_hint(raw, parsed.as_ref(), trivia);
+        let role = dialect.block_role(raw, parsed.as_ref(), trivia);
+        let literal = if trivia == TriviaKind::Content {
+            dialect
+                .literal_region(raw, parsed.as_ref(), &parents, trivia)
+                .map(|region| {
+                    open_literal = Some(region.end);
+                    LiteralSlot::Open(region.kind_label)
+                })
+        } else {
+            None
+        };
 
         if has_mixed_leading_whitespace(raw) {
             parse_findings.push(ParseFinding {
@@ -373,6 +871,9 @@ fn collect_lines<D: Dialect>(
             });
         }
 
+        if trivia == TriviaKind::Content {
+            enclosing.push((indent, out.len()));
+        }
         out.push(LineCandidate {
             raw: raw.to_string(),
             line_ending: line_ending.to_string(),
@@ -380,11 +881,16 @@ fn collect_lines<D: Dialect>(
             parsed,
             key_hint,
             trivia,
-            indent: count_indent(raw),
+            secrets,
+            interfaces,
+            list_hint,
+            role,
+            literal,
+            indent,
         });
 
-        *line_count += 1;
-        line_no += 1;
+        *line_count += physical_lines;
+        line_no += physical_lines;
         start = next_start;
     }
 
@@ -394,13 +900,14 @@ fn collect_lines<D: Dialect>(
 fn next_content_indent(lines: &[LineCandidate], idx: usize) -> Option<usize> {
     lines[idx + 1..]
         .iter()
+        .filter(|line| !matches!(line.literal, Some(LiteralSlot::Body | LiteralSlot::Close)))
         .find(|line| line.trivia == TriviaKind::Content)
         .map(|line| line.indent)
 }
 
-fn attach_node(doc: &mut Document, parent_stack: &[(usize, NodeId)], id: NodeId) {
-    if let Some((_, parent_id)) = parent_stack.last() {
-        if !doc.add_child(*parent_id, id) {
+fn attach_node(doc: &mut Document, parent_stack: &[OpenBlock], id: NodeId) {
+    if let Some(parent) = parent_stack.last() {
+        if !doc.add_child(parent.id, id) {
             // If a parent cannot accept children for any reason, keep data by falling back to root.
             doc.roots.push(id);
         }
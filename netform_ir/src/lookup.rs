@@ -0,0 +1,106 @@
+//! Read-only lookup and navigation over a [`Document`] tree.
+//!
+//! Paths use the same addressing as diff output: root index first, then child
+//! indices. Footer positions (`children.len()`) do not address a node.
+
+use crate::{Document, Node, NodeId, Path};
+
+impl Document {
+    /// Resolve a [`Path`] to the node it addresses.
+    pub fn resolve(&self, path: &Path) -> Option<NodeId> {
+        let (first, rest) = path.0.split_first()?;
+        let mut current = *self.roots.get(*first)?;
+        for idx in rest {
+            match self.node(current)? {
+                Node::Block(block) => current = *block.children.get(*idx)?,
+                Node::Line(_) => return None,
+            }
+        }
+        Some(current)
+    }
+
+    /// Compute the [`Path`] of an attached node.
+    ///
+    /// Returns `None` for unknown, removed, or detached nodes.
+    pub fn path_of(&self, id: NodeId) -> Option<Path> {
+        self.node(id)?;
+        let mut rev = Vec::new();
+        let mut current = id;
+        loop {
+            let location = self.locate(current)?;
+            rev.push(location.index);
+            match location.parent {
+                Some(parent) => current = parent,
+                None => break,
+            }
+        }
+        rev.reverse();
+        Some(Path(rev))
+    }
+
+    /// Return the parent block of an attached node (`None` for roots).
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.node(id)?;
+        self.locate(id)?.parent
+    }
+
+    /// Return enclosing blocks of `id`, nearest first.
+    pub fn ancestors(&self, id: NodeId) -> Vec<NodeId> {
+        let mut out = Vec::new();
+        let mut current = id;
+        while let Some(parent) = self.parent(current) {
+            out.push(parent);
+            current = parent;
+        }
+        out
+    }
+
+    /// Return the direct children of `id` (empty for lines).
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        match self.node(id) {
+            Some(Node::Block(block)) => &block.children,
+            _ => &[],
+        }
+    }
+
+    /// Visit every attached node in render order, paired with its path.
+    pub fn walk(&self) -> Vec<(NodeId, Path)> {
+        let mut out = Vec::new();
+        for (idx, root) in self.roots.iter().copied().enumerate() {
+            self.walk_node(root, vec![idx], &mut out);
+        }
+        out
+    }
+
+    /// Find attached nodes whose line (block header for blocks) carries `key_hint`.
+    ///
+    /// Results are in render order; more than one match means the hint is ambiguous.
+    pub fn find_by_key_hint(&self, key_hint: &str) -> Vec<NodeId> {
+        self.walk()
+            .into_iter()
+            .map(|(id, _)| id)
+            .filter(|id| {
+                let line = match self.node(*id) {
+                    Some(Node::Line(line)) => line,
+                    Some(Node::Block(block)) => &block.header,
+                    None => return false,
+                };
+                line.key_hint.as_deref() == Some(key_hint)
+            })
+            .collect()
+    }
+
+    fn walk_node(&self, id: NodeId, path: Vec<usize>, out: &mut Vec<(NodeId, Path)>) {
+        let Some(node) = self.node(id) else {
+            return;
+        };
+        out.push((id, Path(path.clone())));
+        if let Node::Block(block) = node {
+            for (child_idx, child) in block.children.iter().copied().enumerate() {
+                let mut child_path = path.clone();
+                child_path.push(child_idx);
+                self.walk_node(child, child_path, out);
+            }
+        }
+    }
+}
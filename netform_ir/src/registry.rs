@@ -0,0 +1,102 @@
+//! Runtime dialect lookup by name.
+//!
+//! [`parse_with_dialect`] is generic over the dialect type, so picking a
+//! dialect from a flag or fixture field used to mean a `match` over every
+//! dialect crate. A [`DialectRegistry`] holds boxed dialects keyed by the names
+//! used on the command line (`iosxe`, `junos`, ...); dialect crates expose a
+//! `register` function that adds themselves.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::{Dialect, Document, GenericDialect};
+
+/// Dialects available by name, with `generic` always present.
+pub struct DialectRegistry {
+    dialects: BTreeMap<String, Box<dyn Dialect>>,
+}
+
+/// Error returned when a name is not registered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownDialectError {
+    /// Name that was looked up.
+    pub name: String,
+    /// Registered names, sorted.
+    pub known: Vec<String>,
+}
+
+impl fmt::Display for UnknownDialectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown dialect `{}` (known: {})",
+            self.name,
+            self.known.join(", ")
+        )
+    }
+}
+
+impl Error for UnknownDialectError {}
+
+impl DialectRegistry {
+    /// Registry holding only the built-in `generic` dialect.
+    pub fn new() -> Self {
+        let mut registry = Self {
+            dialects: BTreeMap::new(),
+        };
+        registry.register("generic", Box::new(GenericDialect));
+        registry
+    }
+
+    /// Add `dialect` under `name`, returning the dialect it replaces.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        dialect: Box<dyn Dialect>,
+    ) -> Option<Box<dyn Dialect>> {
+        self.dialects.insert(name.into(), dialect)
+    }
+
+    /// Dialect registered under `name`.
+    pub fn get(&self, name: &str) -> Option<&dyn Dialect> {
+        self.dialects.get(name).map(Box::as_ref)
+    }
+
+    /// Whether `name` is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.dialects.contains_key(name)
+    }
+
+    /// Registered names, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.dialects.keys().map(String::as_str)
+    }
+
+    /// Dialect registered under `name`, or an error listing the known names.
+    pub fn resolve(&self, name: &str) -> Result<&dyn Dialect, UnknownDialectError> {
+        self.get(name).ok_or_else(|| UnknownDialectError {
+            name: name.to_string(),
+            known: self.names().map(ToString::to_string).collect(),
+        })
+    }
+
+    /// Parse `input` with the dialect registered under `name`.
+    pub fn parse(&self, name: &str, input: &str) -> Result<Document, UnknownDialectError> {
+        Ok(self.resolve(name)?.parse_document(input))
+    }
+}
+
+impl Default for DialectRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for DialectRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DialectRegistry")
+            .field("names", &self.names().collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -0,0 +1,131 @@
+//! Secret value spans and the keyword scan shared by dialects.
+
+use serde::{Deserialize, Serialize};
+
+/// Byte range of a secret value (password, key, hash, community) in a line's
+/// `raw` text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A keyword whose following token is a secret value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecretKeyword {
+    pub keyword: &'static str,
+    /// Whether an encryption-type marker may sit between the keyword and its
+    /// value (the `7` in `password 7 ...`).
+    pub typed: bool,
+}
+
+impl SecretKeyword {
+    /// A keyword whose value may follow an encryption-type marker.
+    pub const fn typed(keyword: &'static str) -> Self {
+        Self {
+            keyword,
+            typed: true,
+        }
+    }
+
+    /// A keyword immediately followed by its value.
+    pub const fn plain(keyword: &'static str) -> Self {
+        Self {
+            keyword,
+            typed: false,
+        }
+    }
+}
+
+/// Type markers that may sit between a secret keyword and its value.
+const TYPE_MARKERS: &[&str] = &["sha256", "sha512", "scrypt", "ENC", "encrypted", "clear"];
+
+/// Locate the value following each of `keywords` in `raw`.
+///
+/// Keywords match whole whitespace-separated tokens. After a
+/// [`SecretKeyword::typed`] keyword, one encryption-type marker is skipped
+/// when another token follows it: a number of up to two digits or one of
+/// `sha256`, `sha512`, `scrypt`, `ENC`, `encrypted`, `clear`. Surrounding
+/// quotes and a trailing `;` stay outside the span.
+pub fn secret_values_after(raw: &str, keywords: &[SecretKeyword]) -> Vec<SecretSpan> {
+    let tokens = token_ranges(raw);
+    let mut out = Vec::new();
+    let mut idx = 0usize;
+    while idx < tokens.len() {
+        let (start, end) = tokens[idx];
+        idx += 1;
+        let Some(keyword) = keywords
+            .iter()
+            .find(|keyword| keyword.keyword == &raw[start..end])
+        else {
+            continue;
+        };
+        if keyword.typed
+            && tokens.get(idx).is_some_and(|&(start, end)| {
+                is_type_marker(&raw[start..end]) && idx + 1 < tokens.len()
+            })
+        {
+            idx += 1;
+        }
+        let Some(&(start, end)) = tokens.get(idx) else {
+            break;
+        };
+        idx += 1;
+        if let Some(span) = value_span(raw, start, end) {
+            out.push(span);
+        }
+    }
+    out
+}
+
+fn is_type_marker(token: &str) -> bool {
+    (token.len() <= 2 && token.bytes().all(|b| b.is_ascii_digit())) || TYPE_MARKERS.contains(&token)
+}
+
+/// Byte ranges of whitespace-separated tokens; quoted runs may hold spaces.
+fn token_ranges(raw: &str) -> Vec<(usize, usize)> {
+    let mut out = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut quote: Option<char> = None;
+    for (pos, ch) in raw.char_indices() {
+        if let Some(q) = quote {
+            if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+        match ch {
+            '"' | '\'' => {
+                start.get_or_insert(pos);
+                quote = Some(ch);
+            }
+            c if c.is_whitespace() => {
+                if let Some(token_start) = start.take() {
+                    out.push((token_start, pos));
+                }
+            }
+            _ => {
+                start.get_or_insert(pos);
+            }
+        }
+    }
+    if let Some(token_start) = start {
+        out.push((token_start, raw.len()));
+    }
+    out
+}
+
+fn value_span(raw: &str, mut start: usize, mut end: usize) -> Option<SecretSpan> {
+    if raw[start..end].ends_with(';') {
+        end -= 1;
+    }
+    let value = &raw[start..end];
+    if value.len() >= 2
+        && let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'')
+        && value.ends_with(quote)
+    {
+        start += 1;
+        end -= 1;
+    }
+    (start < end).then_some(SecretSpan { start, end })
+}
@@ -0,0 +1,355 @@
+//! Hierarchical selector language for querying a [`Document`].
+//!
+//! A selector is a chain of steps joined by combinators:
+//! - `a > b`: `b` is a direct child of a node matched by `a`
+//! - `a >> b`: `b` is any descendant of a node matched by `a`
+//! - a leading `>>` lets the first step match at any depth (roots only otherwise)
+//!
+//! Each step is a list of terms that must all match the node's line (the
+//! header for blocks):
+//! - token patterns compared as a prefix of `head` + `args`; `*` and `?` glob
+//!   within a token, a lone `*` matches one token, `**` matches any run of
+//!   tokens, and a trailing `$` requires the pattern to cover every token
+//! - `"quoted text"` matches one token literally, quotes included
+//! - `[key=glob]` matches the dialect key hint
+//! - `/regex/` searches the raw line text (`\/` escapes a slash)
+//!
+//! ```rust
+//! use netform_ir::{Selector, parse_generic};
+//!
+//! let doc = parse_generic("interface Ethernet1\n  shutdown\ninterface Ethernet2\n  no shutdown\n");
+//! let selector = Selector::parse("interface * > shutdown").expect("valid selector");
+//! let matches = doc.select(&selector);
+//! assert_eq!(matches.len(), 1);
+//! assert_eq!(matches[0].path.0, vec![0, 0]);
+//! ```
+
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::{Document, LineNode, Node, NodeId, Path};
+
+/// Parsed selector ready to run against documents.
+#[derive(Debug, Clone)]
+pub struct Selector {
+    steps: Vec<(Combinator, Step)>,
+}
+
+/// Error returned when selector text is malformed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorError {
+    /// Byte offset in the selector text where the problem was found.
+    pub position: usize,
+    pub message: String,
+}
+
+/// One node matched by a selector.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SelectorMatch {
+    pub node: NodeId,
+    pub path: Path,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    Child,
+    Descendant,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Step {
+    tokens: Vec<TokenPattern>,
+    anchored: bool,
+    key_hints: Vec<String>,
+    regexes: Vec<Regex>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenPattern {
+    Glob(String),
+    Literal(String),
+    AnyRun,
+}
+
+impl fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid selector at byte {}: {}",
+            self.position, self.message
+        )
+    }
+}
+
+impl Error for SelectorError {}
+
+impl FromStr for Selector {
+    type Err = SelectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Selector {
+    /// Parse selector text.
+    pub fn parse(input: &str) -> Result<Self, SelectorError> {
+        let err = |position: usize, message: &str| SelectorError {
+            position,
+            message: message.to_string(),
+        };
+
+        let bytes = input.as_bytes();
+        let mut steps = Vec::new();
+        let mut combinator = Combinator::Child;
+        let mut step = Step::default();
+        let mut has_terms = false;
+        let mut pos = 0usize;
+
+        while pos < bytes.len() {
+            if let Some(space) = input[pos..].chars().next().filter(|c| c.is_whitespace()) {
+                pos += space.len_utf8();
+                continue;
+            }
+            let ch = bytes[pos];
+            match ch {
+                b'>' => {
+                    let next = if bytes.get(pos + 1) == Some(&b'>') {
+                        Combinator::Descendant
+                    } else {
+                        Combinator::Child
+                    };
+                    if has_terms {
+                        steps.push((combinator, std::mem::take(&mut step)));
+                        has_terms = false;
+                    } else if !(steps.is_empty() && next == Combinator::Descendant) {
+                        return Err(err(pos, "combinator without a preceding step"));
+                    }
+                    combinator = next;
+                    pos += if next == Combinator::Descendant { 2 } else { 1 };
+                }
+                b'[' => {
+                    let end = input[pos..]
+                        .find(']')
+                        .map(|idx| pos + idx)
+                        .ok_or_else(|| err(pos, "unterminated `[`"))?;
+                    let body = &input[pos + 1..end];
+                    let hint = body
+                        .strip_prefix("key=")
+                        .ok_or_else(|| err(pos, "expected `[key=...]`"))?;
+                    step.key_hints.push(hint.to_string());
+                    has_terms = true;
+                    pos = end + 1;
+                }
+                b'/' => {
+                    let mut pattern = String::new();
+                    let mut idx = pos + 1;
+                    let mut closed = false;
+                    while idx < bytes.len() {
+                        match bytes[idx] {
+                            b'\\' if bytes.get(idx + 1) == Some(&b'/') => {
+                                pattern.push('/');
+                                idx += 2;
+                            }
+                            b'/' => {
+                                closed = true;
+                                break;
+                            }
+                            _ => {
+                                let ch = input[idx..].chars().next().expect("char boundary");
+                                pattern.push(ch);
+                                idx += ch.len_utf8();
+                            }
+                        }
+                    }
+                    if !closed {
+                        return Err(err(pos, "unterminated `/regex/`"));
+                    }
+                    let regex = Regex::new(&pattern)
+                        .map_err(|e| err(pos, &format!("invalid regex: {e}")))?;
+                    step.regexes.push(regex);
+                    has_terms = true;
+                    pos = idx + 1;
+                }
+                b'"' => {
+                    let end = input[pos + 1..]
+                        .find('"')
+                        .map(|idx| pos + 1 + idx)
+                        .ok_or_else(|| err(pos, "unterminated quoted token"))?;
+                    if step.anchored {
+                        return Err(err(pos, "`$` must be the last token of a step"));
+                    }
+                    step.tokens
+                        .push(TokenPattern::Literal(input[pos..=end].to_string()));
+                    has_terms = true;
+                    pos = end + 1;
+                }
+                _ => {
+                    let end = input[pos..]
+                        .find(|c: char| c.is_whitespace() || c == '>')
+                        .map_or(input.len(), |idx| pos + idx);
+                    let token = &input[pos..end];
+                    if token.is_empty() {
+                        return Err(err(pos, "expected a token"));
+                    }
+                    if step.anchored {
+                        return Err(err(pos, "`$` must be the last token of a step"));
+                    }
+                    match token {
+                        "$" => step.anchored = true,
+                        "**" => step.tokens.push(TokenPattern::AnyRun),
+                        _ => step.tokens.push(TokenPattern::Glob(token.to_string())),
+                    }
+                    has_terms = true;
+                    pos = end;
+                }
+            }
+        }
+
+        if !has_terms {
+            return Err(err(input.len(), "selector must end with a step"));
+        }
+        steps.push((combinator, step));
+        Ok(Self { steps })
+    }
+}
+
+impl Document {
+    /// Return attached nodes matched by `selector`, in render order.
+    pub fn select(&self, selector: &Selector) -> Vec<SelectorMatch> {
+        let order = self.walk();
+        let rank = order
+            .iter()
+            .enumerate()
+            .map(|(idx, (id, _))| (*id, idx))
+            .collect::<HashMap<_, _>>();
+
+        let mut contexts: Option<BTreeSet<usize>> = None;
+        for (combinator, step) in &selector.steps {
+            let candidates = match (&contexts, combinator) {
+                (None, Combinator::Child) => self.roots.clone(),
+                (None, Combinator::Descendant) => order.iter().map(|(id, _)| *id).collect(),
+                (Some(ctx), Combinator::Child) => ctx
+                    .iter()
+                    .flat_map(|idx| self.children(order[*idx].0).iter().copied())
+                    .collect(),
+                (Some(ctx), Combinator::Descendant) => ctx
+                    .iter()
+                    .flat_map(|idx| self.descendants(order[*idx].0))
+                    .collect(),
+            };
+
+            contexts = Some(
+                candidates
+                    .into_iter()
+                    .filter(|id| self.node(*id).is_some_and(|node| step.matches(node)))
+                    .filter_map(|id| rank.get(&id).copied())
+                    .collect(),
+            );
+        }
+
+        contexts
+            .unwrap_or_default()
+            .into_iter()
+            .map(|idx| SelectorMatch {
+                node: order[idx].0,
+                path: order[idx].1.clone(),
+            })
+            .collect()
+    }
+
+    fn descendants(&self, id: NodeId) -> Vec<NodeId> {
+        let mut out = Vec::new();
+        let mut stack = self.children(id).iter().rev().copied().collect::<Vec<_>>();
+        while let Some(next) = stack.pop() {
+            out.push(next);
+            stack.extend(self.children(next).iter().rev().copied());
+        }
+        out
+    }
+}
+
+impl Step {
+    fn matches(&self, node: &Node) -> bool {
+        let line = match node {
+            Node::Line(line) => line,
+            Node::Block(block) => &block.header,
+        };
+        self.matches_tokens(line)
+            && self.key_hints.iter().all(|pattern| {
+                line.key_hint
+                    .as_deref()
+                    .is_some_and(|hint| glob_match(pattern, hint))
+            })
+            && self.regexes.iter().all(|regex| regex.is_match(&line.raw))
+    }
+
+    fn matches_tokens(&self, line: &LineNode) -> bool {
+        if self.tokens.is_empty() && !self.anchored {
+            return true;
+        }
+        let Some(parsed) = &line.parsed else {
+            return false;
+        };
+        let tokens = std::iter::once(parsed.head.as_str())
+            .chain(parsed.args.iter().map(String::as_str))
+            .collect::<Vec<_>>();
+        match_tokens(&self.tokens, &tokens, self.anchored)
+    }
+}
+
+fn match_tokens(patterns: &[TokenPattern], tokens: &[&str], anchored: bool) -> bool {
+    let Some((first, rest)) = patterns.split_first() else {
+        return !anchored || tokens.is_empty();
+    };
+    match first {
+        TokenPattern::AnyRun => {
+            (0..=tokens.len()).any(|skip| match_tokens(rest, &tokens[skip..], anchored))
+        }
+        TokenPattern::Glob(pattern) => tokens.split_first().is_some_and(|(token, tail)| {
+            glob_match(pattern, token) && match_tokens(rest, tail, anchored)
+        }),
+        TokenPattern::Literal(text) => tokens
+            .split_first()
+            .is_some_and(|(token, tail)| *token == text && match_tokens(rest, tail, anchored)),
+    }
+}
+
+/// Match `text` against a glob where `*` spans any run and `?` one character.
+///
+/// ```rust
+/// use netform_ir::glob_match;
+///
+/// assert!(glob_match("Gi*", "GigabitEthernet0/1"));
+/// assert!(glob_match("edge-?.cfg", "edge-1.cfg"));
+/// assert!(!glob_match("edge-?.cfg", "edge-10.cfg"));
+/// ```
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    let (mut p, mut t) = (0usize, 0usize);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|ch| *ch == '*')
+}
@@ -0,0 +1,116 @@
+use netform_ir::{LOW_CONFIDENCE, detect_dialect, detect_dialect_in};
+
+fn best(input: &str) -> String {
+    detect_dialect(input)
+        .best()
+        .expect("detection always ranks a guess")
+        .name
+        .clone()
+}
+
+#[test]
+fn detects_vendor_headers_and_structure() {
+    let cases = [
+        (
+            "Building configuration...\n\nCurrent configuration : 1234 bytes\n!\nhostname r1\n!\ninterface GigabitEthernet1\n ip address 10.0.0.1 255.255.255.0\n!\nend\n",
+            "iosxe",
+        ),
+        (
+            "!Command: show running-config\n!Time: Thu Oct  1 10:00:00 2026\n\nfeature lacp\nfeature bgp\n\nvrf context TENANT-A\n",
+            "nxos",
+        ),
+        (
+            "! Command: show running-config\n! device: leaf-01 (DCS-7050SX3, EOS-4.30.1F)\n!\nhostname leaf-01\n!\n",
+            "eos",
+        ),
+        (
+            "!! IOS XR Configuration 7.9.2\n!\nhostname pe-1\n!\nroute-policy PASS\n  pass\nend-policy\n!\n",
+            "iosxr",
+        ),
+        (
+            "## Last commit: 2026-10-01 10:00:00 UTC by admin\nsystem {\n    host-name r1;\n}\n",
+            "junos",
+        ),
+        (
+            "set system host-name r1\nset interfaces ge-0/0/0 unit 0 family inet address 10.0.0.1/31\n",
+            "junos",
+        ),
+        (
+            "#config-version=FGT60F-7.2.5-FW-build1517-230606:opmode=0\nconfig system global\n    set hostname \"fw-01\"\nend\n",
+            "fortios",
+        ),
+        (
+            "# 2026-10-01 10:00:00 by RouterOS 7.15.3\n/interface bridge\nadd name=bridge-lan\n/ip address\nadd address=192.168.88.1/24 interface=bridge-lan\n",
+            "routeros",
+        ),
+        (
+            "# TiMOS-B-23.10.R1 both/x86_64 Nokia 7750 SR\nconfigure\n    system\n    exit\nexit all\n",
+            "sros",
+        ),
+        (
+            "configure {\n    router \"Base\" {\n        autonomous-system 65000\n    }\n}\n",
+            "sros",
+        ),
+        (
+            "!Software Version V800R021C10SPC100\n#\nsysname PE-1\n#\nreturn\n",
+            "vrp",
+        ),
+        (
+            ": Saved\n:\nASA Version 9.18(3)\n!\ninterface GigabitEthernet0/0\n nameif outside\n",
+            "asa",
+        ),
+    ];
+
+    for (input, expected) in cases {
+        let detection = detect_dialect(input);
+        assert_eq!(best(input), expected, "ranking: {:?}", detection.ranked);
+        assert!(detection.is_confident(), "ranking: {:?}", detection.ranked);
+    }
+}
+
+#[test]
+fn unknown_text_ranks_generic_with_zero_confidence() {
+    let detection = detect_dialect("hostname r1\nip domain-name example.net\n");
+    assert_eq!(detection.ranked.len(), 1);
+    assert_eq!(best("hostname r1\n"), "generic");
+    assert_eq!(detection.ranked[0].confidence, 0.0);
+    assert!(!detection.is_confident());
+}
+
+#[test]
+fn weak_or_shared_signals_stay_below_low_confidence() {
+    // A lone `!` is shared by the whole ios family.
+    let detection = detect_dialect("hostname r1\n!\n");
+    assert_eq!(best("hostname r1\n!\n"), "iosxe");
+    assert!(detection.best().expect("guess").confidence < LOW_CONFIDENCE);
+    assert!(detection.ranked.len() > 1);
+}
+
+#[test]
+fn detection_only_scores_the_leading_sample() {
+    let input = "hostname r1\n#config-version=FGT60F-7.2.5\n";
+    assert_eq!(
+        detect_dialect_in(input, 1).best().expect("guess").name,
+        "generic"
+    );
+    assert_eq!(
+        detect_dialect_in(input, 2).best().expect("guess").name,
+        "fortios"
+    );
+}
+
+#[test]
+fn detection_is_deterministic() {
+    let input = "!\n}\n";
+    assert_eq!(detect_dialect(input), detect_dialect(input));
+    let names = detect_dialect(input)
+        .ranked
+        .into_iter()
+        .map(|guess| guess.name)
+        .collect::<Vec<_>>();
+    // Equal scores rank by name.
+    assert_eq!(
+        names,
+        ["iosxe", "asa", "eos", "iosxr", "junos", "nxos", "sros"]
+    );
+}
@@ -0,0 +1,181 @@
+use netform_ir::{
+    BlockNode, EditError, GenericDialect, LineNode, Node, NodeId, Path, parse_generic,
+};
+
+const SAMPLE: &str = "interface Ethernet1\n  description old\n  mtu 9000\nip access-list extended ACL-IN\n  10 permit ip any any\n  20 deny ip any any\nhostname edge-1\n";
+
+fn child(doc: &netform_ir::Document, parent: NodeId, idx: usize) -> NodeId {
+    match doc.node(parent) {
+        Some(Node::Block(block)) => block.children[idx],
+        _ => panic!("expected block"),
+    }
+}
+
+#[test]
+fn replace_line_swaps_text_and_keeps_structure() {
+    let mut doc = parse_generic(SAMPLE);
+    let description = child(&doc, doc.roots[0], 0);
+
+    let old = doc
+        .replace_line(
+            description,
+            LineNode::from_dialect("  description uplink", &GenericDialect),
+        )
+        .expect("replace description");
+    assert_eq!(old.raw, "  description old");
+
+    doc.recompute_spans();
+    assert_eq!(
+        doc.render(),
+        SAMPLE.replace("description old", "description uplink")
+    );
+    assert_eq!(doc.metadata.original_bytes, doc.render().len());
+}
+
+#[test]
+fn remove_detaches_and_tombstones_subtree() {
+    let mut doc = parse_generic(SAMPLE);
+    let acl = doc.roots[1];
+    let entry = child(&doc, acl, 1);
+
+    doc.remove(entry).expect("remove acl entry");
+    assert!(doc.is_removed(entry));
+    assert!(doc.node(entry).is_none());
+    assert!(!doc.render().contains("20 deny"));
+
+    doc.remove(acl).expect("remove acl block");
+    let first_entry = NodeId(entry.0 - 1);
+    assert!(doc.is_removed(first_entry));
+    assert_eq!(doc.roots.len(), 2);
+    assert_eq!(doc.remove(acl), Err(EditError::RemovedNode(acl)));
+}
+
+#[test]
+fn insert_at_path_and_recompute_spans() {
+    let mut doc = parse_generic(SAMPLE);
+    let id = doc
+        .insert_at(
+            &Path(vec![0, 1]),
+            Node::Line(LineNode::from_dialect("  no shutdown", &GenericDialect)),
+        )
+        .expect("insert child line");
+
+    doc.recompute_spans();
+    let rendered = doc.render();
+    assert!(
+        rendered.starts_with("interface Ethernet1\n  description old\n  no shutdown\n  mtu 9000\n")
+    );
+
+    let Some(Node::Line(line)) = doc.node(id) else {
+        panic!("expected inserted line");
+    };
+    assert_eq!(line.span.line, 3);
+    assert_eq!(
+        &rendered[line.span.start_byte..line.span.end_byte],
+        "  no shutdown"
+    );
+    assert_eq!(doc.metadata.line_count, 8);
+
+    assert_eq!(
+        doc.insert_at(
+            &Path(vec![2, 0]),
+            Node::Line(LineNode::from_dialect("x", &GenericDialect))
+        ),
+        Err(EditError::NotABlock(doc.roots[2]))
+    );
+    assert!(matches!(
+        doc.insert_at(
+            &Path(vec![9]),
+            Node::Line(LineNode::from_dialect("x", &GenericDialect))
+        ),
+        Err(EditError::InvalidPath(_))
+    ));
+}
+
+#[test]
+fn move_to_relocates_nodes_and_restores_on_failure() {
+    let mut doc = parse_generic(SAMPLE);
+    let hostname = doc.roots[2];
+    let interface = doc.roots[0];
+
+    doc.move_to(hostname, &Path(vec![0]))
+        .expect("move to front");
+    assert!(
+        doc.render()
+            .starts_with("hostname edge-1\ninterface Ethernet1\n")
+    );
+
+    let mtu = child(&doc, interface, 1);
+    doc.move_to(mtu, &Path(vec![1, 0]))
+        .expect("reorder within block");
+    assert!(
+        doc.render()
+            .contains("interface Ethernet1\n  mtu 9000\n  description old\n")
+    );
+
+    let before = doc.render();
+    assert_eq!(
+        doc.move_to(interface, &Path(vec![5])),
+        Err(EditError::InvalidPath(Path(vec![5])))
+    );
+    assert_eq!(doc.render(), before);
+}
+
+#[test]
+fn replace_keeps_reused_children_and_tombstones_the_rest() {
+    let mut doc = parse_generic(SAMPLE);
+    let acl = doc.roots[1];
+    let keep = child(&doc, acl, 0);
+    let dropped = child(&doc, acl, 1);
+
+    doc.replace(
+        acl,
+        Node::Block(BlockNode {
+            header: LineNode::from_dialect("ip access-list extended ACL-EDGE", &GenericDialect),
+            children: vec![keep],
+            footer: None,
+            kind_label: None,
+        }),
+    )
+    .expect("replace acl block");
+
+    assert!(doc.is_removed(dropped));
+    assert!(!doc.is_removed(keep));
+    assert!(
+        doc.render()
+            .contains("ip access-list extended ACL-EDGE\n  10 permit ip any any\nhostname")
+    );
+
+    let description = child(&doc, doc.roots[0], 0);
+    assert_eq!(
+        doc.replace(
+            acl,
+            Node::Block(BlockNode {
+                header: LineNode::from_dialect("x", &GenericDialect),
+                children: vec![description],
+                footer: None,
+                kind_label: None,
+            }),
+        ),
+        Err(EditError::AlreadyAttached(description))
+    );
+}
+
+#[test]
+fn reorder_children_requires_permutation() {
+    let mut doc = parse_generic(SAMPLE);
+    let roots = doc.roots.clone();
+
+    doc.reorder_children(None, &[roots[2], roots[0], roots[1]])
+        .expect("reorder roots");
+    assert!(doc.render().starts_with("hostname edge-1\n"));
+
+    assert_eq!(
+        doc.reorder_children(None, &[roots[0], roots[1]]),
+        Err(EditError::InvalidOrder)
+    );
+    assert_eq!(
+        doc.reorder_children(Some(roots[2]), &[]),
+        Err(EditError::NotABlock(roots[2]))
+    );
+}
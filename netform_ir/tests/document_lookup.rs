@@ -0,0 +1,88 @@
+use netform_ir::{Dialect, Node, NodeId, ParsedLineParts, Path, TriviaKind, parse_with_dialect};
+
+struct HintDialect;
+
+impl Dialect for HintDialect {
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        if raw.trim().is_empty() {
+            TriviaKind::Blank
+        } else {
+            TriviaKind::Content
+        }
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        let mut tokens = raw.split_whitespace().map(ToString::to_string);
+        Some(ParsedLineParts {
+            head: tokens.next()?,
+            args: tokens.collect(),
+        })
+    }
+
+    fn key_hint(
+        &self,
+        _raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        _trivia: TriviaKind,
+    ) -> Option<String> {
+        let parsed = parsed?;
+        match (parsed.head.as_str(), parsed.args.as_slice()) {
+            ("interface", [name, ..]) => Some(format!("interface:{name}")),
+            ("router", [proto, asn, ..]) => Some(format!("router:{proto}:{asn}")),
+            _ => None,
+        }
+    }
+}
+
+const SAMPLE: &str = "interface Ethernet1\n  description uplink\nrouter bgp 65000\n  address-family ipv4\n    neighbor 10.0.0.1 activate\ninterface Ethernet1\n  mtu 9000\n";
+
+#[test]
+fn resolve_and_path_of_are_inverse() {
+    let doc = parse_with_dialect(SAMPLE, &HintDialect);
+
+    for (id, path) in doc.walk() {
+        assert_eq!(doc.resolve(&path), Some(id));
+        assert_eq!(doc.path_of(id), Some(path));
+    }
+
+    let neighbor = doc.resolve(&Path(vec![1, 0, 0])).expect("neighbor line");
+    let Some(Node::Line(line)) = doc.node(neighbor) else {
+        panic!("expected neighbor line");
+    };
+    assert_eq!(line.raw, "    neighbor 10.0.0.1 activate");
+
+    assert_eq!(doc.resolve(&Path(vec![])), None);
+    assert_eq!(doc.resolve(&Path(vec![0, 5])), None);
+    assert_eq!(doc.resolve(&Path(vec![0, 0, 0])), None);
+    assert_eq!(doc.path_of(NodeId(999)), None);
+}
+
+#[test]
+fn find_by_key_hint_returns_all_matches_in_order() {
+    let doc = parse_with_dialect(SAMPLE, &HintDialect);
+
+    assert_eq!(doc.find_by_key_hint("router:bgp:65000"), vec![doc.roots[1]]);
+    assert_eq!(
+        doc.find_by_key_hint("interface:Ethernet1"),
+        vec![doc.roots[0], doc.roots[2]]
+    );
+    assert!(doc.find_by_key_hint("interface:Ethernet9").is_empty());
+}
+
+#[test]
+fn parent_and_ancestors_navigate_upwards() {
+    let mut doc = parse_with_dialect(SAMPLE, &HintDialect);
+    let router = doc.roots[1];
+    let family = doc.children(router)[0];
+    let neighbor = doc.children(family)[0];
+
+    assert_eq!(doc.parent(neighbor), Some(family));
+    assert_eq!(doc.parent(router), None);
+    assert_eq!(doc.ancestors(neighbor), vec![family, router]);
+    assert!(doc.children(neighbor).is_empty());
+
+    doc.remove(family).expect("remove address-family");
+    assert_eq!(doc.parent(neighbor), None);
+    assert_eq!(doc.path_of(neighbor), None);
+    assert!(doc.ancestors(neighbor).is_empty());
+}
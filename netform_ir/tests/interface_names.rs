@@ -0,0 +1,93 @@
+use netform_ir::{
+    Dialect, InterfaceName, Node, ParsedLineParts, TriviaKind, canonical_interface_name,
+    interface_names_in, parse_with_dialect,
+};
+
+const TYPES: &[(&str, &str)] = &[
+    ("GigabitEthernet", "Gi"),
+    ("TenGigabitEthernet", "Te"),
+    ("TwentyFiveGigE", "Twe"),
+    ("TwoGigabitEthernet", "Tw"),
+    ("Port-channel", "Po"),
+];
+
+#[test]
+fn canonical_names_expand_prefixes_case_insensitively() {
+    let expand = |name| canonical_interface_name(name, TYPES);
+    assert_eq!(expand("Gi0/1").as_deref(), Some("GigabitEthernet0/1"));
+    assert_eq!(
+        expand("gig0/1.100").as_deref(),
+        Some("GigabitEthernet0/1.100")
+    );
+    assert_eq!(
+        expand("Te1/1/1").as_deref(),
+        Some("TenGigabitEthernet1/1/1")
+    );
+    assert_eq!(expand("Twe1/0/1").as_deref(), Some("TwentyFiveGigE1/0/1"));
+    assert_eq!(
+        expand("Tw1/0/1").as_deref(),
+        Some("TwoGigabitEthernet1/0/1")
+    );
+    assert_eq!(expand("po10").as_deref(), Some("Port-channel10"));
+
+    // Already canonical, shorter than the shortest abbreviation, or not a name.
+    assert_eq!(expand("GigabitEthernet0/1"), None);
+    assert_eq!(expand("G0/1"), None);
+    assert_eq!(expand("Gi0/1-4"), None);
+    assert_eq!(expand("10.0.0.1"), None);
+    assert_eq!(expand("edge-1"), None);
+}
+
+#[test]
+fn interface_names_are_located_by_token() {
+    let raw = "ip route 0.0.0.0 0.0.0.0 Gi0/1 10.0.0.1";
+    assert_eq!(
+        interface_names_in(raw, TYPES),
+        vec![InterfaceName {
+            start: 25,
+            end: 30,
+            canonical: "GigabitEthernet0/1".to_string(),
+        }]
+    );
+}
+
+struct AbbrevDialect;
+
+impl Dialect for AbbrevDialect {
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        netform_ir::GenericDialect.classify_trivia(raw)
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        netform_ir::GenericDialect.parse_parts(raw)
+    }
+
+    fn interface_names(
+        &self,
+        raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        trivia: TriviaKind,
+    ) -> Vec<InterfaceName> {
+        if trivia != TriviaKind::Content {
+            return Vec::new();
+        }
+        interface_names_in(raw, TYPES)
+    }
+}
+
+#[test]
+fn parser_records_interface_names_on_lines() {
+    let input = "interface Po10\n channel-group 10\n";
+    let doc = parse_with_dialect(input, &AbbrevDialect);
+    assert_eq!(doc.render(), input);
+
+    let Some(Node::Block(block)) = doc.node(doc.roots[0]) else {
+        panic!("expected interface block");
+    };
+    assert_eq!(block.header.interfaces.len(), 1);
+    assert_eq!(block.header.interfaces[0].canonical, "Port-channel10");
+    let Some(Node::Line(child)) = doc.node(block.children[0]) else {
+        panic!("expected child line");
+    };
+    assert!(child.interfaces.is_empty());
+}
@@ -1,4 +1,35 @@
-use netform_ir::{Node, TriviaKind, parse_generic};
+use netform_ir::{
+    BlockRole, Dialect, GenericDialect, LiteralEnd, LiteralRegion, Node, ParsedLineParts,
+    TriviaKind, parse_generic, parse_with_dialect,
+};
+
+struct BraceDialect;
+
+impl Dialect for BraceDialect {
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        GenericDialect.classify_trivia(raw)
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        GenericDialect.parse_parts(raw)
+    }
+
+    fn block_role(
+        &self,
+        raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        _trivia: TriviaKind,
+    ) -> Option<BlockRole> {
+        let trimmed = raw.trim();
+        if trimmed.ends_with('{') {
+            Some(BlockRole::Open)
+        } else if trimmed == "}" {
+            Some(BlockRole::Close)
+        } else {
+            None
+        }
+    }
+}
 
 #[test]
 fn builds_blocks_from_indentation() {
@@ -103,3 +134,262 @@ fn spans_are_present_for_all_lines() {
 
     assert_eq!(line_count, doc.metadata.line_count);
 }
+
+#[test]
+fn explicit_block_roles_override_indentation_and_fill_footer() {
+    let input = "outer {\nnested\n  child\n}\ntail\n";
+    let doc = parse_with_dialect(input, &BraceDialect);
+
+    assert_eq!(doc.render(), input);
+    assert_eq!(doc.roots.len(), 2);
+    match doc.node(doc.roots[0]).expect("root 0") {
+        Node::Block(block) => {
+            assert_eq!(block.header.raw, "outer {");
+            assert_eq!(block.children.len(), 1);
+            assert_eq!(block.footer.as_ref().map(|f| f.raw.as_str()), Some("}"));
+            // Indentation still nests lines within the explicit block.
+            assert!(matches!(
+                doc.node(block.children[0]),
+                Some(Node::Block(inner)) if inner.header.raw == "nested"
+            ));
+        }
+        _ => panic!("expected first root to be a block"),
+    }
+    assert!(doc.metadata.parse_findings.is_empty());
+}
+
+struct LiteralDialect;
+
+impl Dialect for LiteralDialect {
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        GenericDialect.classify_trivia(raw)
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        GenericDialect.parse_parts(raw)
+    }
+
+    fn literal_region(
+        &self,
+        raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        _parents: &[&ParsedLineParts],
+        _trivia: TriviaKind,
+    ) -> Option<LiteralRegion> {
+        raw.starts_with("text").then(|| LiteralRegion {
+            kind_label: "text".to_string(),
+            end: LiteralEnd::Marker("END".to_string()),
+        })
+    }
+}
+
+#[test]
+fn literal_regions_keep_lines_opaque_inside_one_block() {
+    let input = "text\n! not a comment\n  indented\nEND\nhostname edge-1\n";
+    let doc = parse_with_dialect(input, &LiteralDialect);
+
+    assert_eq!(doc.render(), input);
+    assert_eq!(doc.roots.len(), 2);
+    match doc.node(doc.roots[0]).expect("root 0") {
+        Node::Block(block) => {
+            assert_eq!(block.kind_label.as_deref(), Some("text"));
+            assert_eq!(block.children.len(), 2);
+            assert_eq!(block.footer.as_ref().map(|f| f.raw.as_str()), Some("END"));
+            for child in &block.children {
+                let Some(Node::Line(line)) = doc.node(*child) else {
+                    panic!("expected literal line");
+                };
+                assert_eq!(line.trivia, TriviaKind::Content);
+                assert!(line.parsed.is_none());
+            }
+        }
+        _ => panic!("expected first root to be a block"),
+    }
+    assert!(doc.metadata.parse_findings.is_empty());
+
+    let doc = parse_with_dialect("text\nno end\n", &LiteralDialect);
+    assert_eq!(doc.roots.len(), 1);
+    assert!(
+        doc.metadata
+            .parse_findings
+            .iter()
+            .any(|f| f.code == "unterminated-literal")
+    );
+}
+
+struct TerminatorDialect;
+
+impl Dialect for TerminatorDialect {
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        GenericDialect.classify_trivia(raw)
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        GenericDialect.parse_parts(raw)
+    }
+
+    fn block_role(
+        &self,
+        raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        _trivia: TriviaKind,
+    ) -> Option<BlockRole> {
+        (raw.trim() == "!").then_some(BlockRole::Terminator)
+    }
+}
+
+#[test]
+fn terminator_closes_same_indent_block_or_stays_a_line() {
+    let input = "outer\n inner\n  leaf\n !\n sibling\n!\n!\n";
+    let doc = parse_with_dialect(input, &TerminatorDialect);
+
+    assert_eq!(doc.render(), input);
+    assert_eq!(doc.roots.len(), 2);
+    match doc.node(doc.roots[0]).expect("root 0") {
+        Node::Block(block) => {
+            assert_eq!(block.footer.as_ref().map(|f| f.raw.as_str()), Some("!"));
+            assert_eq!(block.children.len(), 2);
+            assert!(matches!(
+                doc.node(block.children[0]),
+                Some(Node::Block(inner)) if inner.footer.as_ref().is_some_and(|f| f.raw == " !")
+            ));
+        }
+        _ => panic!("expected first root to be a block"),
+    }
+    // The second `!` has no open block at its indentation and stays a root line.
+    assert!(matches!(doc.node(doc.roots[1]), Some(Node::Line(line)) if line.raw == "!"));
+    assert!(doc.metadata.parse_findings.is_empty());
+}
+
+#[test]
+fn terminator_after_same_indent_line_closes_an_empty_block() {
+    let input = "outer\n empty\n !\n inner\n  leaf\n !\n!\n";
+    let doc = parse_with_dialect(input, &TerminatorDialect);
+
+    assert_eq!(doc.render(), input);
+    let Some(Node::Block(outer)) = doc.node(doc.roots[0]) else {
+        panic!("expected outer block");
+    };
+    assert_eq!(outer.children.len(), 2);
+    assert!(matches!(
+        doc.node(outer.children[0]),
+        Some(Node::Block(empty)) if empty.children.is_empty()
+            && empty.footer.as_ref().is_some_and(|f| f.raw == " !")
+    ));
+    assert_eq!(outer.footer.as_ref().map(|f| f.raw.as_str()), Some("!"));
+}
+
+struct SectionDialect;
+
+impl Dialect for SectionDialect {
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        GenericDialect.classify_trivia(raw)
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        GenericDialect.parse_parts(raw)
+    }
+
+    fn block_role(
+        &self,
+        raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        _trivia: TriviaKind,
+    ) -> Option<BlockRole> {
+        raw.starts_with('/').then_some(BlockRole::Section)
+    }
+
+    fn continues_line(&self, raw: &str) -> bool {
+        raw.trim_end().ends_with('\\')
+    }
+}
+
+#[test]
+fn sections_hold_lines_until_the_next_section() {
+    let input = "# export\n/ip address\nadd address=10.0.0.1/24 \\\n    interface=ether1\nadd address=10.0.1.1/24\n/interface bridge\nadd name=br0\n";
+    let doc = parse_with_dialect(input, &SectionDialect);
+
+    assert_eq!(doc.render(), input);
+    assert!(doc.metadata.parse_findings.is_empty());
+    assert_eq!(doc.roots.len(), 3);
+
+    let Some(Node::Block(address)) = doc.node(doc.roots[1]) else {
+        panic!("expected section block");
+    };
+    assert_eq!(address.header.raw, "/ip address");
+    assert!(address.footer.is_none());
+    assert_eq!(address.children.len(), 2);
+
+    // The continued entry is one line whose raw keeps the inner newline.
+    let Some(Node::Line(first)) = doc.node(address.children[0]) else {
+        panic!("expected joined entry line");
+    };
+    assert_eq!(
+        first.raw,
+        "add address=10.0.0.1/24 \\\n    interface=ether1"
+    );
+    assert_eq!(first.span.line, 3);
+    let Some(Node::Line(second)) = doc.node(address.children[1]) else {
+        panic!("expected entry line");
+    };
+    assert_eq!(second.span.line, 5);
+
+    assert!(matches!(
+        doc.node(doc.roots[2]),
+        Some(Node::Block(bridge)) if bridge.children.len() == 1
+    ));
+}
+
+struct ExitDialect;
+
+impl Dialect for ExitDialect {
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        GenericDialect.classify_trivia(raw)
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        GenericDialect.parse_parts(raw)
+    }
+
+    fn block_role(
+        &self,
+        raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        _trivia: TriviaKind,
+    ) -> Option<BlockRole> {
+        (raw.trim() == "quit").then_some(BlockRole::Exit)
+    }
+}
+
+#[test]
+fn exit_closes_the_enclosing_block_at_child_or_header_indent() {
+    let input = "bgp\n family\n  peer\n  quit\n quit\nisis\n level\n  metric\n quit\nquit\nquit\n";
+    let doc = parse_with_dialect(input, &ExitDialect);
+
+    assert_eq!(doc.render(), input);
+    assert!(doc.metadata.parse_findings.is_empty());
+    assert_eq!(doc.roots.len(), 3);
+
+    // Child-indented `quit` lines close the block they sit in.
+    let Some(Node::Block(bgp)) = doc.node(doc.roots[0]) else {
+        panic!("expected bgp block");
+    };
+    assert_eq!(bgp.footer.as_ref().map(|f| f.raw.as_str()), Some(" quit"));
+    assert!(matches!(
+        doc.node(bgp.children[0]),
+        Some(Node::Block(family)) if family.footer.as_ref().is_some_and(|f| f.raw == "  quit")
+    ));
+
+    // Header-indented `quit` lines close the block opened at that indentation.
+    let Some(Node::Block(isis)) = doc.node(doc.roots[1]) else {
+        panic!("expected isis block");
+    };
+    assert_eq!(isis.footer.as_ref().map(|f| f.raw.as_str()), Some("quit"));
+    assert!(matches!(
+        doc.node(isis.children[0]),
+        Some(Node::Block(level)) if level.footer.as_ref().is_some_and(|f| f.raw == " quit")
+    ));
+
+    // Nothing is left open for the last `quit`.
+    assert!(matches!(doc.node(doc.roots[2]), Some(Node::Line(line)) if line.raw == "quit"));
+}
@@ -0,0 +1,91 @@
+use netform_ir::{
+    Dialect, DialectHint, DialectRegistry, ParsedLineParts, TriviaKind, UnknownDialectError,
+    parse_generic,
+};
+
+struct HashCommentDialect;
+
+impl Dialect for HashCommentDialect {
+    fn dialect_hint(&self) -> DialectHint {
+        DialectHint::Named("hash".to_string())
+    }
+
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        if raw.trim().is_empty() {
+            TriviaKind::Blank
+        } else if raw.trim_start().starts_with('#') {
+            TriviaKind::Comment
+        } else {
+            TriviaKind::Content
+        }
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        let mut tokens = raw.split_whitespace().map(ToString::to_string);
+        Some(ParsedLineParts {
+            head: tokens.next()?,
+            args: tokens.collect(),
+        })
+    }
+}
+
+#[test]
+fn new_registry_holds_generic() {
+    let registry = DialectRegistry::new();
+    assert_eq!(registry.names().collect::<Vec<_>>(), ["generic"]);
+
+    let input = "interface Ethernet1\n  description uplink\n";
+    let doc = registry
+        .parse("generic", input)
+        .expect("generic is built in");
+    assert_eq!(doc, parse_generic(input));
+}
+
+#[test]
+fn registered_dialects_resolve_by_name() {
+    let mut registry = DialectRegistry::new();
+    assert!(
+        registry
+            .register("hash", Box::new(HashCommentDialect))
+            .is_none()
+    );
+    assert!(registry.contains("hash"));
+    assert_eq!(registry.names().collect::<Vec<_>>(), ["generic", "hash"]);
+
+    let doc = registry
+        .parse("hash", "# managed\nhostname r1\n")
+        .expect("hash is registered");
+    assert_eq!(doc.metadata.dialect_hint, DialectHint::Named("hash".into()));
+    assert_eq!(
+        registry
+            .get("hash")
+            .expect("registered")
+            .classify_trivia("# x"),
+        TriviaKind::Comment
+    );
+
+    // Registering a name again replaces the previous dialect.
+    assert!(
+        registry
+            .register("generic", Box::new(HashCommentDialect))
+            .is_some()
+    );
+    let doc = registry.parse("generic", "hostname r1\n").expect("generic");
+    assert_eq!(doc.metadata.dialect_hint, DialectHint::Named("hash".into()));
+}
+
+#[test]
+fn unknown_names_list_the_registered_dialects() {
+    let registry = DialectRegistry::new();
+    let err = registry
+        .parse("junos", "system {\n}\n")
+        .expect_err("unknown");
+    assert_eq!(
+        err,
+        UnknownDialectError {
+            name: "junos".to_string(),
+            known: vec!["generic".to_string()],
+        }
+    );
+    assert_eq!(err.to_string(), "unknown dialect `junos` (known: generic)");
+}
@@ -0,0 +1,99 @@
+use netform_ir::{
+    Dialect, Node, ParsedLineParts, SecretKeyword, SecretSpan, TriviaKind, parse_with_dialect,
+    secret_values_after,
+};
+
+fn values<'a>(raw: &'a str, keywords: &[SecretKeyword]) -> Vec<&'a str> {
+    secret_values_after(raw, keywords)
+        .into_iter()
+        .map(|span| &raw[span.start..span.end])
+        .collect()
+}
+
+#[test]
+fn secret_values_skip_type_markers_quotes_and_terminators() {
+    let keywords = [
+        SecretKeyword::typed("secret"),
+        SecretKeyword::typed("password"),
+        SecretKeyword::plain("encrypted-password"),
+        SecretKeyword::plain("community"),
+    ];
+    assert_eq!(
+        values("enable secret 9 $9$abc$def", &keywords),
+        vec!["$9$abc$def"]
+    );
+    assert_eq!(
+        values(" neighbor 10.0.0.1 password 7 0822455D0A16", &keywords),
+        vec!["0822455D0A16"]
+    );
+    assert_eq!(
+        values("username admin secret sha512 $6$salt$hash", &keywords),
+        vec!["$6$salt$hash"]
+    );
+    assert_eq!(
+        values("snmp-server community public RO", &keywords),
+        vec!["public"]
+    );
+    assert_eq!(
+        values("    encrypted-password \"$6$salt$hash\";", &keywords),
+        vec!["$6$salt$hash"]
+    );
+    assert_eq!(
+        values("service password-encryption", &keywords),
+        Vec::<&str>::new()
+    );
+    assert_eq!(values("no enable secret", &keywords), Vec::<&str>::new());
+    // Only typed keywords take an encryption-type number before the value.
+    assert_eq!(values("snmp-server community 12 RO", &keywords), vec!["12"]);
+}
+
+struct SecretDialect;
+
+impl Dialect for SecretDialect {
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        netform_ir::GenericDialect.classify_trivia(raw)
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        netform_ir::GenericDialect.parse_parts(raw)
+    }
+
+    fn secret_spans(
+        &self,
+        raw: &str,
+        _parsed: Option<&ParsedLineParts>,
+        parents: &[&ParsedLineParts],
+        _trivia: TriviaKind,
+    ) -> Vec<SecretSpan> {
+        // Only count passwords inside a `router` section.
+        if !parents.iter().any(|parent| parent.head == "router") {
+            return Vec::new();
+        }
+        secret_values_after(raw, &[SecretKeyword::typed("password")])
+    }
+}
+
+#[test]
+fn parser_records_dialect_secret_spans_on_lines() {
+    let input = "router bgp 65000\n neighbor 10.0.0.1 password 7 0822455D0A16\nline vty 0\n password 7 0822455D0A16\n";
+    let doc = parse_with_dialect(input, &SecretDialect);
+    assert_eq!(doc.render(), input);
+
+    let Some(Node::Block(bgp)) = doc.node(doc.roots[0]) else {
+        panic!("expected bgp block");
+    };
+    assert!(bgp.header.secrets.is_empty());
+    let Some(Node::Line(neighbor)) = doc.node(bgp.children[0]) else {
+        panic!("expected neighbor line");
+    };
+    assert_eq!(neighbor.secrets, vec![SecretSpan { start: 30, end: 42 }]);
+
+    // Parents come from indentation, so the vty password sits outside `router`.
+    let Some(Node::Block(vty)) = doc.node(doc.roots[1]) else {
+        panic!("expected vty block");
+    };
+    let Some(Node::Line(password)) = doc.node(vty.children[0]) else {
+        panic!("expected password line");
+    };
+    assert!(password.secrets.is_empty());
+}
@@ -0,0 +1,158 @@
+use netform_ir::{Dialect, ParsedLineParts, Path, Selector, TriviaKind, parse_with_dialect};
+
+struct HintDialect;
+
+impl Dialect for HintDialect {
+    fn classify_trivia(&self, raw: &str) -> TriviaKind {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            TriviaKind::Blank
+        } else if trimmed.starts_with('!') {
+            TriviaKind::Comment
+        } else {
+            TriviaKind::Content
+        }
+    }
+
+    fn parse_parts(&self, raw: &str) -> Option<ParsedLineParts> {
+        let mut tokens = raw.split_whitespace().map(ToString::to_string);
+        Some(ParsedLineParts {
+            head: tokens.next()?,
+            args: tokens.collect(),
+        })
+    }
+
+    fn key_hint(
+        &self,
+        _raw: &str,
+        parsed: Option<&ParsedLineParts>,
+        _trivia: TriviaKind,
+    ) -> Option<String> {
+        let parsed = parsed?;
+        match (parsed.head.as_str(), parsed.args.as_slice()) {
+            ("interface", [name, ..]) => Some(format!("interface:{name}")),
+            ("router", [proto, asn, ..]) => Some(format!("router:{proto}:{asn}")),
+            _ => None,
+        }
+    }
+}
+
+const SAMPLE: &str = "\
+interface Ethernet1
+  description \"core-uplink\"
+  shutdown
+interface Ethernet2
+  no shutdown
+interface Port-Channel1
+  shutdown
+router bgp 65000
+  neighbor 10.0.0.1 remote-as 65001
+  address-family ipv4
+    neighbor 10.0.0.2 remote-as 65002
+    neighbor 10.0.0.2 activate
+router bgp 65100
+  neighbor 10.9.9.9 remote-as 65009
+! shutdown all the things
+";
+
+fn paths(selector: &str) -> Vec<Vec<usize>> {
+    let doc = parse_with_dialect(SAMPLE, &HintDialect);
+    let selector = Selector::parse(selector).expect("valid selector");
+    doc.select(&selector)
+        .into_iter()
+        .map(|m| {
+            assert_eq!(doc.resolve(&m.path), Some(m.node));
+            m.path.0
+        })
+        .collect()
+}
+
+#[test]
+fn child_steps_match_tokens_as_prefix() {
+    assert_eq!(
+        paths("interface * > shutdown"),
+        vec![vec![0, 1], vec![2, 0]]
+    );
+    assert_eq!(paths("interface Ethernet* > shutdown"), vec![vec![0, 1]]);
+    assert_eq!(
+        paths("interface Ethernet2 > no shutdown $"),
+        vec![vec![1, 0]]
+    );
+    assert_eq!(
+        paths("interface * > description \"core-uplink\""),
+        vec![vec![0, 0]]
+    );
+}
+
+#[test]
+fn descendant_steps_cross_nesting_levels() {
+    assert_eq!(
+        paths("router bgp 65000 >> neighbor * remote-as *"),
+        vec![vec![3, 0], vec![3, 1, 0]]
+    );
+    assert_eq!(paths(">> neighbor ** activate $"), vec![vec![3, 1, 1]]);
+    assert_eq!(paths(">> shutdown $"), vec![vec![0, 1], vec![2, 0]]);
+}
+
+#[test]
+fn key_hint_and_regex_terms_filter_steps() {
+    assert_eq!(paths("[key=router:bgp:65100] > neighbor"), vec![vec![4, 0]]);
+    assert_eq!(
+        paths("[key=interface:*] /Port-/ > shutdown"),
+        vec![vec![2, 0]]
+    );
+    assert_eq!(paths(">> /remote-as 650\\d2/"), vec![vec![3, 1, 0]]);
+    assert_eq!(paths("/^! /"), vec![vec![5]]);
+}
+
+#[test]
+fn results_are_unique_and_in_render_order() {
+    let doc = parse_with_dialect(SAMPLE, &HintDialect);
+    let selector: Selector = ">> ** >> neighbor".parse().expect("valid selector");
+    let matched = doc.select(&selector);
+
+    assert_eq!(
+        matched.iter().map(|m| m.path.clone()).collect::<Vec<_>>(),
+        vec![
+            Path(vec![3, 0]),
+            Path(vec![3, 1, 0]),
+            Path(vec![3, 1, 1]),
+            Path(vec![4, 0]),
+        ]
+    );
+}
+
+#[test]
+fn malformed_selectors_report_position() {
+    for (input, position) in [
+        ("", 0),
+        ("> interface", 0),
+        ("interface >", 11),
+        ("interface > > shutdown", 12),
+        ("[key=interface:*", 0),
+        ("[name=x]", 0),
+        ("/unterminated", 0),
+        ("/(/", 0),
+        ("interface $ Ethernet1", 12),
+    ] {
+        let err = Selector::parse(input).expect_err(input);
+        assert_eq!(err.position, position, "position for {input:?}");
+    }
+}
+
+#[test]
+fn any_whitespace_separates_selector_terms() {
+    assert_eq!(
+        paths("interface * > shutdown\n"),
+        paths("interface * > shutdown")
+    );
+    assert_eq!(
+        paths("interface *\r\n>\tshutdown"),
+        vec![vec![0, 1], vec![2, 0]]
+    );
+    assert_eq!(
+        paths("interface\u{a0}Ethernet*\u{a0}>\u{a0}shutdown"),
+        vec![vec![0, 1]]
+    );
+    assert!(Selector::parse("\u{a0}\n").is_err());
+}